@@ -0,0 +1,22 @@
+use crate::export::ExportFormat;
+
+use std::path::PathBuf;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Command {
+    Help,
+    ModelImport {
+        source: PathBuf,
+        allow_unverified: bool,
+    },
+    Transcribe {
+        source: PathBuf,
+        format: ExportFormat,
+        diarize: bool,
+    },
+    CompareProcessing {
+        source: PathBuf,
+    },
+    SttWorker,
+    GpuDiagnostics,
+}
@@ -0,0 +1,202 @@
+mod command;
+
+// ---------------------------------------------------------------------------------------------- //
+
+pub(crate) use command::Command;
+
+use crate::{
+    diagnostics::{GPU_DIAGNOSTICS_ARG, report_output, run_gpu_diagnostics},
+    error::{AppError, AppResult},
+    export::ExportFormat,
+    stt::{
+        ModelConfig, STT_WORKER_ARG, compare_processing, import_model, run_stt_worker_process,
+        transcribe_file,
+    },
+};
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+pub(crate) const USAGE: &str = "\
+Usage:
+  auto-scribe                          Start the Auto Scribe app
+  auto-scribe model import <path> [--allow-unverified]
+                                       Install model files from a directory, .tar.gz or .zip;
+                                       a SHA256SUMS manifest is required unless --allow-unverified
+  auto-scribe transcribe <file.wav> [--format text|srt|vtt|json|md] [--diarize]
+                                       Print a transcript of a WAV file, optionally with speakers
+  auto-scribe compare-processing <file.wav>
+                                       Transcribe a WAV file with and without audio processing
+  auto-scribe --stt-worker             Run speech-to-text inference over stdin/stdout (started by the app)
+  auto-scribe --gpu-diagnostics        Print the GPU diagnostics report (started by the app)
+  auto-scribe --help                   Show this help";
+
+pub(crate) fn parse_args(args: impl IntoIterator<Item = OsString>) -> AppResult<Option<Command>> {
+    let args = args.into_iter().collect::<Vec<_>>();
+    let words = args.iter().map(|arg| arg.to_str()).collect::<Vec<_>>();
+
+    match words.as_slice() {
+        [] => Ok(None),
+        [Some("-h" | "--help" | "help")] => Ok(Some(Command::Help)),
+        [Some("model"), Some("import"), _, options @ ..] => args
+            .get(2)
+            .map(|source| parse_model_import_options(source.into(), options))
+            .transpose(),
+        [Some("transcribe"), _, options @ ..] => args
+            .get(1)
+            .map(|source| parse_transcribe_options(source.into(), options))
+            .transpose(),
+        [Some("compare-processing"), _] => {
+            Ok(args.get(1).map(|source| Command::CompareProcessing {
+                source: source.into(),
+            }))
+        }
+        [Some(STT_WORKER_ARG)] => Ok(Some(Command::SttWorker)),
+        [Some(GPU_DIAGNOSTICS_ARG)] => Ok(Some(Command::GpuDiagnostics)),
+        _ => Err(AppError::command_line(format!(
+            "unrecognized arguments: {}",
+            args.iter()
+                .map(|arg| arg.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(" ")
+        ))),
+    }
+}
+
+fn parse_model_import_options(source: PathBuf, options: &[Option<&str>]) -> AppResult<Command> {
+    let mut allow_unverified = false;
+
+    for option in options {
+        match option {
+            Some("--allow-unverified") => allow_unverified = true,
+            _ => {
+                return Err(AppError::command_line(format!(
+                    "unrecognized model import option {}",
+                    option.unwrap_or("(not UTF-8)")
+                )));
+            }
+        }
+    }
+
+    Ok(Command::ModelImport {
+        source,
+        allow_unverified,
+    })
+}
+
+fn parse_transcribe_options(source: PathBuf, options: &[Option<&str>]) -> AppResult<Command> {
+    let mut format = ExportFormat::Text;
+    let mut diarize = false;
+    let mut options = options.iter();
+
+    while let Some(option) = options.next() {
+        match option {
+            Some("--diarize") => diarize = true,
+            Some("--format") => {
+                let Some(Some(format_name)) = options.next() else {
+                    return Err(AppError::command_line("--format needs a transcript format"));
+                };
+                format = ExportFormat::from_name(format_name).ok_or_else(|| {
+                    AppError::command_line(format!(
+                        "unknown transcript format {format_name:?}; expected one of {}",
+                        ExportFormat::ALL.map(ExportFormat::name).join(", ")
+                    ))
+                })?;
+            }
+            _ => {
+                return Err(AppError::command_line(format!(
+                    "unrecognized transcribe option {}",
+                    option.unwrap_or("(not UTF-8)")
+                )));
+            }
+        }
+    }
+
+    Ok(Command::Transcribe {
+        source,
+        format,
+        diarize,
+    })
+}
+
+pub(crate) fn run(command: Command) -> ExitCode {
+    let result = match command {
+        Command::Help => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Command::ModelImport {
+            source,
+            allow_unverified,
+        } => run_model_import(&source, allow_unverified),
+        Command::Transcribe {
+            source,
+            format,
+            diarize,
+        } => run_transcribe(&source, format, diarize),
+        Command::CompareProcessing { source } => run_compare_processing(&source),
+        Command::SttWorker => run_stt_worker_process().map_err(AppError::speech_to_text),
+        Command::GpuDiagnostics => report_output(&run_gpu_diagnostics())
+            .map(|report| print!("{report}"))
+            .map_err(AppError::operation),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            report_error(&error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+pub(crate) fn report_usage_error(error: &AppError) -> ExitCode {
+    report_error(error);
+    eprintln!("{USAGE}");
+    ExitCode::from(2)
+}
+
+fn run_model_import(source: &Path, allow_unverified: bool) -> AppResult<()> {
+    let config = ModelConfig::load().map_err(AppError::model_import)?;
+    println!(
+        "Importing STT model from {} to {}",
+        source.display(),
+        config.model_dir().display()
+    );
+
+    let allow_unverified = allow_unverified || config.allow_unverified_import();
+    let checksum_status =
+        import_model(&config, source, allow_unverified).map_err(AppError::model_import)?;
+    println!(
+        "Installed STT model at {} ({})",
+        config.model_dir().display(),
+        checksum_status.label()
+    );
+    Ok(())
+}
+
+fn run_transcribe(source: &Path, format: ExportFormat, diarize: bool) -> AppResult<()> {
+    let config = ModelConfig::load().map_err(AppError::speech_to_text)?;
+    let transcript = transcribe_file(&config, source, diarize).map_err(AppError::speech_to_text)?;
+    print!("{}", format.render(&transcript));
+    Ok(())
+}
+
+fn run_compare_processing(source: &Path) -> AppResult<()> {
+    let config = ModelConfig::load().map_err(AppError::speech_to_text)?;
+    let (processing, unprocessed, processed) =
+        compare_processing(&config, source).map_err(AppError::speech_to_text)?;
+    println!("Without processing:");
+    println!("{}", unprocessed.text());
+    println!();
+    println!("With processing ({}):", processing.label());
+    println!("{}", processed.text());
+    Ok(())
+}
+
+fn report_error(error: &AppError) {
+    eprintln!("{}: {error}", error.message());
+}
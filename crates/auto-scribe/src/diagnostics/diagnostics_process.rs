@@ -0,0 +1,117 @@
+use crate::diagnostics::{GPU_DIAGNOSTICS_ARG, GpuReport};
+
+use std::{env, process::Command};
+
+/// Printed ahead of the report, so anything a native library writes to stdout first is skipped.
+const REPORT_MARKER: &str = "# auto-scribe gpu report";
+
+/// Runs the diagnostics in a one-off `auto-scribe --gpu-diagnostics` child. Loading CUDA
+/// libraries and running test sessions can crash inside a broken driver; that then ends only the
+/// child, and the crash becomes the report.
+pub(crate) fn run_gpu_diagnostics_process() -> GpuReport {
+    let executable = match env::current_exe() {
+        Ok(executable) => executable,
+        Err(error) => return GpuReport::failed(format!("locate executable: {error}")),
+    };
+    let output = match Command::new(&executable).arg(GPU_DIAGNOSTICS_ARG).output() {
+        Ok(output) => output,
+        Err(error) => {
+            return GpuReport::failed(format!(
+                "start {} {GPU_DIAGNOSTICS_ARG}: {error}",
+                executable.display()
+            ));
+        }
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return GpuReport::failed(match stderr.lines().rfind(|line| !line.trim().is_empty()) {
+            Some(last_line) => format!(
+                "process exited with {}: {}",
+                output.status,
+                last_line.trim()
+            ),
+            None => format!("process exited with {}", output.status),
+        });
+    }
+
+    parse_report_output(&String::from_utf8_lossy(&output.stdout))
+        .unwrap_or_else(|error| GpuReport::failed(format!("unreadable report: {error}")))
+}
+
+/// What `auto-scribe --gpu-diagnostics` prints for `run_gpu_diagnostics_process`.
+pub(crate) fn report_output(report: &GpuReport) -> Result<String, String> {
+    toml::to_string(&report.to_table())
+        .map(|table| format!("{REPORT_MARKER}\n{table}"))
+        .map_err(|error| error.to_string())
+}
+
+fn parse_report_output(stdout: &str) -> Result<GpuReport, String> {
+    let Some((_, report)) = stdout.rsplit_once(REPORT_MARKER) else {
+        return Err("no report in the process output".to_string());
+    };
+    let table = report
+        .parse::<toml::Table>()
+        .map_err(|error| error.to_string())?;
+    GpuReport::from_table(&table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::{LibraryCheck, ProviderProbe, SystemVersions};
+    use std::{path::PathBuf, time::Duration};
+
+    fn report() -> GpuReport {
+        GpuReport {
+            executable_dir: Some(PathBuf::from("/opt/auto-scribe")),
+            provider_libraries: vec![LibraryCheck {
+                name: "libonnxruntime_providers_cuda.so",
+                error: Some(
+                    "not found at /opt/auto-scribe/libonnxruntime_providers_cuda.so".into(),
+                ),
+            }],
+            cuda_libraries: vec![LibraryCheck {
+                name: "libcuda.so.1",
+                error: None,
+            }],
+            versions: SystemVersions {
+                nvidia_driver: Some("550.54".to_string()),
+                cuda_driver_api: None,
+                cuda_runtime: Some("12.4".to_string()),
+                gpus: vec!["NVIDIA RTX A2000, 6138 MiB".to_string()],
+                onnx_runtime: "ONNX Runtime 1.22".to_string(),
+            },
+            provider_probes: vec![
+                ProviderProbe {
+                    provider: "CPU",
+                    result: Ok(Duration::from_micros(1_250)),
+                },
+                ProviderProbe {
+                    provider: "CUDA",
+                    result: Err("CUDA driver version is insufficient".to_string()),
+                },
+            ],
+            failure: None,
+        }
+    }
+
+    #[test]
+    fn reports_round_trip_through_the_process_output() -> Result<(), String> {
+        let output = format!("stray library output\n{}", report_output(&report())?);
+        assert_eq!(parse_report_output(&output)?, report());
+
+        let failed = GpuReport::failed("process exited with signal: 11 (SIGSEGV)".to_string());
+        assert_eq!(parse_report_output(&report_output(&failed)?)?, failed);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_missing_or_foreign_reports() -> Result<(), String> {
+        assert!(parse_report_output("Segmentation fault").is_err());
+
+        let output = report_output(&report())?.replace("libcuda.so.1", "libevil.so");
+        assert!(parse_report_output(&output).is_err());
+        Ok(())
+    }
+}
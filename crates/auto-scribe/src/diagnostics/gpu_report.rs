@@ -0,0 +1,367 @@
+use crate::{
+    diagnostics::{
+        CUDA_LIBRARIES, LibraryCheck, ONNX_PROVIDER_LIBRARIES, ProviderProbe, SystemVersions,
+        check_cuda_libraries, check_provider_libraries, probe_execution_providers,
+    },
+    stt::ExecutionProvider,
+};
+
+use std::{env, path::PathBuf, time::Duration};
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct GpuReport {
+    pub(crate) executable_dir: Option<PathBuf>,
+    pub(crate) provider_libraries: Vec<LibraryCheck>,
+    pub(crate) cuda_libraries: Vec<LibraryCheck>,
+    pub(crate) versions: SystemVersions,
+    pub(crate) provider_probes: Vec<ProviderProbe>,
+    /// Why the diagnostics process gave no report, such as a crash while loading a CUDA library.
+    pub(crate) failure: Option<String>,
+}
+
+pub(crate) fn run_gpu_diagnostics() -> GpuReport {
+    let executable_dir = env::current_exe()
+        .ok()
+        .and_then(|path| path.parent().map(PathBuf::from));
+    let executable_dir_ref = executable_dir.as_deref();
+
+    GpuReport {
+        provider_libraries: if cfg!(feature = "cuda") {
+            check_provider_libraries(executable_dir_ref)
+        } else {
+            Vec::new()
+        },
+        cuda_libraries: if cfg!(feature = "cuda") {
+            check_cuda_libraries(executable_dir_ref)
+        } else {
+            Vec::new()
+        },
+        versions: SystemVersions::detect(executable_dir_ref),
+        provider_probes: probe_execution_providers(),
+        executable_dir,
+        failure: None,
+    }
+}
+
+impl GpuReport {
+    pub(crate) fn failed(reason: String) -> Self {
+        Self {
+            executable_dir: env::current_exe()
+                .ok()
+                .and_then(|path| path.parent().map(PathBuf::from)),
+            provider_libraries: Vec::new(),
+            cuda_libraries: Vec::new(),
+            versions: SystemVersions {
+                onnx_runtime: "unknown".to_string(),
+                ..SystemVersions::default()
+            },
+            provider_probes: Vec::new(),
+            failure: Some(reason),
+        }
+    }
+
+    pub(crate) fn summary(&self) -> String {
+        if let Some(failure) = &self.failure {
+            return format!("Diagnostics failed: {failure}");
+        }
+
+        let missing = self
+            .provider_libraries
+            .iter()
+            .chain(&self.cuda_libraries)
+            .filter(|check| !check.is_ok())
+            .map(|check| check.name)
+            .collect::<Vec<_>>();
+        let failed_probes = self
+            .provider_probes
+            .iter()
+            .filter(|probe| probe.result.is_err())
+            .map(|probe| probe.provider)
+            .collect::<Vec<_>>();
+
+        match (missing.is_empty(), failed_probes.is_empty()) {
+            (true, true) => "All GPU checks passed".to_string(),
+            (true, false) => format!("Test inference failed on {}", failed_probes.join(", ")),
+            (false, _) => format!("Missing {}", missing.join(", ")),
+        }
+    }
+
+    pub(crate) fn to_text(&self) -> String {
+        let mut lines = vec![
+            format!("Auto Scribe {} GPU diagnostics", env!("CARGO_PKG_VERSION")),
+            format!("Summary: {}", self.summary()),
+            format!(
+                "Executable directory: {}",
+                self.executable_dir
+                    .as_ref()
+                    .map(|dir| dir.display().to_string())
+                    .unwrap_or_else(|| "unknown".to_string())
+            ),
+            String::new(),
+            "Versions:".to_string(),
+            format!("  ONNX Runtime: {}", self.versions.onnx_runtime),
+            format!(
+                "  NVIDIA driver: {}",
+                optional_label(&self.versions.nvidia_driver)
+            ),
+            format!(
+                "  CUDA driver API: {}",
+                optional_label(&self.versions.cuda_driver_api)
+            ),
+            format!(
+                "  CUDA runtime: {}",
+                optional_label(&self.versions.cuda_runtime)
+            ),
+        ];
+
+        if self.versions.gpus.is_empty() {
+            lines.push("  GPUs: none reported by nvidia-smi".to_string());
+        }
+        lines.extend(self.versions.gpus.iter().map(|gpu| format!("  GPU: {gpu}")));
+
+        lines.push(String::new());
+        if cfg!(feature = "cuda") {
+            lines.push("ONNX Runtime provider libraries:".to_string());
+            lines.extend(self.provider_libraries.iter().map(library_line));
+
+            lines.push(String::new());
+            lines.push("CUDA libraries:".to_string());
+            lines.extend(self.cuda_libraries.iter().map(library_line));
+        } else {
+            lines.push("CUDA support: not included in this build".to_string());
+        }
+
+        lines.push(String::new());
+        lines.push("Test inference:".to_string());
+        lines.extend(self.provider_probes.iter().map(probe_line));
+
+        lines.join("\n")
+    }
+
+    /// The report as the diagnostics process prints it for the app.
+    pub(crate) fn to_table(&self) -> toml::Table {
+        let mut table = toml::Table::new();
+        if let Some(dir) = &self.executable_dir {
+            table.insert(
+                "executable_dir".to_string(),
+                toml::Value::String(dir.display().to_string()),
+            );
+        }
+        if let Some(failure) = &self.failure {
+            table.insert("failure".to_string(), toml::Value::String(failure.clone()));
+        }
+
+        let versions = &self.versions;
+        let mut versions_table = toml::Table::new();
+        for (key, value) in [
+            ("nvidia_driver", &versions.nvidia_driver),
+            ("cuda_driver_api", &versions.cuda_driver_api),
+            ("cuda_runtime", &versions.cuda_runtime),
+        ] {
+            if let Some(value) = value {
+                versions_table.insert(key.to_string(), toml::Value::String(value.clone()));
+            }
+        }
+        versions_table.insert("gpus".to_string(), string_array(&versions.gpus));
+        versions_table.insert(
+            "onnx_runtime".to_string(),
+            toml::Value::String(versions.onnx_runtime.clone()),
+        );
+        table.insert("versions".to_string(), toml::Value::Table(versions_table));
+
+        table.insert(
+            "provider_libraries".to_string(),
+            library_array(&self.provider_libraries),
+        );
+        table.insert(
+            "cuda_libraries".to_string(),
+            library_array(&self.cuda_libraries),
+        );
+        table.insert(
+            "provider_probes".to_string(),
+            toml::Value::Array(
+                self.provider_probes
+                    .iter()
+                    .map(|probe| {
+                        let mut probe_table = toml::Table::new();
+                        probe_table.insert(
+                            "provider".to_string(),
+                            toml::Value::String(probe.provider.to_string()),
+                        );
+                        match &probe.result {
+                            Ok(elapsed) => probe_table.insert(
+                                "elapsed_us".to_string(),
+                                toml::Value::Integer(
+                                    i64::try_from(elapsed.as_micros()).unwrap_or(i64::MAX),
+                                ),
+                            ),
+                            Err(error) => probe_table
+                                .insert("error".to_string(), toml::Value::String(error.clone())),
+                        };
+                        toml::Value::Table(probe_table)
+                    })
+                    .collect(),
+            ),
+        );
+        table
+    }
+
+    /// Reads the table written by `to_table`. Library and provider names must be ones this
+    /// build knows, since the report only ever comes from the same executable.
+    pub(crate) fn from_table(table: &toml::Table) -> Result<Self, String> {
+        let versions_table = table
+            .get("versions")
+            .and_then(toml::Value::as_table)
+            .ok_or("report has no versions")?;
+        let optional = |key: &str| {
+            versions_table
+                .get(key)
+                .and_then(toml::Value::as_str)
+                .map(str::to_string)
+        };
+        let versions = SystemVersions {
+            nvidia_driver: optional("nvidia_driver"),
+            cuda_driver_api: optional("cuda_driver_api"),
+            cuda_runtime: optional("cuda_runtime"),
+            gpus: versions_table
+                .get("gpus")
+                .and_then(toml::Value::as_array)
+                .ok_or("report has no GPU list")?
+                .iter()
+                .map(|gpu| {
+                    gpu.as_str()
+                        .map(str::to_string)
+                        .ok_or("GPU is not a string")
+                })
+                .collect::<Result<_, _>>()?,
+            onnx_runtime: optional("onnx_runtime").ok_or("report has no ONNX Runtime version")?,
+        };
+
+        let provider_probes = table_array(table, "provider_probes")?
+            .map(|probe| {
+                let label = probe.get("provider").and_then(toml::Value::as_str);
+                let provider = ExecutionProvider::ALL
+                    .into_iter()
+                    .map(ExecutionProvider::label)
+                    .find(|known| Some(*known) == label)
+                    .ok_or_else(|| format!("unknown provider {label:?} in report"))?;
+                let result = match (probe.get("elapsed_us"), probe.get("error")) {
+                    (Some(elapsed), None) => elapsed
+                        .as_integer()
+                        .and_then(|micros| u64::try_from(micros).ok())
+                        .map(|micros| Ok(Duration::from_micros(micros)))
+                        .ok_or("probe time is not a positive integer")?,
+                    (None, Some(error)) => Err(error
+                        .as_str()
+                        .ok_or("probe error is not a string")?
+                        .to_string()),
+                    _ => return Err(format!("probe for {provider} has no result")),
+                };
+                Ok(ProviderProbe { provider, result })
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Self {
+            executable_dir: table
+                .get("executable_dir")
+                .and_then(toml::Value::as_str)
+                .map(PathBuf::from),
+            provider_libraries: library_checks(
+                table,
+                "provider_libraries",
+                &ONNX_PROVIDER_LIBRARIES,
+            )?,
+            cuda_libraries: library_checks(table, "cuda_libraries", &CUDA_LIBRARIES)?,
+            versions,
+            provider_probes,
+            failure: table
+                .get("failure")
+                .and_then(toml::Value::as_str)
+                .map(str::to_string),
+        })
+    }
+}
+
+fn string_array(values: &[String]) -> toml::Value {
+    toml::Value::Array(
+        values
+            .iter()
+            .map(|value| toml::Value::String(value.clone()))
+            .collect(),
+    )
+}
+
+fn library_array(checks: &[LibraryCheck]) -> toml::Value {
+    toml::Value::Array(
+        checks
+            .iter()
+            .map(|check| {
+                let mut check_table = toml::Table::new();
+                check_table.insert(
+                    "name".to_string(),
+                    toml::Value::String(check.name.to_string()),
+                );
+                if let Some(error) = &check.error {
+                    check_table.insert("error".to_string(), toml::Value::String(error.clone()));
+                }
+                toml::Value::Table(check_table)
+            })
+            .collect(),
+    )
+}
+
+fn table_array<'a>(
+    table: &'a toml::Table,
+    key: &str,
+) -> Result<impl Iterator<Item = &'a toml::Table>, String> {
+    let items = table
+        .get(key)
+        .and_then(toml::Value::as_array)
+        .ok_or_else(|| format!("report has no {key}"))?;
+    if items.iter().any(|item| !item.is_table()) {
+        return Err(format!("report {key} must be tables"));
+    }
+    Ok(items.iter().filter_map(toml::Value::as_table))
+}
+
+fn library_checks(
+    table: &toml::Table,
+    key: &str,
+    known: &[&'static str],
+) -> Result<Vec<LibraryCheck>, String> {
+    table_array(table, key)?
+        .map(|check| {
+            let name = check.get("name").and_then(toml::Value::as_str);
+            let name = known
+                .iter()
+                .copied()
+                .find(|known| Some(*known) == name)
+                .ok_or_else(|| format!("unknown library {name:?} in report"))?;
+            Ok(LibraryCheck {
+                name,
+                error: check
+                    .get("error")
+                    .and_then(toml::Value::as_str)
+                    .map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+fn library_line(check: &LibraryCheck) -> String {
+    match &check.error {
+        None => format!("  ok       {}", check.name),
+        Some(error) => format!("  missing  {}: {error}", check.name),
+    }
+}
+
+fn probe_line(probe: &ProviderProbe) -> String {
+    match &probe.result {
+        Ok(elapsed) => format!("  ok       {} ({} ms)", probe.provider, elapsed.as_millis()),
+        Err(error) => format!("  failed   {}: {error}", probe.provider),
+    }
+}
+
+fn optional_label(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("not detected")
+}
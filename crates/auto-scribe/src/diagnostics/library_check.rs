@@ -0,0 +1,51 @@
+use crate::diagnostics::{CUDA_LIBRARIES, ONNX_PROVIDER_LIBRARIES};
+
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct LibraryCheck {
+    pub(crate) name: &'static str,
+    pub(crate) error: Option<String>,
+}
+
+impl LibraryCheck {
+    pub(crate) fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+pub(crate) fn check_provider_libraries(executable_dir: Option<&Path>) -> Vec<LibraryCheck> {
+    ONNX_PROVIDER_LIBRARIES
+        .into_iter()
+        .map(|name| LibraryCheck {
+            name,
+            error: match executable_dir.map(|dir| dir.join(name)) {
+                Some(path) if path.is_file() => None,
+                Some(path) => Some(format!("not found at {}", path.display())),
+                None => Some("executable directory is unknown".to_string()),
+            },
+        })
+        .collect()
+}
+
+pub(crate) fn check_cuda_libraries(executable_dir: Option<&Path>) -> Vec<LibraryCheck> {
+    CUDA_LIBRARIES
+        .into_iter()
+        .map(|name| LibraryCheck {
+            name,
+            error: open_library(name, executable_dir).err(),
+        })
+        .collect()
+}
+
+fn open_library(name: &str, executable_dir: Option<&Path>) -> Result<(), String> {
+    let bundled_path = executable_dir
+        .map(|dir| dir.join(name))
+        .filter(|path| path.is_file());
+    let candidate = bundled_path.unwrap_or_else(|| PathBuf::from(name));
+
+    // Loading runs the library initializers, which is what the CUDA provider does as well.
+    unsafe { libloading::Library::new(&candidate) }
+        .map(drop)
+        .map_err(|error| error.to_string())
+}
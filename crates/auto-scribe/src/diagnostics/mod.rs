@@ -0,0 +1,29 @@
+mod diagnostics_process;
+mod gpu_report;
+mod library_check;
+mod provider_probe;
+mod system_versions;
+
+// ---------------------------------------------------------------------------------------------- //
+
+pub(crate) use diagnostics_process::{report_output, run_gpu_diagnostics_process};
+pub(crate) use gpu_report::{GpuReport, run_gpu_diagnostics};
+pub(crate) use library_check::{LibraryCheck, check_cuda_libraries, check_provider_libraries};
+pub(crate) use provider_probe::{ProviderProbe, probe_execution_providers};
+pub(crate) use system_versions::SystemVersions;
+
+/// Runs the GPU checks and prints the report for the diagnostics window, which starts it.
+pub(crate) const GPU_DIAGNOSTICS_ARG: &str = "--gpu-diagnostics";
+pub(crate) const ONNX_PROVIDER_LIBRARIES: [&str; 2] = [
+    "libonnxruntime_providers_shared.so",
+    "libonnxruntime_providers_cuda.so",
+];
+pub(crate) const CUDA_LIBRARIES: [&str; 7] = [
+    "libcuda.so.1",
+    "libcudart.so.12",
+    "libcublas.so.12",
+    "libcublasLt.so.12",
+    "libcufft.so.11",
+    "libcurand.so.10",
+    "libcudnn.so.9",
+];
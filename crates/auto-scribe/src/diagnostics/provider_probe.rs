@@ -0,0 +1,118 @@
+use crate::stt::ExecutionProvider;
+
+use ort::{ep::ExecutionProviderDispatch, session::Session, value::Tensor};
+use std::time::{Duration, Instant};
+
+const PROBE_INPUT: [f32; 4] = [-1.0, 0.0, 0.5, 2.0];
+const PROBE_EXPECTED: [f32; 4] = [0.0, 0.0, 0.5, 2.0];
+const ONNX_IR_VERSION: u64 = 8;
+const ONNX_OPSET_VERSION: u64 = 13;
+const ONNX_FLOAT: u64 = 1;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ProviderProbe {
+    pub(crate) provider: &'static str,
+    pub(crate) result: Result<Duration, String>,
+}
+
+pub(crate) fn probe_execution_providers() -> Vec<ProviderProbe> {
+    let model = probe_model();
+
+    ExecutionProvider::ALL
+        .into_iter()
+        .filter_map(|provider| {
+            provider.dispatch().map(|dispatch| ProviderProbe {
+                provider: provider.label(),
+                result: run_probe(&model, dispatch.error_on_failure()),
+            })
+        })
+        .collect()
+}
+
+fn run_probe(model: &[u8], provider: ExecutionProviderDispatch) -> Result<Duration, String> {
+    let started = Instant::now();
+    let mut session = Session::builder()
+        .map_err(|error| error.to_string())?
+        .with_execution_providers([provider])
+        .map_err(|error| error.to_string())?
+        .commit_from_memory(model)
+        .map_err(|error| error.to_string())?;
+    let input = Tensor::from_array(([1, PROBE_INPUT.len()], PROBE_INPUT.to_vec()))
+        .map_err(|error| error.to_string())?;
+    let outputs = session
+        .run(ort::inputs!["x" => input])
+        .map_err(|error| error.to_string())?;
+    let (_, values) = outputs["y"]
+        .try_extract_tensor::<f32>()
+        .map_err(|error| error.to_string())?;
+
+    if values != PROBE_EXPECTED {
+        return Err(format!(
+            "unexpected output {values:?}, expected {PROBE_EXPECTED:?}"
+        ));
+    }
+
+    Ok(started.elapsed())
+}
+
+// A single-node `y = Relu(x)` ONNX model, encoded by hand so no model file has to ship.
+fn probe_model() -> Vec<u8> {
+    let node = [
+        bytes_field(1, b"x"),
+        bytes_field(2, b"y"),
+        bytes_field(4, b"Relu"),
+    ]
+    .concat();
+    let graph = [
+        bytes_field(1, &node),
+        bytes_field(2, b"auto-scribe-probe"),
+        bytes_field(11, &value_info(b"x")),
+        bytes_field(12, &value_info(b"y")),
+    ]
+    .concat();
+    let opset = [bytes_field(1, b""), varint_field(2, ONNX_OPSET_VERSION)].concat();
+
+    [
+        varint_field(1, ONNX_IR_VERSION),
+        bytes_field(7, &graph),
+        bytes_field(8, &opset),
+    ]
+    .concat()
+}
+
+fn value_info(name: &[u8]) -> Vec<u8> {
+    let shape = [1, PROBE_INPUT.len() as u64]
+        .into_iter()
+        .flat_map(|dim| bytes_field(1, &varint_field(1, dim)))
+        .collect::<Vec<_>>();
+    let tensor_type = [varint_field(1, ONNX_FLOAT), bytes_field(2, &shape)].concat();
+    let type_proto = bytes_field(1, &tensor_type);
+
+    [bytes_field(1, name), bytes_field(2, &type_proto)].concat()
+}
+
+fn varint_field(field_number: u64, value: u64) -> Vec<u8> {
+    [varint(field_number << 3), varint(value)].concat()
+}
+
+fn bytes_field(field_number: u64, bytes: &[u8]) -> Vec<u8> {
+    [
+        varint((field_number << 3) | 2),
+        varint(bytes.len() as u64),
+        bytes.to_vec(),
+    ]
+    .concat()
+}
+
+fn varint(mut value: u64) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            encoded.push(byte);
+            return encoded;
+        }
+        encoded.push(byte | 0x80);
+    }
+}
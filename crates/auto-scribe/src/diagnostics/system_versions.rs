@@ -0,0 +1,80 @@
+use std::{ffi::c_int, fs, path::Path, process::Command};
+
+const NVIDIA_DRIVER_VERSION_FILE: &str = "/proc/driver/nvidia/version";
+
+type CudaGetVersion = unsafe extern "C" fn(*mut c_int) -> c_int;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct SystemVersions {
+    pub(crate) nvidia_driver: Option<String>,
+    pub(crate) cuda_driver_api: Option<String>,
+    pub(crate) cuda_runtime: Option<String>,
+    pub(crate) gpus: Vec<String>,
+    pub(crate) onnx_runtime: String,
+}
+
+impl SystemVersions {
+    pub(crate) fn detect(executable_dir: Option<&Path>) -> Self {
+        Self {
+            nvidia_driver: nvidia_driver_version(),
+            cuda_driver_api: cuda_version("libcuda.so.1", b"cuDriverGetVersion\0", None),
+            cuda_runtime: cuda_version(
+                "libcudart.so.12",
+                b"cudaRuntimeGetVersion\0",
+                executable_dir,
+            ),
+            gpus: nvidia_smi_gpus(),
+            onnx_runtime: ort::info().to_string(),
+        }
+    }
+}
+
+fn nvidia_driver_version() -> Option<String> {
+    let contents = fs::read_to_string(NVIDIA_DRIVER_VERSION_FILE).ok()?;
+    let first_line = contents.lines().next()?;
+
+    first_line
+        .split_whitespace()
+        .find(|word| word.contains('.') && word.chars().all(|c| c.is_ascii_digit() || c == '.'))
+        .map(str::to_string)
+}
+
+fn cuda_version(library: &str, symbol: &[u8], executable_dir: Option<&Path>) -> Option<String> {
+    let library_path = executable_dir
+        .map(|dir| dir.join(library))
+        .filter(|path| path.is_file())
+        .unwrap_or_else(|| library.into());
+
+    // Both entry points only write an integer such as 12040 and need no prior initialization.
+    let version = unsafe {
+        let library = libloading::Library::new(library_path).ok()?;
+        let get_version = library.get::<CudaGetVersion>(symbol).ok()?;
+        let mut version: c_int = 0;
+        if get_version(&mut version) != 0 {
+            return None;
+        }
+        version
+    };
+
+    Some(format!("{}.{}", version / 1000, (version % 1000) / 10))
+}
+
+fn nvidia_smi_gpus() -> Vec<String> {
+    let Ok(output) = Command::new("nvidia-smi")
+        .args(["--query-gpu=name,memory.total", "--format=csv,noheader"])
+        .output()
+    else {
+        return Vec::new();
+    };
+
+    if !output.status.success() {
+        return Vec::new();
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
@@ -34,6 +34,18 @@ pub(crate) enum AppError {
         message: String,
         location: ErrorLocation,
     },
+
+    #[error("{message} {location}")]
+    CommandLine {
+        message: String,
+        location: ErrorLocation,
+    },
+
+    #[error("{message} {location}")]
+    ModelImport {
+        message: String,
+        location: ErrorLocation,
+    },
 }
 
 impl AppError {
@@ -82,6 +94,22 @@ impl AppError {
         }
     }
 
+    #[track_caller]
+    pub(crate) fn command_line(message: impl Into<String>) -> Self {
+        Self::CommandLine {
+            message: message.into(),
+            location: ErrorLocation::from(Location::caller()),
+        }
+    }
+
+    #[track_caller]
+    pub(crate) fn model_import(error: crate::stt::SttError) -> Self {
+        Self::ModelImport {
+            message: format!("Failed to import model: {error}"),
+            location: ErrorLocation::from(Location::caller()),
+        }
+    }
+
     pub(crate) fn message(&self) -> &str {
         match self {
             Self::Operation { .. } => "Application Error",
@@ -89,6 +117,8 @@ impl AppError {
             Self::MainWindow { .. } => "Main Window Error",
             Self::HotkeyRuntime { .. } => "Hotkey Runtime Error",
             Self::SpeechToText { .. } => "Speech-to-Text Error",
+            Self::CommandLine { .. } => "Command Line Error",
+            Self::ModelImport { .. } => "Model Import Error",
         }
     }
 
@@ -98,7 +128,9 @@ impl AppError {
             | Self::DesktopMetadata { location, .. }
             | Self::MainWindow { location, .. }
             | Self::HotkeyRuntime { location, .. }
-            | Self::SpeechToText { location, .. } => *location,
+            | Self::SpeechToText { location, .. }
+            | Self::CommandLine { location, .. }
+            | Self::ModelImport { location, .. } => *location,
         }
     }
 }
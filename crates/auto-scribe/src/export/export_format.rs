@@ -0,0 +1,70 @@
+use crate::{
+    export::{render_json, render_markdown, render_srt, render_web_vtt},
+    stt::Transcript,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ExportFormat {
+    Text,
+    Srt,
+    WebVtt,
+    Json,
+    Markdown,
+}
+
+impl ExportFormat {
+    pub(crate) const ALL: [Self; 5] = [
+        Self::Text,
+        Self::Srt,
+        Self::WebVtt,
+        Self::Json,
+        Self::Markdown,
+    ];
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| {
+            format.name().eq_ignore_ascii_case(name.trim())
+                || format.extension().eq_ignore_ascii_case(name.trim())
+        })
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Srt => "srt",
+            Self::WebVtt => "webvtt",
+            Self::Json => "json",
+            Self::Markdown => "markdown",
+        }
+    }
+
+    pub(crate) fn extension(self) -> &'static str {
+        match self {
+            Self::Text => "txt",
+            Self::Srt => "srt",
+            Self::WebVtt => "vtt",
+            Self::Json => "json",
+            Self::Markdown => "md",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Text => "Text",
+            Self::Srt => "SRT",
+            Self::WebVtt => "WebVTT",
+            Self::Json => "JSON",
+            Self::Markdown => "Markdown",
+        }
+    }
+
+    pub(crate) fn render(self, transcript: &Transcript) -> String {
+        match self {
+            Self::Text => format!("{}\n", transcript.labelled_text()),
+            Self::Srt => render_srt(transcript),
+            Self::WebVtt => render_web_vtt(transcript),
+            Self::Json => render_json(transcript),
+            Self::Markdown => render_markdown(transcript),
+        }
+    }
+}
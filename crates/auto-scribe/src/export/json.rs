@@ -0,0 +1,72 @@
+use crate::stt::{Transcript, TranscriptSegment, TranscriptWord};
+
+use std::time::Duration;
+
+pub(crate) fn render_json(transcript: &Transcript) -> String {
+    let speakers = transcript
+        .speakers
+        .iter()
+        .map(|speaker| json_string(speaker))
+        .collect::<Vec<_>>();
+    let segments = transcript
+        .segments
+        .iter()
+        .map(|segment| segment_json(segment, transcript.speaker_name(segment)))
+        .collect::<Vec<_>>();
+
+    format!(
+        "{{\n  \"text\": {},\n  \"duration\": {},\n  \"speakers\": [{}],\n  \"segments\": [{}\n  ]\n}}\n",
+        json_string(&transcript.text()),
+        seconds(transcript.duration()),
+        speakers.join(", "),
+        segments.join(",")
+    )
+}
+
+fn segment_json(segment: &TranscriptSegment, speaker: Option<&str>) -> String {
+    let words = segment.words.iter().map(word_json).collect::<Vec<_>>();
+    let speaker = speaker
+        .map(json_string)
+        .unwrap_or_else(|| "null".to_string());
+
+    format!(
+        "\n    {{\n      \"start\": {},\n      \"end\": {},\n      \"speaker\": {speaker},\n      \"text\": {},\n      \"words\": [{}\n      ]\n    }}",
+        seconds(segment.start),
+        seconds(segment.end),
+        json_string(&segment.text()),
+        words.join(",")
+    )
+}
+
+fn word_json(word: &TranscriptWord) -> String {
+    format!(
+        "\n        {{ \"text\": {}, \"start\": {}, \"end\": {} }}",
+        json_string(&word.text),
+        seconds(word.start),
+        seconds(word.end)
+    )
+}
+
+fn seconds(offset: Duration) -> String {
+    format!("{:.3}", offset.as_secs_f64())
+}
+
+fn json_string(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            character if character.is_control() => {
+                output.push_str(&format!("\\u{:04x}", u32::from(character)));
+            }
+            character => output.push(character),
+        }
+    }
+    output.push('"');
+    output
+}
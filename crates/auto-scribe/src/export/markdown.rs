@@ -0,0 +1,23 @@
+use crate::{export::clock_timestamp, stt::Transcript};
+
+pub(crate) fn render_markdown(transcript: &Transcript) -> String {
+    let mut output = "# Transcript\n\n".to_string();
+    let segments = transcript
+        .segments
+        .iter()
+        .filter(|segment| !segment.words.is_empty());
+
+    for segment in segments {
+        let start = clock_timestamp(segment.start, '.');
+        let end = clock_timestamp(segment.end, '.');
+        match transcript.speaker_name(segment) {
+            Some(speaker) => output.push_str(&format!(
+                "- **{start} – {end}** *{speaker}:* {}\n",
+                segment.text()
+            )),
+            None => output.push_str(&format!("- **{start} – {end}** {}\n", segment.text())),
+        }
+    }
+
+    output
+}
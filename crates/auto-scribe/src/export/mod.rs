@@ -0,0 +1,18 @@
+mod export_format;
+mod json;
+mod markdown;
+mod srt;
+mod timestamp;
+mod web_vtt;
+
+#[cfg(test)]
+mod tests;
+
+// ---------------------------------------------------------------------------------------------- //
+
+pub(crate) use export_format::ExportFormat;
+pub(crate) use json::render_json;
+pub(crate) use markdown::render_markdown;
+pub(crate) use srt::render_srt;
+pub(crate) use timestamp::clock_timestamp;
+pub(crate) use web_vtt::render_web_vtt;
@@ -0,0 +1,26 @@
+use crate::{export::clock_timestamp, stt::Transcript};
+
+pub(crate) fn render_srt(transcript: &Transcript) -> String {
+    let mut output = String::new();
+    let cues = transcript
+        .segments
+        .iter()
+        .filter(|segment| !segment.words.is_empty());
+
+    for (index, segment) in cues.enumerate() {
+        let text = match transcript.speaker_name(segment) {
+            Some(speaker) => format!("{speaker}: {}", segment.text()),
+            None => segment.text(),
+        };
+        // A line with "-->" would be read as the next cue's timing line.
+        let text = text.replace("-->", "->");
+        output.push_str(&format!(
+            "{}\n{} --> {}\n{text}\n\n",
+            index + 1,
+            clock_timestamp(segment.start, ','),
+            clock_timestamp(segment.end, ',')
+        ));
+    }
+
+    output
+}
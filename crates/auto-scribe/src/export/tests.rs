@@ -0,0 +1,131 @@
+use crate::{
+    export::ExportFormat,
+    stt::{Transcript, TranscriptSegment, TranscriptWord},
+};
+
+use std::time::Duration;
+
+/// Golden files live in `export/golden/`; a change to any renderer must update them.
+const GOLDEN: [(&str, ExportFormat, &str); 8] = [
+    (
+        "plain.srt",
+        ExportFormat::Srt,
+        include_str!("golden/plain.srt"),
+    ),
+    (
+        "plain.vtt",
+        ExportFormat::WebVtt,
+        include_str!("golden/plain.vtt"),
+    ),
+    (
+        "plain.json",
+        ExportFormat::Json,
+        include_str!("golden/plain.json"),
+    ),
+    (
+        "plain.md",
+        ExportFormat::Markdown,
+        include_str!("golden/plain.md"),
+    ),
+    (
+        "diarized.srt",
+        ExportFormat::Srt,
+        include_str!("golden/diarized.srt"),
+    ),
+    (
+        "diarized.vtt",
+        ExportFormat::WebVtt,
+        include_str!("golden/diarized.vtt"),
+    ),
+    (
+        "diarized.json",
+        ExportFormat::Json,
+        include_str!("golden/diarized.json"),
+    ),
+    (
+        "diarized.md",
+        ExportFormat::Markdown,
+        include_str!("golden/diarized.md"),
+    ),
+];
+
+fn word(text: &str, start_ms: u64, end_ms: u64) -> TranscriptWord {
+    TranscriptWord {
+        text: text.to_string(),
+        start: Duration::from_millis(start_ms),
+        end: Duration::from_millis(end_ms),
+    }
+}
+
+fn segment(words: Vec<TranscriptWord>, speaker: Option<usize>) -> TranscriptSegment {
+    TranscriptSegment {
+        start: words.first().map(|word| word.start).unwrap_or_default(),
+        end: words.last().map(|word| word.end).unwrap_or_default(),
+        words,
+        speaker,
+    }
+}
+
+/// Covers escaping, a segment without words, and an offset past one hour.
+fn plain_transcript() -> Transcript {
+    Transcript {
+        segments: vec![
+            segment(
+                vec![word("Hello", 0, 560), word("world.", 560, 1_120)],
+                None,
+            ),
+            segment(Vec::new(), None),
+            segment(
+                vec![
+                    word("Is", 1_680, 2_240),
+                    word("a", 1_680, 2_240),
+                    word("<", 2_240, 2_800),
+                    word("b", 2_240, 2_800),
+                    word("&", 2_800, 3_360),
+                    word("c", 2_800, 3_360),
+                    word("-->", 3_360, 3_920),
+                    word("\"d\"?", 3_360, 3_920),
+                ],
+                None,
+            ),
+            segment(vec![word("Later", 3_661_500, 3_662_060)], None),
+        ],
+        speakers: Vec::new(),
+    }
+}
+
+fn diarized_transcript() -> Transcript {
+    Transcript {
+        segments: vec![
+            segment(
+                vec![word("Hi", 0, 560), word("there.", 560, 1_120)],
+                Some(0),
+            ),
+            segment(vec![word("Hello", 1_120, 1_680)], Some(1)),
+            segment(vec![word("Bye.", 1_680, 2_240)], Some(0)),
+        ],
+        speakers: vec!["Ada".to_string(), "Bo & <Cy>".to_string()],
+    }
+}
+
+#[test]
+fn renderers_match_golden_files() {
+    for (name, format, expected) in GOLDEN {
+        let transcript = if name.starts_with("plain") {
+            plain_transcript()
+        } else {
+            diarized_transcript()
+        };
+
+        assert_eq!(format.render(&transcript), expected, "golden file {name}");
+    }
+}
+
+#[test]
+fn web_vtt_escapes_cue_text() {
+    let rendered = ExportFormat::WebVtt.render(&plain_transcript());
+
+    assert!(rendered.contains("Is a &lt; b &amp; c --&gt; \"d\"?"));
+    assert!(!rendered.contains(" < "));
+    assert!(!rendered.contains(" & "));
+}
@@ -0,0 +1,15 @@
+use std::time::Duration;
+
+/// Formats an offset as `HH:MM:SS` followed by `separator` and milliseconds, as subtitle
+/// formats expect: SRT uses a comma and WebVTT a full stop.
+pub(crate) fn clock_timestamp(offset: Duration, separator: char) -> String {
+    let millis = offset.as_millis();
+    let seconds = millis / 1000;
+    format!(
+        "{:02}:{:02}:{:02}{separator}{:03}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        millis % 1000
+    )
+}
@@ -0,0 +1,33 @@
+use crate::{export::clock_timestamp, stt::Transcript};
+
+pub(crate) fn render_web_vtt(transcript: &Transcript) -> String {
+    let mut output = "WEBVTT\n\n".to_string();
+    let cues = transcript
+        .segments
+        .iter()
+        .filter(|segment| !segment.words.is_empty());
+
+    for segment in cues {
+        let text = escape_cue_text(&segment.text());
+        let text = match transcript.speaker_name(segment) {
+            Some(speaker) => format!("<v {}>{text}", escape_cue_text(speaker)),
+            None => text,
+        };
+        output.push_str(&format!(
+            "{} --> {}\n{text}\n\n",
+            clock_timestamp(segment.start, '.'),
+            clock_timestamp(segment.end, '.')
+        ));
+    }
+
+    output
+}
+
+/// Cue text and voice names may not contain `&`, `<` or `-->`, so they are escaped; `>` is
+/// escaped too so a voice name cannot close its `<v ...>` tag.
+fn escape_cue_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace("-->", "->")
+}
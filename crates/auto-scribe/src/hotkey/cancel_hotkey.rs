@@ -0,0 +1,46 @@
+use crate::error::{AppResult, ResultContext};
+
+use global_hotkey::hotkey::HotKey;
+use std::str::FromStr;
+
+#[derive(Clone, Debug)]
+pub(crate) struct CancelHotkey {
+    label: String,
+}
+
+impl CancelHotkey {
+    pub(crate) fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+        }
+    }
+
+    pub(crate) fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub(crate) fn global_hotkey(&self) -> AppResult<HotKey> {
+        HotKey::from_str(&self.label)
+            .with_context(|| format!("parse cancel hotkey {:?}", self.label))
+    }
+
+    /// Converts `Ctrl+Alt+Backspace` into the `CTRL+ALT+BackSpace` form used by portal triggers.
+    pub(crate) fn portal_trigger(&self) -> String {
+        self.label
+            .split('+')
+            .map(str::trim)
+            .map(|token| match token.to_ascii_uppercase().as_str() {
+                "CTRL" | "CONTROL" => "CTRL".to_string(),
+                "ALT" | "OPTION" => "ALT".to_string(),
+                "SHIFT" => "SHIFT".to_string(),
+                "SUPER" | "LOGO" | "META" | "CMD" | "COMMAND" => "LOGO".to_string(),
+                "BACKSPACE" => "BackSpace".to_string(),
+                "ESC" | "ESCAPE" => "Escape".to_string(),
+                "SPACE" => "space".to_string(),
+                _ if token.chars().count() == 1 => token.to_ascii_lowercase(),
+                _ => token.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("+")
+    }
+}
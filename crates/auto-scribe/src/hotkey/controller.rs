@@ -1,13 +1,28 @@
 use crate::{
+    export::ExportFormat,
     hotkey::{BackendKind, Event, HOTKEY_LABEL, Runtime, RuntimeEvent, Snapshot},
-    stt::{Session, WorkerEvent},
-    windows::{HotkeyWindow, open_hotkey_window},
+    output::{OutputMethod, OutputProfile, focused_app_ids, type_text},
+    stt::{
+        ExecutionProvider, HistoryEntry, InstalledModel, Session, SpeakerMode, WorkerEvent,
+        WorkerHealth, toggle_execution_provider,
+    },
+    windows::{
+        HotkeyWindow, open_diagnostics_window, open_history_window, open_hotkey_window,
+        open_models_window, open_output_profiles_window,
+    },
 };
 
-use gpui::{ClipboardItem, Context, Subscription, Task, WindowHandle};
-use std::{borrow::BorrowMut, time::Duration};
+use gpui::{ClipboardItem, Context, PathPromptOptions, Subscription, Task, WindowHandle};
+use gpui_component::Root;
+use std::{
+    borrow::BorrowMut,
+    env, fs,
+    path::{Path, PathBuf},
+    time::{Duration, UNIX_EPOCH},
+};
 
 const TRANSCRIPT_POPUP_CLOSE_DELAY: Duration = Duration::from_secs(5);
+const MEETING_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
 pub(crate) struct Controller {
     runtime: Option<Runtime>,
@@ -16,8 +31,15 @@ pub(crate) struct Controller {
     window_closed_subscription: Option<Subscription>,
     backend_kind: BackendKind,
     popup_window: Option<WindowHandle<HotkeyWindow>>,
+    models_window: Option<WindowHandle<Root>>,
+    diagnostics_window: Option<WindowHandle<Root>>,
+    history_window: Option<WindowHandle<Root>>,
+    output_profiles_window: Option<WindowHandle<Root>>,
     popup_visible: bool,
     popup_close_generation: u64,
+    idle_unload_generation: u64,
+    worker_restart_generation: u64,
+    meeting_generation: u64,
     is_hotkey_down: bool,
     status: String,
     stt: Session,
@@ -32,8 +54,15 @@ impl Controller {
             window_closed_subscription: None,
             backend_kind,
             popup_window: None,
+            models_window: None,
+            diagnostics_window: None,
+            history_window: None,
+            output_profiles_window: None,
             popup_visible: false,
             popup_close_generation: 0,
+            idle_unload_generation: 0,
+            worker_restart_generation: 0,
+            meeting_generation: 0,
             is_hotkey_down: false,
             status: format!("Starting {} backend", backend_kind.label()),
             stt,
@@ -59,15 +88,26 @@ impl Controller {
             stt_status: stt.status,
             stt_model_can_download: stt.model_can_download,
             stt_model_downloading: stt.model_downloading,
+            stt_model_importing: stt.model_importing,
             stt_model_download_files_percent: stt.model_download_files_percent,
             stt_model_download_files_label: stt.model_download_files_label,
             stt_model_download_file_percent: stt.model_download_file_percent,
             stt_model_download_file_known: stt.model_download_file_known,
             stt_model_download_file_label: stt.model_download_file_label,
+            stt_model_unloaded: stt.model_unloaded,
+            stt_worker_restarting: stt.worker_restarting,
             stt_model_dir: stt.model_dir,
             stt_config_path: stt.config_path,
-            stt_use_gpu: stt.use_gpu,
-            stt_auto_mute_speakers: stt.auto_mute_speakers,
+            stt_enabled_providers: stt.enabled_providers,
+            stt_execution_providers: stt.execution_providers,
+            stt_active_provider: stt.active_provider,
+            stt_provider_fallback: stt.provider_fallback,
+            stt_onnx_threads: stt.onnx_threads,
+            stt_graph_optimization: stt.graph_optimization,
+            stt_optimized_model_cache: stt.optimized_model_cache,
+            stt_speaker_mode: stt.speaker_mode,
+            stt_pause_media: stt.pause_media,
+            stt_meeting_label: stt.meeting_label,
         }
     }
 
@@ -90,6 +130,7 @@ impl Controller {
         match event {
             RuntimeEvent::Hotkey(Event::Pressed) => self.hotkey_pressed(cx),
             RuntimeEvent::Hotkey(Event::Released) => self.hotkey_released(cx),
+            RuntimeEvent::Hotkey(Event::Cancel) => self.cancel(cx),
             RuntimeEvent::Status(message) => {
                 self.status = message;
                 cx.notify();
@@ -102,38 +143,297 @@ impl Controller {
     }
 
     pub(crate) fn apply_stt_event(&mut self, event: WorkerEvent, cx: &mut Context<Self>) {
-        let completed_transcript = match &event {
-            WorkerEvent::Transcript(transcript) => Some(transcript.clone()),
-            WorkerEvent::Ready
-            | WorkerEvent::Error(_)
-            | WorkerEvent::ModelDownloadProgress { .. }
-            | WorkerEvent::ModelDownloadFinished
-            | WorkerEvent::ModelDownloadError(_) => None,
-        };
-
-        self.stt.apply_worker_event(event);
+        let completed_transcript = self.stt.apply_worker_event(event);
         self.update_popup_content(cx);
+        self.schedule_idle_unload(cx);
+        self.schedule_worker_restart(cx);
+        self.sync_escape_cancel();
 
         if let Some(transcript) = completed_transcript {
-            self.copy_transcript_to_clipboard(transcript, cx);
+            self.deliver_transcript(transcript, cx);
             self.schedule_popup_close_after_transcript(cx);
         }
 
         cx.notify();
     }
 
+    pub(crate) fn cancel(&mut self, cx: &mut Context<Self>) {
+        if !self.stt.cancel() {
+            return;
+        }
+
+        self.status = "Cancelled".to_string();
+        self.update_popup_content(cx);
+        self.hide_popup(cx);
+        self.schedule_idle_unload(cx);
+        cx.notify();
+    }
+
     pub(crate) fn download_model(&mut self, cx: &mut Context<Self>) {
         self.stt.start_model_download();
         cx.notify();
     }
 
-    pub(crate) fn set_auto_mute_speakers(&mut self, enabled: bool, cx: &mut Context<Self>) {
-        self.stt.set_auto_mute_speakers(enabled);
+    pub(crate) fn import_model(&mut self, cx: &mut Context<Self>) {
+        let selected_paths = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: true,
+            multiple: false,
+            prompt: Some("Import model".into()),
+        });
+
+        cx.spawn(async move |controller, cx| {
+            let Ok(Ok(Some(paths))) = selected_paths.await else {
+                return;
+            };
+            let Some(source) = paths.into_iter().next() else {
+                return;
+            };
+
+            let _ = controller.update(cx, |controller, cx| {
+                controller.stt.start_model_import(source);
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    pub(crate) fn installed_models(&self) -> Vec<InstalledModel> {
+        self.stt.installed_models().to_vec()
+    }
+
+    pub(crate) fn models_root(&self) -> PathBuf {
+        self.stt.models_root()
+    }
+
+    pub(crate) fn transcript_history(&self) -> Vec<HistoryEntry> {
+        self.stt.history().cloned().collect()
+    }
+
+    pub(crate) fn rename_speaker(
+        &mut self,
+        entry_id: usize,
+        speaker: usize,
+        name: &str,
+        cx: &mut Context<Self>,
+    ) {
+        if self.stt.rename_speaker(entry_id, speaker, name) {
+            cx.notify();
+        }
+    }
+
+    pub(crate) fn export_transcript(
+        &mut self,
+        entry_id: usize,
+        format: ExportFormat,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry) = self.stt.history_entry(entry_id) else {
+            return;
+        };
+        let contents = format.render(&entry.transcript);
+        let completed_at = entry
+            .completed_at
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        let file_name = format!("transcript-{completed_at}.{}", format.extension());
+        let directory = env::var_os("HOME").map_or_else(|| PathBuf::from("."), PathBuf::from);
+        let selected_path = cx.prompt_for_new_path(&directory, Some(&file_name));
+
+        cx.spawn(async move |controller, cx| {
+            let Ok(Ok(Some(path))) = selected_path.await else {
+                return;
+            };
+
+            let status = match fs::write(&path, contents) {
+                Ok(()) => format!("Exported {} to {}", format.label(), path.display()),
+                Err(error) => format!("Failed to export {}: {error}", path.display()),
+            };
+            let _ = controller.update(cx, |controller, cx| {
+                controller.status = status;
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    pub(crate) fn retranscribe_recording(&mut self, entry_id: usize, cx: &mut Context<Self>) {
+        let Some(path) = self
+            .stt
+            .history_entry(entry_id)
+            .and_then(|entry| entry.recording_path.clone())
+        else {
+            return;
+        };
+
+        self.status = format!("Re-transcribing {}", path.display());
+        self.stt.retranscribe_recording(path);
+        self.schedule_idle_unload(cx);
+        self.schedule_worker_restart(cx);
+        self.sync_escape_cancel();
+        cx.notify();
+    }
+
+    pub(crate) fn start_meeting(&mut self, cx: &mut Context<Self>) {
+        self.stt.start_meeting();
+        if self.stt.meeting_active() {
+            self.schedule_meeting_poll(cx);
+        }
+        self.schedule_worker_restart(cx);
+        cx.notify();
+    }
+
+    pub(crate) fn stop_meeting(&mut self, cx: &mut Context<Self>) {
+        self.meeting_generation = self.meeting_generation.wrapping_add(1);
+        self.stt.stop_meeting();
+        self.schedule_idle_unload(cx);
+        self.schedule_worker_restart(cx);
         cx.notify();
     }
 
-    pub(crate) fn set_use_gpu(&mut self, enabled: bool, cx: &mut Context<Self>) {
-        self.stt.set_use_gpu(enabled);
+    pub(crate) fn worker_health(&self) -> WorkerHealth {
+        self.stt.worker_health().clone()
+    }
+
+    pub(crate) fn open_models_window(&mut self, cx: &mut Context<Self>) {
+        self.stt.refresh_installed_models();
+
+        if let Some(window) = self.models_window
+            && window
+                .update(cx, |_, window, _| window.activate_window())
+                .is_ok()
+        {
+            cx.notify();
+            return;
+        }
+
+        let controller = cx.entity();
+        match open_models_window(cx.borrow_mut(), controller) {
+            Ok(window) => self.models_window = Some(window),
+            Err(error) => {
+                self.models_window = None;
+                self.status = format!("Failed to open models window: {error}");
+            }
+        }
+
+        cx.notify();
+    }
+
+    pub(crate) fn open_diagnostics_window(&mut self, cx: &mut Context<Self>) {
+        if let Some(window) = self.diagnostics_window
+            && window
+                .update(cx, |_, window, _| window.activate_window())
+                .is_ok()
+        {
+            return;
+        }
+
+        let controller = cx.entity();
+        match open_diagnostics_window(cx.borrow_mut(), controller) {
+            Ok(window) => self.diagnostics_window = Some(window),
+            Err(error) => {
+                self.diagnostics_window = None;
+                self.status = format!("Failed to open diagnostics window: {error}");
+            }
+        }
+
+        cx.notify();
+    }
+
+    pub(crate) fn open_history_window(&mut self, cx: &mut Context<Self>) {
+        if let Some(window) = self.history_window
+            && window
+                .update(cx, |_, window, _| window.activate_window())
+                .is_ok()
+        {
+            return;
+        }
+
+        let controller = cx.entity();
+        match open_history_window(cx.borrow_mut(), controller) {
+            Ok(window) => self.history_window = Some(window),
+            Err(error) => {
+                self.history_window = None;
+                self.status = format!("Failed to open history window: {error}");
+            }
+        }
+
+        cx.notify();
+    }
+
+    pub(crate) fn open_output_profiles_window(&mut self, cx: &mut Context<Self>) {
+        if let Some(window) = self.output_profiles_window
+            && window
+                .update(cx, |_, window, _| window.activate_window())
+                .is_ok()
+        {
+            return;
+        }
+
+        let controller = cx.entity();
+        match open_output_profiles_window(cx.borrow_mut(), controller) {
+            Ok(window) => self.output_profiles_window = Some(window),
+            Err(error) => {
+                self.output_profiles_window = None;
+                self.status = format!("Failed to open output profiles window: {error}");
+            }
+        }
+
+        cx.notify();
+    }
+
+    pub(crate) fn output_profiles(&self) -> Vec<OutputProfile> {
+        self.stt.output_profiles().to_vec()
+    }
+
+    pub(crate) fn save_output_profile(
+        &mut self,
+        index: Option<usize>,
+        profile: OutputProfile,
+        cx: &mut Context<Self>,
+    ) {
+        self.stt.save_output_profile(index, profile);
+        cx.notify();
+    }
+
+    pub(crate) fn delete_output_profile(&mut self, index: usize, cx: &mut Context<Self>) {
+        self.stt.delete_output_profile(index);
+        cx.notify();
+    }
+
+    pub(crate) fn refresh_models(&mut self, cx: &mut Context<Self>) {
+        self.stt.refresh_installed_models();
+        cx.notify();
+    }
+
+    pub(crate) fn delete_model(&mut self, model_dir: &Path, cx: &mut Context<Self>) {
+        self.stt.delete_model(model_dir);
+        cx.notify();
+    }
+
+    pub(crate) fn redownload_model(&mut self, cx: &mut Context<Self>) {
+        self.stt.redownload_model();
+        cx.notify();
+    }
+
+    pub(crate) fn set_speaker_mode(&mut self, mode: SpeakerMode, cx: &mut Context<Self>) {
+        self.stt.set_speaker_mode(mode);
+        cx.notify();
+    }
+
+    pub(crate) fn set_pause_media(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        self.stt.set_pause_media(enabled);
+        cx.notify();
+    }
+
+    pub(crate) fn toggle_execution_provider(
+        &mut self,
+        provider: ExecutionProvider,
+        cx: &mut Context<Self>,
+    ) {
+        let providers = toggle_execution_provider(self.stt.execution_providers(), provider);
+        self.stt.set_execution_providers(&providers);
         cx.notify();
     }
 
@@ -149,14 +449,35 @@ impl Controller {
             cx.notify();
         }
 
-        let only_hidden_popup_remains = self.popup_window.is_some_and(|popup_window| {
-            cx.windows()
+        for auxiliary_window in [
+            &mut self.models_window,
+            &mut self.diagnostics_window,
+            &mut self.history_window,
+            &mut self.output_profiles_window,
+        ] {
+            if auxiliary_window.is_some_and(|handle| handle.update(cx, |_, _, _| ()).is_err()) {
+                *auxiliary_window = None;
+            }
+        }
+
+        let auxiliary_window_ids = self
+            .popup_window
+            .map(|window| window.window_id())
+            .into_iter()
+            .chain(self.models_window.map(|window| window.window_id()))
+            .chain(self.diagnostics_window.map(|window| window.window_id()))
+            .chain(self.history_window.map(|window| window.window_id()))
+            .chain(self.output_profiles_window.map(|window| window.window_id()))
+            .collect::<Vec<_>>();
+        let only_auxiliary_windows_remain = self.popup_window.is_some()
+            && cx
+                .windows()
                 .into_iter()
-                .all(|window| window.window_id() == popup_window.window_id())
-        });
+                .all(|window| auxiliary_window_ids.contains(&window.window_id()));
 
-        if only_hidden_popup_remains {
+        if only_auxiliary_windows_remain {
             self.stt.stop_recording_for_shutdown();
+            self.stt.stop_meeting();
             cx.quit();
         }
     }
@@ -175,6 +496,7 @@ impl Controller {
             self.stt.popup_opened();
             self.update_popup_content(cx);
         }
+        self.sync_escape_cancel();
 
         cx.notify();
     }
@@ -184,6 +506,8 @@ impl Controller {
         self.status = format!("Waiting for {}", HOTKEY_LABEL);
         self.stt.popup_released();
         self.update_popup_content(cx);
+        self.schedule_idle_unload(cx);
+        self.schedule_worker_restart(cx);
 
         if !self.stt.should_keep_popup_open_after_release() {
             self.hide_popup(cx);
@@ -209,8 +533,10 @@ impl Controller {
         if self.popup_window.is_none() {
             let popup_label = self.stt.popup_label();
             let popup_transcript = self.stt.popup_transcript();
+            let controller = cx.entity().downgrade();
             match open_hotkey_window(
                 cx.borrow_mut(),
+                controller,
                 self.backend_kind,
                 popup_label,
                 popup_transcript,
@@ -236,9 +562,10 @@ impl Controller {
 
         let popup_label = self.stt.popup_label();
         let popup_transcript = self.stt.popup_transcript();
+        let cancellable = self.stt.can_cancel();
         if window
             .update(cx, |popup, _, cx| {
-                popup.set_content(popup_label, popup_transcript, cx);
+                popup.set_content(popup_label, popup_transcript, cancellable, cx);
             })
             .is_err()
         {
@@ -247,8 +574,95 @@ impl Controller {
         }
     }
 
+    /// Applies the output profile matching the focused window; without one the transcript is
+    /// copied unchanged. Asking the compositor or X server for the focused window runs external
+    /// commands, so that happens on the background executor.
+    fn deliver_transcript(&mut self, transcript: String, cx: &mut Context<Self>) {
+        if self.stt.output_profiles().is_empty() {
+            self.copy_transcript_to_clipboard(transcript, cx);
+            return;
+        }
+
+        let lookup = cx
+            .background_executor()
+            .spawn(async move { focused_app_ids() });
+        cx.spawn(async move |controller, cx| {
+            let app_ids = lookup.await;
+            let _ = controller.update(cx, |controller, cx| {
+                controller.deliver_transcript_to(&app_ids, transcript, cx);
+            });
+        })
+        .detach();
+    }
+
+    fn deliver_transcript_to(
+        &mut self,
+        app_ids: &[String],
+        transcript: String,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(profile) = self
+            .stt
+            .output_profiles()
+            .iter()
+            .find(|profile| profile.matches(app_ids))
+            .cloned()
+        else {
+            self.copy_transcript_to_clipboard(transcript, cx);
+            return;
+        };
+
+        let text = profile.format(&transcript);
+        match profile.output {
+            OutputMethod::Clipboard => self.copy_transcript_to_clipboard(text, cx),
+            OutputMethod::Type => self.type_transcript(profile.name, text, cx),
+        }
+    }
+
     fn copy_transcript_to_clipboard(&mut self, transcript: String, cx: &mut Context<Self>) {
         cx.write_to_clipboard(ClipboardItem::new_string(transcript));
+        self.set_popup_delivery_label("Copied to clipboard", cx);
+    }
+
+    /// Typing a long transcript takes a while, so it runs off the UI thread. If it fails the
+    /// text is copied instead.
+    fn type_transcript(&mut self, profile_name: String, text: String, cx: &mut Context<Self>) {
+        self.set_popup_delivery_label("Typed into the focused window", cx);
+        let typing = {
+            let text = text.clone();
+            cx.background_executor()
+                .spawn(async move { type_text(&text) })
+        };
+
+        cx.spawn(async move |controller, cx| {
+            let Err(error) = typing.await else {
+                return;
+            };
+            let _ = controller.update(cx, |controller, cx| {
+                controller.copy_transcript_to_clipboard(text, cx);
+                controller.status = format!(
+                    "Typing for output profile {profile_name} failed; copied to clipboard instead: {error}"
+                );
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    fn set_popup_delivery_label(&mut self, delivery_label: &'static str, cx: &mut Context<Self>) {
+        let Some(window) = self.popup_window else {
+            return;
+        };
+
+        if window
+            .update(cx, |popup, _, cx| {
+                popup.set_delivery_label(delivery_label, cx)
+            })
+            .is_err()
+        {
+            self.popup_window = None;
+            self.popup_visible = false;
+        }
     }
 
     fn schedule_popup_close_after_transcript(&mut self, cx: &mut Context<Self>) {
@@ -267,7 +681,10 @@ impl Controller {
     }
 
     fn hide_popup_after_transcript(&mut self, generation: u64, cx: &mut Context<Self>) {
-        if self.is_hotkey_down || self.popup_close_generation != generation {
+        if self.is_hotkey_down
+            || self.popup_close_generation != generation
+            || self.stt.should_keep_popup_open_after_release()
+        {
             return;
         }
 
@@ -275,8 +692,97 @@ impl Controller {
         cx.notify();
     }
 
+    fn schedule_idle_unload(&mut self, cx: &mut Context<Self>) {
+        self.idle_unload_generation = self.idle_unload_generation.wrapping_add(1);
+        let Some(idle_unload_after) = self.stt.idle_unload_after() else {
+            return;
+        };
+        let generation = self.idle_unload_generation;
+
+        cx.spawn(async move |controller, cx| {
+            cx.background_executor().timer(idle_unload_after).await;
+            let _ = controller.update(cx, |controller, cx| {
+                controller.unload_idle_model(generation, cx);
+            });
+        })
+        .detach();
+    }
+
+    fn unload_idle_model(&mut self, generation: u64, cx: &mut Context<Self>) {
+        if self.is_hotkey_down || self.idle_unload_generation != generation {
+            return;
+        }
+
+        self.stt.unload_idle_model();
+        self.update_popup_content(cx);
+        cx.notify();
+    }
+
+    fn schedule_meeting_poll(&mut self, cx: &mut Context<Self>) {
+        self.meeting_generation = self.meeting_generation.wrapping_add(1);
+        let generation = self.meeting_generation;
+
+        cx.spawn(async move |controller, cx| {
+            loop {
+                cx.background_executor().timer(MEETING_POLL_INTERVAL).await;
+                let polling = controller
+                    .update(cx, |controller, cx| controller.poll_meeting(generation, cx))
+                    .unwrap_or(false);
+                if !polling {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn poll_meeting(&mut self, generation: u64, cx: &mut Context<Self>) -> bool {
+        if self.meeting_generation != generation {
+            return false;
+        }
+
+        self.stt.poll_meeting();
+        self.schedule_worker_restart(cx);
+        cx.notify();
+        self.stt.meeting_active()
+    }
+
+    fn schedule_worker_restart(&mut self, cx: &mut Context<Self>) {
+        let Some(restart_delay) = self.stt.take_worker_restart_delay() else {
+            return;
+        };
+        self.worker_restart_generation = self.worker_restart_generation.wrapping_add(1);
+        let generation = self.worker_restart_generation;
+
+        cx.spawn(async move |controller, cx| {
+            cx.background_executor().timer(restart_delay).await;
+            let _ = controller.update(cx, |controller, cx| {
+                controller.restart_crashed_worker(generation, cx);
+            });
+        })
+        .detach();
+    }
+
+    fn restart_crashed_worker(&mut self, generation: u64, cx: &mut Context<Self>) {
+        if self.worker_restart_generation != generation {
+            return;
+        }
+
+        self.stt.restart_crashed_worker();
+        self.update_popup_content(cx);
+        cx.notify();
+    }
+
+    fn sync_escape_cancel(&mut self) {
+        let enabled = self.popup_visible && self.stt.can_cancel();
+        if let Some(runtime) = &mut self.runtime {
+            runtime.set_escape_cancels(enabled);
+        }
+    }
+
     fn hide_popup(&mut self, cx: &mut Context<Self>) {
         self.popup_visible = false;
+        self.sync_escape_cancel();
 
         if let Some(window) = self.popup_window
             && window
@@ -2,4 +2,5 @@
 pub(crate) enum Event {
     Pressed,
     Released,
+    Cancel,
 }
@@ -3,17 +3,57 @@ use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, hotkey::HotKey};
 pub(crate) struct GlobalRuntime {
     manager: GlobalHotKeyManager,
     hotkey: HotKey,
+    cancel_hotkey: Option<HotKey>,
+    escape_hotkeys: Vec<HotKey>,
+    registered_escape_hotkeys: Vec<HotKey>,
 }
 
 impl GlobalRuntime {
-    pub(crate) fn new(manager: GlobalHotKeyManager, hotkey: HotKey) -> Self {
-        Self { manager, hotkey }
+    pub(crate) fn new(
+        manager: GlobalHotKeyManager,
+        hotkey: HotKey,
+        cancel_hotkey: Option<HotKey>,
+        escape_hotkeys: Vec<HotKey>,
+    ) -> Self {
+        Self {
+            manager,
+            hotkey,
+            cancel_hotkey,
+            escape_hotkeys,
+            registered_escape_hotkeys: Vec::new(),
+        }
+    }
+
+    /// Escape is grabbed only while there is something to cancel, so it keeps working in other
+    /// applications the rest of the time.
+    pub(crate) fn set_escape_cancels(&mut self, enabled: bool) {
+        if enabled {
+            for escape_hotkey in &self.escape_hotkeys {
+                if !self.registered_escape_hotkeys.contains(escape_hotkey)
+                    && self.manager.register(*escape_hotkey).is_ok()
+                {
+                    self.registered_escape_hotkeys.push(*escape_hotkey);
+                }
+            }
+        } else {
+            self.unregister_escape_hotkeys();
+        }
+    }
+
+    fn unregister_escape_hotkeys(&mut self) {
+        for escape_hotkey in self.registered_escape_hotkeys.drain(..) {
+            let _ = self.manager.unregister(escape_hotkey);
+        }
     }
 }
 
 impl Drop for GlobalRuntime {
     fn drop(&mut self) {
         let _ = self.manager.unregister(self.hotkey);
+        if let Some(cancel_hotkey) = self.cancel_hotkey {
+            let _ = self.manager.unregister(cancel_hotkey);
+        }
+        self.unregister_escape_hotkeys();
         GlobalHotKeyEvent::set_event_handler::<fn(GlobalHotKeyEvent)>(None);
     }
 }
@@ -1,4 +1,5 @@
 mod backend_kind;
+mod cancel_hotkey;
 mod controller;
 mod event;
 mod global_runtime;
@@ -17,6 +18,7 @@ use async_channel::{Receiver, Sender};
 use gpui::{App, Entity, Task, WeakEntity};
 
 pub(crate) use backend_kind::BackendKind;
+pub(crate) use cancel_hotkey::CancelHotkey;
 pub(crate) use controller::Controller;
 pub(crate) use event::Event;
 pub(crate) use global_runtime::GlobalRuntime;
@@ -28,6 +30,8 @@ pub(crate) use wayland_runtime::WaylandRuntime;
 // ---------------------------------------------------------------------------------------------- //
 
 pub(crate) const HOTKEY_ID: &str = "hold-overlay";
+pub(crate) const CANCEL_HOTKEY_ID: &str = "cancel-recording";
+pub(crate) const DEFAULT_CANCEL_HOTKEY: &str = "Ctrl+Alt+Backspace";
 const HOTKEY_LABEL: &str = "Ctrl+Alt+Space";
 const WAYLAND_APP_ID: &str = crate::icon::APP_ID;
 const WAYLAND_PREFERRED_TRIGGER: &str = "CTRL+ALT+space";
@@ -60,11 +64,12 @@ fn is_wayland_session() -> bool {
 
 pub(crate) fn start_runtime(
     backend_kind: BackendKind,
+    cancel_hotkey: Option<CancelHotkey>,
     sender: Sender<RuntimeEvent>,
 ) -> AppResult<Runtime> {
     match backend_kind {
-        BackendKind::GlobalHotkey => native::start(sender),
-        BackendKind::WaylandPortal => wayland::start(sender),
+        BackendKind::GlobalHotkey => native::start(cancel_hotkey, sender),
+        BackendKind::WaylandPortal => wayland::start(cancel_hotkey, sender),
     }
 }
 
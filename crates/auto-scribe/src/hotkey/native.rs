@@ -1,4 +1,4 @@
-use crate::hotkey::{Event, GlobalRuntime, HOTKEY_LABEL, Runtime, RuntimeEvent};
+use crate::hotkey::{CancelHotkey, Event, GlobalRuntime, HOTKEY_LABEL, Runtime, RuntimeEvent};
 
 use crate::error::{AppResult, ResultContext};
 use async_channel::Sender;
@@ -7,7 +7,10 @@ use global_hotkey::{
     hotkey::{Code, HotKey, Modifiers},
 };
 
-pub(crate) fn start(sender: Sender<RuntimeEvent>) -> AppResult<Runtime> {
+pub(crate) fn start(
+    cancel_hotkey: Option<CancelHotkey>,
+    sender: Sender<RuntimeEvent>,
+) -> AppResult<Runtime> {
     let manager =
         global_hotkey::GlobalHotKeyManager::new().context("create global hotkey manager")?;
     let hotkey = HotKey::new(Some(Modifiers::CONTROL | Modifiers::ALT), Code::Space);
@@ -18,24 +21,122 @@ pub(crate) fn start(sender: Sender<RuntimeEvent>) -> AppResult<Runtime> {
         .with_context(|| format!("register {HOTKEY_LABEL}"))?;
 
     let status_sender = sender.clone();
+    let cancel_hotkey = cancel_hotkey.and_then(|cancel_hotkey| {
+        match cancel_hotkey.global_hotkey().and_then(|global_hotkey| {
+            manager
+                .register(global_hotkey)
+                .with_context(|| format!("register cancel hotkey {}", cancel_hotkey.label()))
+                .map(|()| global_hotkey)
+        }) {
+            Ok(global_hotkey) => Some(global_hotkey),
+            Err(error) => {
+                let _ = status_sender.try_send(RuntimeEvent::Error(error.message().to_string()));
+                None
+            }
+        }
+    });
+    let escape_hotkeys = escape_hotkeys(hotkey);
+    let cancel_hotkey_ids = escape_hotkeys
+        .iter()
+        .chain(&cancel_hotkey)
+        .map(HotKey::id)
+        .collect::<Vec<_>>();
+
     let _ = status_sender.try_send(RuntimeEvent::Status(format!(
         "Registered {HOTKEY_LABEL}; hold it to show the overlay"
     )));
 
     GlobalHotKeyEvent::set_event_handler(Some(move |event: GlobalHotKeyEvent| {
-        if event.id() != hotkey_id {
-            return;
+        if let Some(mapped_event) =
+            hotkey_event(event.id(), event.state(), hotkey_id, &cancel_hotkey_ids)
+        {
+            let _ = sender.try_send(RuntimeEvent::Hotkey(mapped_event));
         }
-
-        let mapped_event = match event.state() {
-            HotKeyState::Pressed => Event::Pressed,
-            HotKeyState::Released => Event::Released,
-        };
-
-        let _ = sender.try_send(RuntimeEvent::Hotkey(mapped_event));
     }));
 
     Ok(Runtime::Global {
-        _runtime: GlobalRuntime::new(manager, hotkey),
+        runtime: GlobalRuntime::new(manager, hotkey, cancel_hotkey, escape_hotkeys),
     })
 }
+
+/// A grab fires only for its exact modifiers, so Escape is registered alone and with the
+/// recording hotkey's modifiers, which are usually still held when a recording is cancelled.
+fn escape_hotkeys(hotkey: HotKey) -> Vec<HotKey> {
+    vec![
+        HotKey::new(None, Code::Escape),
+        HotKey::new(Some(hotkey.mods), Code::Escape),
+    ]
+}
+
+fn hotkey_event(
+    id: u32,
+    state: HotKeyState,
+    hotkey_id: u32,
+    cancel_hotkey_ids: &[u32],
+) -> Option<Event> {
+    match state {
+        HotKeyState::Pressed if id == hotkey_id => Some(Event::Pressed),
+        HotKeyState::Released if id == hotkey_id => Some(Event::Released),
+        HotKeyState::Pressed if cancel_hotkey_ids.contains(&id) => Some(Event::Cancel),
+        HotKeyState::Pressed | HotKeyState::Released => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cancel_event(hotkey: HotKey, modifiers: Modifiers) -> Option<Event> {
+        let escape_hotkeys = escape_hotkeys(hotkey);
+        let cancel_hotkey_ids = escape_hotkeys.iter().map(HotKey::id).collect::<Vec<_>>();
+        let escape = escape_hotkeys
+            .iter()
+            .find(|escape| escape.mods == modifiers && escape.key == Code::Escape)?;
+        hotkey_event(
+            escape.id(),
+            HotKeyState::Pressed,
+            hotkey.id(),
+            &cancel_hotkey_ids,
+        )
+    }
+
+    #[test]
+    fn escape_cancels_alone_and_while_the_recording_hotkey_is_held() {
+        let hotkey = HotKey::new(Some(Modifiers::CONTROL | Modifiers::ALT), Code::Space);
+
+        assert_eq!(
+            cancel_event(hotkey, Modifiers::empty()),
+            Some(Event::Cancel)
+        );
+        assert_eq!(
+            cancel_event(hotkey, Modifiers::CONTROL | Modifiers::ALT),
+            Some(Event::Cancel)
+        );
+        assert_eq!(cancel_event(hotkey, Modifiers::SHIFT), None);
+    }
+
+    #[test]
+    fn escape_releases_do_not_cancel() {
+        let hotkey = HotKey::new(Some(Modifiers::CONTROL | Modifiers::ALT), Code::Space);
+        let escape = HotKey::new(None, Code::Escape);
+
+        assert_eq!(
+            hotkey_event(
+                escape.id(),
+                HotKeyState::Released,
+                hotkey.id(),
+                &[escape.id()]
+            ),
+            None
+        );
+        assert_eq!(
+            hotkey_event(
+                hotkey.id(),
+                HotKeyState::Released,
+                hotkey.id(),
+                &[escape.id()]
+            ),
+            Some(Event::Released)
+        );
+    }
+}
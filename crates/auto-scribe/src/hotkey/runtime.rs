@@ -3,10 +3,21 @@ use crate::hotkey::{GlobalRuntime, WaylandRuntime};
 use std::fmt;
 
 pub(crate) enum Runtime {
-    Global { _runtime: GlobalRuntime },
+    Global { runtime: GlobalRuntime },
     Wayland { _runtime: WaylandRuntime },
 }
 
+impl Runtime {
+    pub(crate) fn set_escape_cancels(&mut self, enabled: bool) {
+        match self {
+            Self::Global { runtime } => runtime.set_escape_cancels(enabled),
+            // Portal shortcuts are bound once per session, so Wayland relies on the cancel
+            // shortcut and the overlay click instead.
+            Self::Wayland { .. } => {}
+        }
+    }
+}
+
 impl fmt::Debug for Runtime {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
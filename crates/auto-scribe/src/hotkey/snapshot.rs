@@ -1,3 +1,5 @@
+use crate::stt::{ExecutionProvider, SpeakerMode};
+
 #[derive(Clone, Debug)]
 pub(crate) struct Snapshot {
     pub(crate) hotkey_label: &'static str,
@@ -12,13 +14,24 @@ pub(crate) struct Snapshot {
     pub(crate) stt_status: String,
     pub(crate) stt_model_can_download: bool,
     pub(crate) stt_model_downloading: bool,
+    pub(crate) stt_model_importing: bool,
     pub(crate) stt_model_download_files_percent: f32,
     pub(crate) stt_model_download_files_label: String,
     pub(crate) stt_model_download_file_percent: f32,
     pub(crate) stt_model_download_file_known: bool,
     pub(crate) stt_model_download_file_label: String,
+    pub(crate) stt_model_unloaded: bool,
+    pub(crate) stt_worker_restarting: bool,
     pub(crate) stt_model_dir: String,
     pub(crate) stt_config_path: String,
-    pub(crate) stt_use_gpu: bool,
-    pub(crate) stt_auto_mute_speakers: bool,
+    pub(crate) stt_enabled_providers: Vec<ExecutionProvider>,
+    pub(crate) stt_execution_providers: String,
+    pub(crate) stt_active_provider: Option<&'static str>,
+    pub(crate) stt_provider_fallback: Option<String>,
+    pub(crate) stt_onnx_threads: String,
+    pub(crate) stt_graph_optimization: &'static str,
+    pub(crate) stt_optimized_model_cache: String,
+    pub(crate) stt_speaker_mode: SpeakerMode,
+    pub(crate) stt_pause_media: bool,
+    pub(crate) stt_meeting_label: Option<String>,
 }
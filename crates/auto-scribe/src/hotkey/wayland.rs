@@ -1,12 +1,12 @@
-use crate::hotkey::{Runtime, RuntimeEvent};
+use crate::hotkey::{CancelHotkey, Runtime, RuntimeEvent};
 
 use crate::error::{AppError, AppResult, ResultContext};
 use async_channel::Sender;
 
 #[cfg(target_os = "linux")]
 use crate::hotkey::{
-    Event, HOTKEY_ID, HOTKEY_LABEL, WAYLAND_APP_ID, WAYLAND_PREFERRED_TRIGGER, WaylandRuntime,
-    format_error_chain,
+    CANCEL_HOTKEY_ID, Event, HOTKEY_ID, HOTKEY_LABEL, WAYLAND_APP_ID, WAYLAND_PREFERRED_TRIGGER,
+    WaylandRuntime, format_error_chain,
 };
 #[cfg(target_os = "linux")]
 use crate::icon::ensure_desktop_entry;
@@ -34,11 +34,14 @@ const WAYLAND_DESKTOP_FILE_NAME: &str = "dev.gpui.AutoScribe.desktop";
 const HOST_APP_REGISTRATION_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[cfg(target_os = "linux")]
-pub(crate) fn start(sender: Sender<RuntimeEvent>) -> AppResult<Runtime> {
+pub(crate) fn start(
+    cancel_hotkey: Option<CancelHotkey>,
+    sender: Sender<RuntimeEvent>,
+) -> AppResult<Runtime> {
     let thread = thread::Builder::new()
         .name("wayland-global-shortcut-portal".to_string())
         .spawn(move || {
-            if let Err(error) = run_portal(sender.clone()) {
+            if let Err(error) = run_portal(cancel_hotkey, sender.clone()) {
                 let message = format_error_chain(&error);
                 let _ = sender.try_send(RuntimeEvent::Error(format!(
                     "Wayland global shortcut portal failed: {message}"
@@ -53,14 +56,14 @@ pub(crate) fn start(sender: Sender<RuntimeEvent>) -> AppResult<Runtime> {
 }
 
 #[cfg(not(target_os = "linux"))]
-pub(crate) fn start(_: Sender<RuntimeEvent>) -> AppResult<Runtime> {
+pub(crate) fn start(_: Option<CancelHotkey>, _: Sender<RuntimeEvent>) -> AppResult<Runtime> {
     Err(AppError::operation(
         "Wayland portal hotkeys are only available on Linux",
     ))
 }
 
 #[cfg(target_os = "linux")]
-fn run_portal(sender: Sender<RuntimeEvent>) -> AppResult<()> {
+fn run_portal(cancel_hotkey: Option<CancelHotkey>, sender: Sender<RuntimeEvent>) -> AppResult<()> {
     pollster::block_on(async move {
         let desktop_entry_path =
             ensure_desktop_entry().context("install XDG desktop file for Wayland portal")?;
@@ -91,15 +94,24 @@ fn run_portal(sender: Sender<RuntimeEvent>) -> AppResult<()> {
             .await
             .context("create global shortcuts session")?;
 
-        let shortcut = NewShortcut::new(HOTKEY_ID, "Show hold overlay")
-            .preferred_trigger(Some(WAYLAND_PREFERRED_TRIGGER));
+        let mut shortcuts = vec![
+            NewShortcut::new(HOTKEY_ID, "Show hold overlay")
+                .preferred_trigger(Some(WAYLAND_PREFERRED_TRIGGER)),
+        ];
+        if let Some(cancel_hotkey) = &cancel_hotkey {
+            let cancel_trigger = cancel_hotkey.portal_trigger();
+            shortcuts.push(
+                NewShortcut::new(CANCEL_HOTKEY_ID, "Cancel recording or transcription")
+                    .preferred_trigger(Some(cancel_trigger.as_str())),
+            );
+        }
 
         let _ = sender.try_send(RuntimeEvent::Status(format!(
             "Requesting {HOTKEY_LABEL}; approve it in the portal dialog"
         )));
 
         let request = portal
-            .bind_shortcuts(&session, &[shortcut], None, BindShortcutsOptions::default())
+            .bind_shortcuts(&session, &shortcuts, None, BindShortcutsOptions::default())
             .await
             .context("request Wayland global shortcut binding")?;
 
@@ -142,6 +154,8 @@ fn run_portal(sender: Sender<RuntimeEvent>) -> AppResult<()> {
 
                     if event.shortcut_id() == HOTKEY_ID {
                         let _ = sender.try_send(RuntimeEvent::Hotkey(Event::Pressed));
+                    } else if event.shortcut_id() == CANCEL_HOTKEY_ID {
+                        let _ = sender.try_send(RuntimeEvent::Hotkey(Event::Cancel));
                     }
                 }
                 event = deactivated.next() => {
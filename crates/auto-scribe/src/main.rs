@@ -1,6 +1,10 @@
+mod cli;
+mod diagnostics;
 mod error;
+mod export;
 mod hotkey;
 mod icon;
+mod output;
 mod stt;
 mod windows;
 
@@ -9,8 +13,8 @@ mod windows;
 use crate::{
     error::{AppError, AppResult},
     hotkey::{
-        Controller, new_event_channel, select_backend_kind, start_event_task, start_runtime,
-        start_stt_event_task,
+        CancelHotkey, Controller, new_event_channel, select_backend_kind, start_event_task,
+        start_runtime, start_stt_event_task,
     },
     stt::Session,
     windows::open_main_window,
@@ -18,6 +22,7 @@ use crate::{
 
 use gpui::{App, AppContext, Pixels, WindowBounds, px, size};
 use gpui_component_assets::Assets as GpuiComponentAssets;
+use std::process::ExitCode;
 
 const WINDOW_TITLE: &str = "Auto Scribe";
 const RESIZE_EDGE_SIZE: Pixels = px(6.0);
@@ -27,7 +32,18 @@ const DEFAULT_START_HEIGHT: f32 = 1000.0;
 const MINIMUM_WIDTH: f32 = 360.0;
 const MINIMUM_HEIGHT: f32 = 220.0;
 
-fn main() {
+fn main() -> ExitCode {
+    match cli::parse_args(std::env::args_os().skip(1)) {
+        Ok(Some(command)) => cli::run(command),
+        Ok(None) => {
+            run_gui();
+            ExitCode::SUCCESS
+        }
+        Err(error) => cli::report_usage_error(&error),
+    }
+}
+
+fn run_gui() {
     prefer_x11_windowing_for_gnome_wayland();
 
     gpui_platform::application()
@@ -69,8 +85,10 @@ fn start(app: &mut App) -> AppResult<()> {
     let backend_kind = select_backend_kind();
     let (sender, receiver) = new_event_channel();
     let (stt, stt_receiver) = Session::new().map_err(AppError::speech_to_text)?;
+    let cancel_hotkey = stt.cancel_hotkey().map(CancelHotkey::new);
     let controller = app.new(|cx| Controller::new(backend_kind, stt, cx));
-    let runtime = start_runtime(backend_kind, sender).map_err(AppError::hotkey_runtime)?;
+    let runtime =
+        start_runtime(backend_kind, cancel_hotkey, sender).map_err(AppError::hotkey_runtime)?;
     let event_task = start_event_task(controller.clone(), receiver, app);
     let stt_event_task = start_stt_event_task(controller.clone(), stt_receiver, app);
     let window_closed_subscription = app.on_window_closed({
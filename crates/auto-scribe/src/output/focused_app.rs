@@ -0,0 +1,211 @@
+use serde_json::Value;
+use std::{env, process::Command};
+
+/// Where the focused window can be read from. Wayland is checked first, like `type_text`, because
+/// Wayland sessions also set `DISPLAY` for XWayland, whose active window is not the focused one.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum FocusSource {
+    Hyprland,
+    Sway,
+    Niri,
+    X11,
+    /// A Wayland compositor without IPC for the focused window, such as GNOME or KDE.
+    Unavailable,
+}
+
+impl FocusSource {
+    fn detect(var_is_set: impl Fn(&str) -> bool) -> Self {
+        if var_is_set("WAYLAND_DISPLAY") {
+            if var_is_set("HYPRLAND_INSTANCE_SIGNATURE") {
+                Self::Hyprland
+            } else if var_is_set("SWAYSOCK") {
+                Self::Sway
+            } else if var_is_set("NIRI_SOCKET") {
+                Self::Niri
+            } else {
+                Self::Unavailable
+            }
+        } else if var_is_set("DISPLAY") {
+            Self::X11
+        } else {
+            Self::Unavailable
+        }
+    }
+}
+
+/// App ID or WM_CLASS names of the focused window, asked from the compositor's IPC on Wayland or
+/// from the X server on X11. Runs external commands, so call it off the UI thread.
+pub(crate) fn focused_app_ids() -> Vec<String> {
+    let source =
+        FocusSource::detect(|name| env::var_os(name).is_some_and(|value| !value.is_empty()));
+    match source {
+        FocusSource::Hyprland => command_stdout("hyprctl", &["activewindow"])
+            .map(|output| hyprland_active_window_classes(&output))
+            .unwrap_or_default(),
+        FocusSource::Sway => command_json("swaymsg", &["-t", "get_tree", "-r"])
+            .map(|tree| sway_focused_app_ids(&tree))
+            .unwrap_or_default(),
+        FocusSource::Niri => command_json("niri", &["msg", "--json", "focused-window"])
+            .map(|window| niri_focused_app_ids(&window))
+            .unwrap_or_default(),
+        FocusSource::X11 => x11_active_window_classes(),
+        FocusSource::Unavailable => Vec::new(),
+    }
+}
+
+/// `hyprctl activewindow` prints lines such as "\tclass: kitty" and "\tinitialClass: kitty".
+fn hyprland_active_window_classes(output: &str) -> Vec<String> {
+    let mut classes = Vec::new();
+    for line in output.lines() {
+        if let Some((key, value)) = line.trim().split_once(':')
+            && matches!(key, "class" | "initialClass")
+        {
+            push_unique(&mut classes, value);
+        }
+    }
+    classes
+}
+
+/// The focused node of `swaymsg -t get_tree`: `app_id` for Wayland clients, and the WM_CLASS
+/// `class` and `instance` under `window_properties` for XWayland clients.
+fn sway_focused_app_ids(node: &Value) -> Vec<String> {
+    if node.get("focused").and_then(Value::as_bool) == Some(true) {
+        let mut app_ids = Vec::new();
+        let properties = node.get("window_properties");
+        for value in [
+            node.get("app_id"),
+            properties.and_then(|properties| properties.get("class")),
+            properties.and_then(|properties| properties.get("instance")),
+        ] {
+            if let Some(value) = value.and_then(Value::as_str) {
+                push_unique(&mut app_ids, value);
+            }
+        }
+        return app_ids;
+    }
+
+    ["nodes", "floating_nodes"]
+        .into_iter()
+        .filter_map(|key| node.get(key).and_then(Value::as_array))
+        .flatten()
+        .map(sway_focused_app_ids)
+        .find(|app_ids| !app_ids.is_empty())
+        .unwrap_or_default()
+}
+
+/// `niri msg --json focused-window` prints the window object, or `null` when nothing is focused.
+fn niri_focused_app_ids(window: &Value) -> Vec<String> {
+    let mut app_ids = Vec::new();
+    if let Some(app_id) = window.get("app_id").and_then(Value::as_str) {
+        push_unique(&mut app_ids, app_id);
+    }
+    app_ids
+}
+
+fn x11_active_window_classes() -> Vec<String> {
+    let Some(window) = command_stdout("xprop", &["-root", "_NET_ACTIVE_WINDOW"])
+        .and_then(|output| output.split_whitespace().last().map(str::to_string))
+        .filter(|window| window.starts_with("0x") && window != "0x0")
+    else {
+        return Vec::new();
+    };
+
+    command_stdout("xprop", &["-id", &window, "WM_CLASS"])
+        .map(|output| wm_class_names(&output))
+        .unwrap_or_default()
+}
+
+/// WM_CLASS holds the instance and class names: `WM_CLASS(STRING) = "kitty", "kitty"`.
+fn wm_class_names(output: &str) -> Vec<String> {
+    let mut classes = Vec::new();
+    if let Some((_, names)) = output.split_once('=') {
+        for name in names.split(',') {
+            push_unique(&mut classes, name.trim().trim_matches('"'));
+        }
+    }
+    classes
+}
+
+fn push_unique(classes: &mut Vec<String>, class: &str) {
+    let class = class.trim();
+    if !class.is_empty() && !classes.iter().any(|existing| existing == class) {
+        classes.push(class.to_string());
+    }
+}
+
+fn command_json(program: &str, args: &[&str]) -> Option<Value> {
+    command_stdout(program, args).and_then(|output| serde_json::from_str(&output).ok())
+}
+
+fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(vars: &[&str]) -> FocusSource {
+        FocusSource::detect(|name| vars.contains(&name))
+    }
+
+    #[test]
+    fn wayland_sessions_do_not_fall_back_to_xwayland() {
+        assert_eq!(detect(&["DISPLAY"]), FocusSource::X11);
+        assert_eq!(
+            detect(&["WAYLAND_DISPLAY", "DISPLAY"]),
+            FocusSource::Unavailable
+        );
+        assert_eq!(
+            detect(&["WAYLAND_DISPLAY", "DISPLAY", "SWAYSOCK"]),
+            FocusSource::Sway
+        );
+        assert_eq!(
+            detect(&["WAYLAND_DISPLAY", "HYPRLAND_INSTANCE_SIGNATURE"]),
+            FocusSource::Hyprland
+        );
+        assert_eq!(
+            detect(&["WAYLAND_DISPLAY", "NIRI_SOCKET"]),
+            FocusSource::Niri
+        );
+        assert_eq!(detect(&[]), FocusSource::Unavailable);
+    }
+
+    #[test]
+    fn reads_hyprland_classes() {
+        let output = "Window 55d0 -> ~:\n\tclass: kitty\n\ttitle: ~\n\tinitialClass: kitty\n";
+        assert_eq!(hyprland_active_window_classes(output), vec!["kitty"]);
+    }
+
+    #[test]
+    fn finds_the_focused_sway_node() -> Result<(), serde_json::Error> {
+        let tree = serde_json::from_str(
+            r#"{"focused": false, "nodes": [
+                {"focused": false, "nodes": [{"focused": false, "app_id": "foot", "nodes": []}]},
+                {"focused": false, "nodes": [], "floating_nodes": [{
+                    "focused": true, "app_id": null,
+                    "window_properties": {"class": "Code", "instance": "code"}
+                }]}
+            ]}"#,
+        )?;
+        assert_eq!(sway_focused_app_ids(&tree), vec!["Code", "code"]);
+        Ok(())
+    }
+
+    #[test]
+    fn reads_niri_and_x11_names() -> Result<(), serde_json::Error> {
+        let window = serde_json::from_str(r#"{"id": 3, "app_id": "Alacritty"}"#)?;
+        assert_eq!(niri_focused_app_ids(&window), vec!["Alacritty"]);
+        assert!(niri_focused_app_ids(&Value::Null).is_empty());
+        assert_eq!(
+            wm_class_names("WM_CLASS(STRING) = \"kitty\", \"kitty\"\n"),
+            vec!["kitty"]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,13 @@
+mod focused_app;
+mod output_method;
+mod output_profile;
+mod text_stage;
+mod type_text;
+
+// ---------------------------------------------------------------------------------------------- //
+
+pub(crate) use focused_app::focused_app_ids;
+pub(crate) use output_method::OutputMethod;
+pub(crate) use output_profile::OutputProfile;
+pub(crate) use text_stage::TextStage;
+pub(crate) use type_text::type_text;
@@ -0,0 +1,31 @@
+/// How a transcript reaches the focused application.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum OutputMethod {
+    Clipboard,
+    /// Synthesized keystrokes; falls back to the clipboard if typing fails.
+    Type,
+}
+
+impl OutputMethod {
+    pub(crate) const ALL: [Self; 2] = [Self::Clipboard, Self::Type];
+
+    pub(crate) fn from_config_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|method| method.config_name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub(crate) fn config_name(self) -> &'static str {
+        match self {
+            Self::Clipboard => "clipboard",
+            Self::Type => "type",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Clipboard => "Clipboard",
+            Self::Type => "Type",
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use crate::{
+    output::{OutputMethod, TextStage},
+    stt::{SttError, SttResult},
+};
+
+/// Delivery rules for the applications whose app ID or WM_CLASS is listed in `apps`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct OutputProfile {
+    pub(crate) name: String,
+    /// App IDs or WM_CLASS names, compared case-insensitively.
+    pub(crate) apps: Vec<String>,
+    pub(crate) output: OutputMethod,
+    pub(crate) stages: Vec<TextStage>,
+    /// Put before every line, such as `// ` for code comments.
+    pub(crate) line_prefix: String,
+    pub(crate) trailing_newline: bool,
+}
+
+impl OutputProfile {
+    pub(crate) fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            apps: Vec::new(),
+            output: OutputMethod::Clipboard,
+            stages: Vec::new(),
+            line_prefix: String::new(),
+            trailing_newline: false,
+        }
+    }
+
+    pub(crate) fn matches(&self, app_ids: &[String]) -> bool {
+        self.apps.iter().any(|app| {
+            app_ids
+                .iter()
+                .any(|app_id| app_id.eq_ignore_ascii_case(app.trim()))
+        })
+    }
+
+    /// Runs the stages in order, then applies the line prefix and trailing newline.
+    pub(crate) fn format(&self, transcript: &str) -> String {
+        let text = self
+            .stages
+            .iter()
+            .fold(transcript.to_string(), |text, stage| stage.apply(&text));
+
+        let mut formatted = if self.line_prefix.is_empty() {
+            text
+        } else {
+            text.lines()
+                .map(|line| format!("{}{line}", self.line_prefix))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        if self.trailing_newline {
+            formatted.push('\n');
+        }
+        formatted
+    }
+
+    /// Reads the `[[output_profile]]` array of tables.
+    pub(crate) fn list_from_value(value: Option<&toml::Value>) -> SttResult<Vec<Self>> {
+        let Some(value) = value else {
+            return Ok(Vec::new());
+        };
+        let Some(profiles) = value.as_array() else {
+            return Err(SttError::model_path(
+                "config output_profile must be an array of tables ([[output_profile]])",
+            ));
+        };
+
+        profiles
+            .iter()
+            .enumerate()
+            .map(|(index, profile)| {
+                profile
+                    .as_table()
+                    .ok_or_else(|| {
+                        SttError::model_path(format!(
+                            "config output_profile {index} is not a table"
+                        ))
+                    })
+                    .and_then(|table| Self::from_table(index, table))
+            })
+            .collect()
+    }
+
+    pub(crate) fn to_table(&self) -> toml::Table {
+        let mut table = toml::Table::new();
+        table.insert("name".to_string(), toml::Value::String(self.name.clone()));
+        table.insert(
+            "apps".to_string(),
+            toml::Value::Array(
+                self.apps
+                    .iter()
+                    .map(|app| toml::Value::String(app.clone()))
+                    .collect(),
+            ),
+        );
+        table.insert(
+            "output".to_string(),
+            toml::Value::String(self.output.config_name().to_string()),
+        );
+        table.insert(
+            "stages".to_string(),
+            toml::Value::Array(
+                self.stages
+                    .iter()
+                    .map(|stage| toml::Value::String(stage.config_name().to_string()))
+                    .collect(),
+            ),
+        );
+        table.insert(
+            "line_prefix".to_string(),
+            toml::Value::String(self.line_prefix.clone()),
+        );
+        table.insert(
+            "trailing_newline".to_string(),
+            toml::Value::Boolean(self.trailing_newline),
+        );
+        table
+    }
+
+    fn from_table(index: usize, table: &toml::Table) -> SttResult<Self> {
+        let name = table
+            .get("name")
+            .and_then(toml::Value::as_str)
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map_or_else(|| format!("Profile {}", index + 1), str::to_string);
+        let invalid = |key: &str, expected: &str| {
+            SttError::model_path(format!(
+                "config output_profile \"{name}\" {key} must be {expected}"
+            ))
+        };
+
+        let apps = string_list(table.get("apps"))
+            .ok_or_else(|| invalid("apps", "a list of app IDs or WM_CLASS names"))?;
+        let output = match table.get("output") {
+            Some(value) => value
+                .as_str()
+                .and_then(OutputMethod::from_config_name)
+                .ok_or_else(|| invalid("output", "one of: clipboard, type"))?,
+            None => OutputMethod::Clipboard,
+        };
+        let stages = string_list(table.get("stages"))
+            .and_then(|stages| {
+                stages
+                    .iter()
+                    .map(|stage| TextStage::from_config_name(stage))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .ok_or_else(|| {
+                invalid(
+                    "stages",
+                    "a list of: sentence_case, no_final_period, shell_safe",
+                )
+            })?;
+        let line_prefix = match table.get("line_prefix") {
+            Some(value) => value
+                .as_str()
+                .ok_or_else(|| invalid("line_prefix", "a string"))?
+                .to_string(),
+            None => String::new(),
+        };
+        let trailing_newline = match table.get("trailing_newline") {
+            Some(value) => value
+                .as_bool()
+                .ok_or_else(|| invalid("trailing_newline", "true or false"))?,
+            None => false,
+        };
+
+        Ok(Self {
+            name,
+            apps,
+            output,
+            stages,
+            line_prefix,
+            trailing_newline,
+        })
+    }
+}
+
+/// A missing key is an empty list; anything but an array of strings is `None`.
+fn string_list(value: Option<&toml::Value>) -> Option<Vec<String>> {
+    let Some(value) = value else {
+        return Some(Vec::new());
+    };
+
+    value
+        .as_array()?
+        .iter()
+        .map(|item| item.as_str().map(str::to_string))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profiles(config: &str) -> SttResult<Vec<OutputProfile>> {
+        let value = config
+            .parse::<toml::Table>()
+            .map_err(|error| SttError::model_path(error.to_string()))?;
+        OutputProfile::list_from_value(value.get("output_profile"))
+    }
+
+    #[test]
+    fn matches_any_listed_app_without_regard_to_case() -> SttResult<()> {
+        let profiles = profiles(
+            r#"
+            [[output_profile]]
+            name = "Terminal"
+            apps = ["kitty", " Alacritty "]
+
+            [[output_profile]]
+            name = "Editor"
+            apps = ["code"]
+            "#,
+        )?;
+        let matching = |app_ids: &[&str]| {
+            let app_ids = app_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+            profiles
+                .iter()
+                .find(|profile| profile.matches(&app_ids))
+                .map(|profile| profile.name.clone())
+        };
+
+        assert_eq!(matching(&["KITTY"]), Some("Terminal".to_string()));
+        assert_eq!(matching(&["alacritty"]), Some("Terminal".to_string()));
+        assert_eq!(matching(&["Code", "code"]), Some("Editor".to_string()));
+        assert_eq!(matching(&["firefox"]), None);
+        assert_eq!(matching(&[]), None);
+        Ok(())
+    }
+
+    #[test]
+    fn formats_with_stages_prefix_and_trailing_newline() {
+        let mut profile = OutputProfile::new("Comments");
+        profile.stages = vec![TextStage::SentenceCase, TextStage::NoFinalPeriod];
+        profile.line_prefix = "// ".to_string();
+        profile.trailing_newline = true;
+
+        assert_eq!(
+            profile.format("first line.\nsecond line."),
+            "// First line.\n// Second line\n"
+        );
+    }
+
+    #[test]
+    fn round_trips_through_the_config_table() -> SttResult<()> {
+        let mut profile = OutputProfile::new("Shell");
+        profile.apps = vec!["foot".to_string()];
+        profile.output = OutputMethod::Type;
+        profile.stages = vec![TextStage::ShellSafe];
+        profile.trailing_newline = true;
+
+        let value = toml::Value::Array(vec![toml::Value::Table(profile.to_table())]);
+        assert_eq!(OutputProfile::list_from_value(Some(&value))?, vec![profile]);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unknown_stages_and_names_unnamed_profiles() -> SttResult<()> {
+        assert!(profiles("[[output_profile]]\nstages = [\"shout\"]").is_err());
+        assert!(profiles("[[output_profile]]\noutput = \"print\"").is_err());
+
+        let unnamed = profiles("[[output_profile]]\napps = [\"kitty\"]")?;
+        assert_eq!(
+            unnamed.first().map(|profile| profile.name.as_str()),
+            Some("Profile 1")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+/// Characters a shell would interpret on a command line.
+const SHELL_SPECIAL_CHARACTERS: &str = "\\`$!\"';&|<>(){}[]*?~#";
+
+/// A text transformation an output profile applies to a transcript, in the listed order.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum TextStage {
+    /// Capitalizes the first letter of every sentence.
+    SentenceCase,
+    /// Drops one trailing period, as commands and chat messages rarely end with one.
+    NoFinalPeriod,
+    /// Joins lines and backslash-escapes shell syntax, so pasted text stays literal and a
+    /// newline can never run a command.
+    ShellSafe,
+}
+
+impl TextStage {
+    pub(crate) const ALL: [Self; 3] = [Self::SentenceCase, Self::NoFinalPeriod, Self::ShellSafe];
+
+    pub(crate) fn from_config_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|stage| stage.config_name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub(crate) fn config_name(self) -> &'static str {
+        match self {
+            Self::SentenceCase => "sentence_case",
+            Self::NoFinalPeriod => "no_final_period",
+            Self::ShellSafe => "shell_safe",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::SentenceCase => "Sentence case",
+            Self::NoFinalPeriod => "No final period",
+            Self::ShellSafe => "Shell-safe",
+        }
+    }
+
+    pub(crate) fn apply(self, text: &str) -> String {
+        match self {
+            Self::SentenceCase => sentence_case(text),
+            Self::NoFinalPeriod => text
+                .trim_end()
+                .strip_suffix('.')
+                .filter(|rest| !rest.ends_with('.'))
+                .unwrap_or(text)
+                .to_string(),
+            Self::ShellSafe => shell_safe(text),
+        }
+    }
+}
+
+/// A sentence starts at the beginning of the text and after `.`, `!` or `?` followed by
+/// whitespace, so `file.rs` and `3.5` are left alone.
+fn sentence_case(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut sentence_start = true;
+    let mut after_terminator = false;
+    for character in text.chars() {
+        if sentence_start && character.is_alphabetic() {
+            result.extend(character.to_uppercase());
+            sentence_start = false;
+            after_terminator = false;
+            continue;
+        }
+
+        if matches!(character, '.' | '!' | '?') {
+            after_terminator = true;
+        } else if character.is_whitespace() {
+            sentence_start |= after_terminator;
+        } else if !matches!(character, '"' | '\'' | '(') {
+            sentence_start = false;
+            after_terminator = false;
+        }
+        result.push(character);
+    }
+    result
+}
+
+fn shell_safe(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for character in text.chars() {
+        if character.is_control() {
+            if !result.ends_with(' ') {
+                result.push(' ');
+            }
+            continue;
+        }
+
+        if SHELL_SPECIAL_CHARACTERS.contains(character) {
+            result.push('\\');
+        }
+        result.push(character);
+    }
+    result.trim().to_string()
+}
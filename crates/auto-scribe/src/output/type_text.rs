@@ -0,0 +1,35 @@
+use crate::error::{AppError, AppResult};
+
+use std::{env, process::Command};
+
+/// Types `text` into the focused window with `wtype` on Wayland or `xdotool` on X11.
+pub(crate) fn type_text(text: &str) -> AppResult<()> {
+    let (program, args): (&str, &[&str]) = if env::var_os("WAYLAND_DISPLAY").is_some() {
+        ("wtype", &["--"])
+    } else {
+        ("xdotool", &["type", "--clearmodifiers", "--"])
+    };
+
+    let output = Command::new(program)
+        .args(args)
+        .arg(text)
+        .output()
+        .map_err(|error| AppError::with_context(program, error))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(AppError::operation(format!(
+            "{program} failed with status {}; {}",
+            output
+                .status
+                .code()
+                .map_or_else(|| "unknown".to_string(), |code| code.to_string()),
+            if stderr.is_empty() {
+                "no stderr"
+            } else {
+                &stderr
+            }
+        )));
+    }
+
+    Ok(())
+}
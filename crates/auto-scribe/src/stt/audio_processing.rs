@@ -0,0 +1,109 @@
+use crate::stt::{
+    HighPassFilter, SttError, SttResult, TARGET_SAMPLE_RATE, normalize_loudness, suppress_noise,
+};
+
+const DEFAULT_HIGH_PASS_HZ: f32 = 80.0;
+const DEFAULT_TARGET_LEVEL_DBFS: f32 = -20.0;
+
+/// Optional clean-up applied to 16 kHz audio before inference, configured in `[audio]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct AudioProcessing {
+    pub(crate) high_pass: bool,
+    pub(crate) noise_suppression: bool,
+    pub(crate) gain_control: bool,
+    pub(crate) target_level_dbfs: f32,
+}
+
+impl Default for AudioProcessing {
+    fn default() -> Self {
+        Self {
+            high_pass: false,
+            noise_suppression: false,
+            gain_control: false,
+            target_level_dbfs: DEFAULT_TARGET_LEVEL_DBFS,
+        }
+    }
+}
+
+impl AudioProcessing {
+    pub(crate) fn from_table(audio_table: Option<&toml::Table>) -> SttResult<Self> {
+        let flag = |key: &str| {
+            audio_table
+                .and_then(|table| table.get(key))
+                .and_then(toml::Value::as_bool)
+                .unwrap_or(false)
+        };
+        let target_level_dbfs = match audio_table.and_then(|table| table.get("target_level_dbfs")) {
+            None => DEFAULT_TARGET_LEVEL_DBFS,
+            Some(value) => value
+                .as_float()
+                .or_else(|| value.as_integer().map(|level| level as f64))
+                .filter(|level| (-60.0..0.0).contains(level))
+                .map(|level| level as f32)
+                .ok_or_else(|| {
+                    SttError::model_path(
+                        "config [audio].target_level_dbfs must be a number between -60 and 0",
+                    )
+                })?,
+        };
+
+        Ok(Self {
+            high_pass: flag("high_pass_filter"),
+            noise_suppression: flag("noise_suppression"),
+            gain_control: flag("automatic_gain_control"),
+            target_level_dbfs,
+        })
+    }
+
+    /// Every stage on, used by the processing comparison when none is configured.
+    pub(crate) fn all_stages(self) -> Self {
+        Self {
+            high_pass: true,
+            noise_suppression: true,
+            gain_control: true,
+            ..self
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.high_pass || self.noise_suppression || self.gain_control
+    }
+
+    pub(crate) fn label(&self) -> String {
+        let stages = [
+            (self.high_pass, "high-pass".to_string()),
+            (self.noise_suppression, "noise suppression".to_string()),
+            (
+                self.gain_control,
+                format!("gain control to {} dBFS", self.target_level_dbfs),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, stage)| enabled.then_some(stage))
+        .collect::<Vec<_>>();
+
+        if stages.is_empty() {
+            return "none".to_string();
+        }
+        stages.join(", ")
+    }
+
+    /// Runs the enabled stages on 16 kHz mono audio in `-1.0..=1.0`.
+    pub(crate) fn apply(&self, mut audio_16k_mono: Vec<f32>) -> SttResult<Vec<f32>> {
+        if self.high_pass {
+            HighPassFilter::new(DEFAULT_HIGH_PASS_HZ, TARGET_SAMPLE_RATE)
+                .process(&mut audio_16k_mono);
+        }
+        if self.noise_suppression {
+            audio_16k_mono = suppress_noise(audio_16k_mono, TARGET_SAMPLE_RATE)?;
+        }
+        if self.gain_control {
+            normalize_loudness(
+                &mut audio_16k_mono,
+                TARGET_SAMPLE_RATE,
+                self.target_level_dbfs,
+            );
+        }
+        Ok(audio_16k_mono)
+    }
+}
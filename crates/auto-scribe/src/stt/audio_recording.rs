@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+#[derive(Clone)]
 pub(crate) struct AudioRecording {
     pub(crate) samples: Vec<f32>,
     pub(crate) sample_rate: u32,
@@ -0,0 +1,39 @@
+use crate::stt::CaptureChannel;
+
+/// Where a recorder captures audio from: `[audio].source` for dictation and
+/// `[meeting].source` for meeting mode.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum AudioSource {
+    Microphone,
+    /// The PulseAudio/PipeWire monitor of an output sink, i.e. what the speakers play.
+    Monitor,
+    /// Microphone and monitor captured as separate channels.
+    MicrophoneAndMonitor,
+}
+
+impl AudioSource {
+    pub(crate) fn from_config_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "microphone" => Some(Self::Microphone),
+            "monitor" => Some(Self::Monitor),
+            "both" => Some(Self::MicrophoneAndMonitor),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Microphone => "microphone",
+            Self::Monitor => "system audio",
+            Self::MicrophoneAndMonitor => "microphone and system audio",
+        }
+    }
+
+    pub(crate) fn channels(self) -> &'static [CaptureChannel] {
+        match self {
+            Self::Microphone => &[CaptureChannel::Microphone],
+            Self::Monitor => &[CaptureChannel::System],
+            Self::MicrophoneAndMonitor => &[CaptureChannel::Microphone, CaptureChannel::System],
+        }
+    }
+}
@@ -0,0 +1,15 @@
+/// One input of a recorder; meeting transcripts label each line with it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum CaptureChannel {
+    Microphone,
+    System,
+}
+
+impl CaptureChannel {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Microphone => "Microphone",
+            Self::System => "System",
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use crate::stt::{
+    AudioRecording, CaptureChannel, CaptureStream, MAX_RECORDING_SECONDS, MonitorCapture,
+    RecorderState, StreamingResampler, SttError, SttResult, TARGET_SAMPLE_RATE,
+};
+
+use cpal::{
+    FromSample, Sample, SampleFormat, SizedSample, Stream, StreamConfig,
+    traits::{DeviceTrait, HostTrait, StreamTrait},
+};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// One capture source feeding a `RecorderState` with mono 16 kHz samples.
+pub(crate) struct CaptureInput {
+    stream: CaptureStream,
+    state: Arc<Mutex<RecorderState>>,
+    sample_rate: u32,
+    channel: CaptureChannel,
+}
+
+impl CaptureInput {
+    pub(crate) fn microphone() -> SttResult<Self> {
+        let host = cpal::default_host();
+        let device = host
+            .default_input_device()
+            .ok_or_else(|| SttError::audio_device("no default input device is available"))?;
+        let supported_config = select_input_config(&device)?;
+        let sample_format = supported_config.sample_format();
+        let config: StreamConfig = supported_config.into();
+        let sample_rate = config.sample_rate;
+        let channels = usize::from(config.channels);
+
+        if channels == 0 {
+            return Err(SttError::audio_device(
+                "input device reported zero audio channels",
+            ));
+        }
+
+        let state = new_state(sample_rate);
+        let stream = build_stream(
+            &device,
+            &config,
+            sample_format,
+            channels,
+            Arc::clone(&state),
+        )?;
+        stream
+            .play()
+            .map_err(|error| SttError::audio_stream(error.to_string()))?;
+
+        Ok(Self {
+            stream: CaptureStream::Device(stream),
+            state,
+            sample_rate,
+            channel: CaptureChannel::Microphone,
+        })
+    }
+
+    /// Captures a PulseAudio/PipeWire monitor source such as `@DEFAULT_MONITOR@`.
+    pub(crate) fn monitor(source_name: &str) -> SttResult<Self> {
+        let state = new_state(TARGET_SAMPLE_RATE);
+        let monitor = MonitorCapture::new(source_name, Arc::clone(&state))?;
+
+        Ok(Self {
+            stream: CaptureStream::Monitor(monitor),
+            state,
+            sample_rate: TARGET_SAMPLE_RATE,
+            channel: CaptureChannel::System,
+        })
+    }
+
+    pub(crate) fn channel(&self) -> CaptureChannel {
+        self.channel
+    }
+
+    pub(crate) fn start(&self) -> SttResult<()> {
+        // Built outside the lock so the audio callback never waits on filter setup.
+        let resampler = (self.sample_rate != TARGET_SAMPLE_RATE)
+            .then(|| StreamingResampler::new(self.sample_rate, TARGET_SAMPLE_RATE))
+            .transpose()?;
+        {
+            let mut state = self.lock_state()?;
+            state.samples.clear();
+            state.resampler = resampler;
+            state.captured_frames = 0;
+            state.recording = true;
+            state.clipped = false;
+            state.last_stream_error = None;
+        }
+
+        if let CaptureStream::Monitor(monitor) = &self.stream {
+            monitor.start()?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn stop(&self) -> SttResult<AudioRecording> {
+        let mut state = self.lock_state()?;
+        state.recording = false;
+        let mut samples = std::mem::take(&mut state.samples);
+        let resampler = state.resampler.take();
+        let clipped = state.clipped;
+        state.clipped = false;
+        drop(state);
+
+        if let CaptureStream::Monitor(monitor) = &self.stream {
+            monitor.stop();
+        }
+        if let Some(resampler) = resampler {
+            resampler.finish(&mut samples)?;
+        }
+
+        Ok(AudioRecording {
+            samples,
+            sample_rate: TARGET_SAMPLE_RATE,
+            clipped,
+        })
+    }
+
+    /// Hands over the samples captured so far without stopping, when `should_take` accepts
+    /// them. Used by meeting mode, which records for longer than the dictation cap.
+    pub(crate) fn take_when(
+        &self,
+        should_take: impl FnOnce(&[f32]) -> bool,
+    ) -> SttResult<Option<AudioRecording>> {
+        let mut state = self.lock_state()?;
+        if !state.recording || !should_take(&state.samples) {
+            return Ok(None);
+        }
+
+        state.captured_frames = 0;
+        Ok(Some(AudioRecording {
+            samples: std::mem::take(&mut state.samples),
+            sample_rate: TARGET_SAMPLE_RATE,
+            clipped: false,
+        }))
+    }
+
+    pub(crate) fn take_stream_error(&self) -> SttResult<Option<String>> {
+        Ok(self.lock_state()?.last_stream_error.take())
+    }
+
+    fn lock_state(&self) -> SttResult<MutexGuard<'_, RecorderState>> {
+        self.state
+            .lock()
+            .map_err(|_| SttError::recorder_state("recording state lock is poisoned"))
+    }
+}
+
+fn new_state(sample_rate: u32) -> Arc<Mutex<RecorderState>> {
+    let max_frames = sample_rate as usize * MAX_RECORDING_SECONDS;
+    Arc::new(Mutex::new(RecorderState::new(max_frames)))
+}
+
+fn select_input_config(device: &cpal::Device) -> SttResult<cpal::SupportedStreamConfig> {
+    let target_rate = TARGET_SAMPLE_RATE;
+    let supported_configs = device
+        .supported_input_configs()
+        .map_err(|error| SttError::audio_device(error.to_string()))?;
+
+    for config_range in supported_configs {
+        if config_range.channels() == 1
+            && config_range.sample_format() == SampleFormat::F32
+            && let Some(config) = config_range.try_with_sample_rate(target_rate)
+        {
+            return Ok(config);
+        }
+    }
+
+    device
+        .default_input_config()
+        .map_err(|error| SttError::audio_device(error.to_string()))
+}
+
+fn build_stream(
+    device: &cpal::Device,
+    config: &StreamConfig,
+    sample_format: SampleFormat,
+    channels: usize,
+    state: Arc<Mutex<RecorderState>>,
+) -> SttResult<Stream> {
+    match sample_format {
+        SampleFormat::I8 => build_typed_stream::<i8>(device, config, channels, state),
+        SampleFormat::I16 => build_typed_stream::<i16>(device, config, channels, state),
+        SampleFormat::I24 => build_typed_stream::<cpal::I24>(device, config, channels, state),
+        SampleFormat::I32 => build_typed_stream::<i32>(device, config, channels, state),
+        SampleFormat::I64 => build_typed_stream::<i64>(device, config, channels, state),
+        SampleFormat::U8 => build_typed_stream::<u8>(device, config, channels, state),
+        SampleFormat::U16 => build_typed_stream::<u16>(device, config, channels, state),
+        SampleFormat::U32 => build_typed_stream::<u32>(device, config, channels, state),
+        SampleFormat::U64 => build_typed_stream::<u64>(device, config, channels, state),
+        SampleFormat::F32 => build_typed_stream::<f32>(device, config, channels, state),
+        SampleFormat::F64 => build_typed_stream::<f64>(device, config, channels, state),
+        unsupported => Err(SttError::audio_device(format!(
+            "unsupported input sample format: {unsupported}"
+        ))),
+    }
+}
+
+fn build_typed_stream<T>(
+    device: &cpal::Device,
+    config: &StreamConfig,
+    channels: usize,
+    state: Arc<Mutex<RecorderState>>,
+) -> SttResult<Stream>
+where
+    T: Sample + SizedSample + Send + 'static,
+    f32: FromSample<T>,
+{
+    let error_state = Arc::clone(&state);
+    device
+        .build_input_stream(
+            *config,
+            move |data: &[T], _callback_info| {
+                record_input_data(data, channels, &state);
+            },
+            move |error| {
+                if let Ok(mut state) = error_state.lock() {
+                    state.last_stream_error = Some(error.to_string());
+                }
+            },
+            None,
+        )
+        .map_err(|error| SttError::audio_stream(error.to_string()))
+}
+
+/// Mixes interleaved frames down to mono and appends them, resampled, to the recording.
+pub(crate) fn record_input_data<T>(data: &[T], channels: usize, state: &Arc<Mutex<RecorderState>>)
+where
+    T: Sample,
+    f32: FromSample<T>,
+{
+    let Ok(mut state) = state.lock() else {
+        return;
+    };
+
+    if !state.recording {
+        return;
+    }
+
+    let state = &mut *state;
+    state.mono_frames.clear();
+    for frame in data.chunks(channels) {
+        if state.captured_frames >= state.max_frames {
+            state.clipped = true;
+            break;
+        }
+
+        let sample_sum = frame
+            .iter()
+            .map(|sample| f32::from_sample(*sample))
+            .sum::<f32>();
+        state.mono_frames.push(sample_sum / frame.len() as f32);
+        state.captured_frames += 1;
+    }
+
+    match &mut state.resampler {
+        Some(resampler) => {
+            if let Err(error) = resampler.push(&state.mono_frames, &mut state.samples) {
+                state.last_stream_error = Some(error.to_string());
+                state.recording = false;
+            }
+        }
+        None => state.samples.extend_from_slice(&state.mono_frames),
+    }
+}
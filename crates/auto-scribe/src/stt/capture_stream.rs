@@ -0,0 +1,9 @@
+use crate::stt::MonitorCapture;
+
+use cpal::Stream;
+
+/// Keeps a capture source alive; dropping it ends the capture.
+pub(crate) enum CaptureStream {
+    Device(Stream),
+    Monitor(MonitorCapture),
+}
@@ -0,0 +1,66 @@
+use crate::stt::{AudioRecording, SttError, SttResult};
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+const DEFAULT_DIARIZATION_MODEL_FILE: &str = "models/diar_streaming_sortformer_4spk-v2.onnx";
+const DEFAULT_MIN_RECORDING_SECONDS: u64 = 20;
+
+/// Speaker diarization settings from `[diarization]`.
+#[derive(Clone, Debug)]
+pub(crate) struct DiarizationConfig {
+    enabled: bool,
+    model_path: PathBuf,
+    min_recording: Duration,
+}
+
+impl DiarizationConfig {
+    pub(crate) fn from_table(
+        diarization_table: Option<&toml::Table>,
+        app_data_dir: &Path,
+    ) -> SttResult<Self> {
+        let enabled = diarization_table
+            .and_then(|table| table.get("enabled"))
+            .and_then(toml::Value::as_bool)
+            .unwrap_or(false);
+        let model_file = diarization_table
+            .and_then(|table| table.get("model_file"))
+            .and_then(toml::Value::as_str)
+            .map(str::trim)
+            .filter(|model_file| !model_file.is_empty())
+            .unwrap_or(DEFAULT_DIARIZATION_MODEL_FILE);
+        let min_recording_seconds = match diarization_table
+            .and_then(|table| table.get("min_recording_seconds"))
+        {
+            None => DEFAULT_MIN_RECORDING_SECONDS,
+            Some(value) => value
+                .as_integer()
+                .and_then(|seconds| u64::try_from(seconds).ok())
+                .ok_or_else(|| {
+                    SttError::model_path(
+                        "config [diarization].min_recording_seconds must be a non-negative integer",
+                    )
+                })?,
+        };
+
+        Ok(Self {
+            enabled,
+            // An absolute model_file replaces the app data directory.
+            model_path: app_data_dir.join(model_file),
+            min_recording: Duration::from_secs(min_recording_seconds),
+        })
+    }
+
+    pub(crate) fn model_path(&self) -> &Path {
+        &self.model_path
+    }
+
+    /// The model to diarize `recording` with, when diarization is enabled and the recording
+    /// is long enough to have more than one speaker.
+    pub(crate) fn model_for(&self, recording: &AudioRecording) -> Option<PathBuf> {
+        (self.enabled && !recording.is_shorter_than(self.min_recording))
+            .then(|| self.model_path.clone())
+    }
+}
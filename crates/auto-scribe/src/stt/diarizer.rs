@@ -0,0 +1,67 @@
+use crate::stt::{SpeakerTurn, SttError, SttResult, TARGET_SAMPLE_RATE};
+
+use parakeet_rs::{
+    ExecutionConfig,
+    sortformer::{DiarizationConfig, Sortformer},
+};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// The Sortformer diarization model, loaded by the worker on the first request that asks
+/// for speaker labels.
+pub(crate) struct Diarizer {
+    model_path: PathBuf,
+    model: Sortformer,
+}
+
+impl Diarizer {
+    pub(crate) fn load(model_path: &Path, config: ExecutionConfig) -> SttResult<Self> {
+        if !model_path.is_file() {
+            return Err(SttError::model_path(format!(
+                "diarization model {} is missing; set [diarization].model_file",
+                model_path.display()
+            )));
+        }
+
+        let model =
+            Sortformer::with_config(model_path, Some(config), DiarizationConfig::callhome())
+                .map_err(|error| {
+                    SttError::speech_to_text(format!(
+                        "load diarization model {}: {error}",
+                        model_path.display()
+                    ))
+                })?;
+
+        Ok(Self {
+            model_path: model_path.to_path_buf(),
+            model,
+        })
+    }
+
+    pub(crate) fn model_path(&self) -> &Path {
+        &self.model_path
+    }
+
+    pub(crate) fn speaker_turns(&mut self, audio_16k_mono: &[f32]) -> SttResult<Vec<SpeakerTurn>> {
+        let segments = self
+            .model
+            .diarize(audio_16k_mono.to_vec(), TARGET_SAMPLE_RATE, 1)
+            .map_err(|error| SttError::speech_to_text(format!("diarization: {error}")))?;
+
+        Ok(segments
+            .into_iter()
+            .filter(|segment| segment.end > segment.start)
+            .map(|segment| SpeakerTurn {
+                speaker: segment.speaker_id,
+                start: seconds(segment.start),
+                end: seconds(segment.end),
+            })
+            .collect())
+    }
+}
+
+fn seconds(value: f32) -> Duration {
+    Duration::try_from_secs_f32(value).unwrap_or_default()
+}
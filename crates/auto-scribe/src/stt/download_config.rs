@@ -0,0 +1,112 @@
+use crate::stt::{SttError, SttResult};
+
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum DownloadProxy {
+    Environment,
+    Disabled,
+    Explicit(String),
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct DownloadConfig {
+    proxy: DownloadProxy,
+    no_proxy: Vec<String>,
+    ca_bundle: Option<PathBuf>,
+    mirrors: Vec<String>,
+}
+
+impl DownloadConfig {
+    pub(crate) fn from_table(
+        download_table: Option<&toml::Table>,
+        app_data_dir: &Path,
+    ) -> SttResult<Self> {
+        let configured_proxy = download_table
+            .and_then(|table| table.get("proxy"))
+            .and_then(toml::Value::as_str)
+            .unwrap_or_default()
+            .trim();
+        let proxy = match configured_proxy {
+            "" => DownloadProxy::Environment,
+            "none" => DownloadProxy::Disabled,
+            explicit => DownloadProxy::Explicit(explicit.to_string()),
+        };
+        let no_proxy = string_list(download_table, "no_proxy")?;
+        let ca_bundle = download_table
+            .and_then(|table| table.get("ca_bundle"))
+            .and_then(toml::Value::as_str)
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+            .map(|path| resolve_data_path(app_data_dir, path));
+        let mirrors = string_list(download_table, "mirrors")?
+            .into_iter()
+            .map(|mirror| mirror.trim_end_matches('/').to_string())
+            .collect();
+
+        Ok(Self {
+            proxy,
+            no_proxy,
+            ca_bundle,
+            mirrors,
+        })
+    }
+
+    pub(crate) fn proxy(&self) -> &DownloadProxy {
+        &self.proxy
+    }
+
+    pub(crate) fn no_proxy(&self) -> &[String] {
+        &self.no_proxy
+    }
+
+    pub(crate) fn ca_bundle(&self) -> Option<&Path> {
+        self.ca_bundle.as_deref()
+    }
+
+    pub(crate) fn mirrors(&self) -> &[String] {
+        &self.mirrors
+    }
+}
+
+fn string_list(download_table: Option<&toml::Table>, key: &str) -> SttResult<Vec<String>> {
+    let Some(value) = download_table.and_then(|table| table.get(key)) else {
+        return Ok(Vec::new());
+    };
+
+    let Some(values) = value.as_array() else {
+        return Err(SttError::model_path(format!(
+            "config [download].{key} must be an array of strings"
+        )));
+    };
+
+    values
+        .iter()
+        .map(|value| {
+            value
+                .as_str()
+                .map(|value| value.trim().to_string())
+                .ok_or_else(|| {
+                    SttError::model_path(format!(
+                        "config [download].{key} must be an array of strings"
+                    ))
+                })
+        })
+        .filter(|value| !matches!(value, Ok(value) if value.is_empty()))
+        .collect()
+}
+
+fn resolve_data_path(app_data_dir: &Path, configured_path: &str) -> PathBuf {
+    if let Some(relative_home_path) = configured_path.strip_prefix("~/")
+        && let Some(home) = std::env::var_os("HOME")
+    {
+        return PathBuf::from(home).join(relative_home_path);
+    }
+
+    let path = PathBuf::from(configured_path);
+    if path.is_absolute() {
+        return path;
+    }
+
+    app_data_dir.join(path)
+}
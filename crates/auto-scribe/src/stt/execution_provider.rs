@@ -0,0 +1,157 @@
+use crate::stt::{SttError, SttResult};
+
+use ort::ep::ExecutionProviderDispatch;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ExecutionProvider {
+    Cpu,
+    Cuda,
+    TensorRt,
+    OpenVino,
+    Rocm,
+    MiGraphX,
+    Xnnpack,
+}
+
+impl ExecutionProvider {
+    pub(crate) const ALL: [Self; 7] = [
+        Self::Cpu,
+        Self::Cuda,
+        Self::TensorRt,
+        Self::OpenVino,
+        Self::Rocm,
+        Self::MiGraphX,
+        Self::Xnnpack,
+    ];
+
+    pub(crate) fn from_config_name(name: &str) -> SttResult<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|provider| provider.config_name().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| {
+                SttError::model_path(format!(
+                    "config [model].execution_providers: unknown provider {name:?}; expected one of {}",
+                    Self::ALL
+                        .map(Self::config_name)
+                        .join(", ")
+                ))
+            })
+    }
+
+    pub(crate) fn config_name(self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::Cuda => "cuda",
+            Self::TensorRt => "tensorrt",
+            Self::OpenVino => "openvino",
+            Self::Rocm => "rocm",
+            Self::MiGraphX => "migraphx",
+            Self::Xnnpack => "xnnpack",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Cpu => "CPU",
+            Self::Cuda => "CUDA",
+            Self::TensorRt => "TensorRT",
+            Self::OpenVino => "OpenVINO",
+            Self::Rocm => "ROCm",
+            Self::MiGraphX => "MIGraphX",
+            Self::Xnnpack => "XNNPACK",
+        }
+    }
+
+    pub(crate) fn is_gpu(self) -> bool {
+        matches!(
+            self,
+            Self::Cuda | Self::TensorRt | Self::OpenVino | Self::Rocm | Self::MiGraphX
+        )
+    }
+
+    pub(crate) fn is_compiled(self) -> bool {
+        match self {
+            Self::Cpu => true,
+            Self::Cuda => cfg!(feature = "cuda"),
+            Self::TensorRt => cfg!(feature = "tensorrt"),
+            Self::OpenVino => cfg!(feature = "openvino"),
+            Self::Rocm => cfg!(feature = "rocm"),
+            Self::MiGraphX => cfg!(feature = "migraphx"),
+            Self::Xnnpack => cfg!(feature = "xnnpack"),
+        }
+    }
+
+    pub(crate) fn dispatch(self) -> Option<ExecutionProviderDispatch> {
+        match self {
+            Self::Cpu => Some(ort::ep::CPU::default().build()),
+            #[cfg(feature = "cuda")]
+            Self::Cuda => Some(ort::ep::CUDA::default().build()),
+            #[cfg(feature = "tensorrt")]
+            Self::TensorRt => Some(ort::ep::TensorRT::default().build()),
+            #[cfg(feature = "openvino")]
+            Self::OpenVino => Some(ort::ep::OpenVINO::default().build()),
+            #[cfg(feature = "rocm")]
+            Self::Rocm => Some(ort::ep::ROCm::default().build()),
+            #[cfg(feature = "migraphx")]
+            Self::MiGraphX => Some(ort::ep::MIGraphX::default().build()),
+            #[cfg(feature = "xnnpack")]
+            Self::Xnnpack => Some(ort::ep::XNNPACK::default().build()),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn execution_provider_list_label(providers: &[ExecutionProvider]) -> String {
+    providers
+        .iter()
+        .map(|provider| provider.label())
+        .collect::<Vec<_>>()
+        .join(" → ")
+}
+
+/// Turns `provider` off if it is in the list, or on just ahead of CPU otherwise, keeping the
+/// order of the other providers.
+pub(crate) fn toggle_execution_provider(
+    providers: &[ExecutionProvider],
+    provider: ExecutionProvider,
+) -> Vec<ExecutionProvider> {
+    if providers.contains(&provider) {
+        return providers
+            .iter()
+            .copied()
+            .filter(|&listed| listed != provider)
+            .collect();
+    }
+
+    let mut toggled = providers.to_vec();
+    let position = toggled
+        .iter()
+        .position(|&listed| listed == ExecutionProvider::Cpu)
+        .unwrap_or(toggled.len());
+    toggled.insert(position, provider);
+    toggled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ExecutionProvider::{Cpu, Cuda, OpenVino, TensorRt};
+
+    #[test]
+    fn toggles_providers_ahead_of_cpu_in_place() {
+        assert_eq!(toggle_execution_provider(&[Cpu], Cuda), vec![Cuda, Cpu]);
+        assert_eq!(
+            toggle_execution_provider(&[TensorRt, Cpu], OpenVino),
+            vec![TensorRt, OpenVino, Cpu]
+        );
+        assert_eq!(
+            toggle_execution_provider(&[TensorRt, Cuda, Cpu], TensorRt),
+            vec![Cuda, Cpu]
+        );
+        assert_eq!(
+            toggle_execution_provider(&[Cuda], OpenVino),
+            vec![Cuda, OpenVino]
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use crate::stt::{
+    AudioProcessing, AudioRecording, ModelConfig, SttError, SttResult, Transcript, WorkerEvent,
+    WorkerRequest, read_wav, spawn_stt_worker, validate_model_dir,
+};
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, atomic::AtomicU64},
+};
+
+/// Transcribes a WAV file with the configured model and audio processing, outside the GUI.
+/// `diarize` labels speakers even when `[diarization]` is disabled or the file is short.
+pub(crate) fn transcribe_file(
+    model_config: &ModelConfig,
+    path: &Path,
+    diarize: bool,
+) -> SttResult<Transcript> {
+    let recording = read_wav(path)?;
+    let diarization = model_config.diarization();
+    let diarization_model = if diarize {
+        Some(diarization.model_path().to_path_buf())
+    } else {
+        diarization.model_for(&recording)
+    };
+
+    transcribe_recording_variants(
+        model_config,
+        recording,
+        &[model_config.audio_processing()],
+        diarization_model,
+    )?
+    .pop()
+    .ok_or_else(|| SttError::worker_channel("STT worker stopped without a transcript"))
+}
+
+/// Transcribes a WAV file without and then with audio processing, using one loaded model.
+/// When no processing stage is configured, every stage is compared.
+pub(crate) fn compare_processing(
+    model_config: &ModelConfig,
+    path: &Path,
+) -> SttResult<(AudioProcessing, Transcript, Transcript)> {
+    let mut processing = model_config.audio_processing();
+    if !processing.is_enabled() {
+        processing = processing.all_stages();
+    }
+
+    let mut transcripts = transcribe_recording_variants(
+        model_config,
+        read_wav(path)?,
+        &[AudioProcessing::default(), processing],
+        None,
+    )?;
+    match (transcripts.pop(), transcripts.pop()) {
+        (Some(processed), Some(unprocessed)) => Ok((processing, unprocessed, processed)),
+        _ => Err(SttError::worker_channel(
+            "STT worker stopped without a transcript",
+        )),
+    }
+}
+
+/// Returns one transcript per entry of `variants`, in order.
+fn transcribe_recording_variants(
+    model_config: &ModelConfig,
+    recording: AudioRecording,
+    variants: &[AudioProcessing],
+    diarization_model: Option<PathBuf>,
+) -> SttResult<Vec<Transcript>> {
+    validate_model_dir(model_config.model_dir())?;
+
+    let (event_tx, event_rx) = async_channel::unbounded();
+    let worker_tx = spawn_stt_worker(
+        model_config.model_dir().to_path_buf(),
+        model_config.execution_providers().to_vec(),
+        model_config.runtime_options().clone(),
+        Arc::new(AtomicU64::new(0)),
+        event_tx,
+    )?;
+    let mut transcripts = Vec::with_capacity(variants.len());
+
+    while transcripts.len() < variants.len() {
+        let event = event_rx
+            .recv_blocking()
+            .map_err(|_| SttError::worker_channel("STT worker stopped without a transcript"))?;
+        match event {
+            WorkerEvent::Ready(_) => {
+                for processing in variants {
+                    worker_tx
+                        .send_blocking(WorkerRequest::Transcribe {
+                            recording: recording.clone(),
+                            processing: *processing,
+                            diarization_model: diarization_model.clone(),
+                            generation: 0,
+                        })
+                        .map_err(|_| {
+                            SttError::worker_channel("could not send recording to STT worker")
+                        })?;
+                }
+            }
+            WorkerEvent::Transcript(transcript) => transcripts.push(transcript),
+            WorkerEvent::Error(message) | WorkerEvent::WorkerCrashed(message) => {
+                return Err(SttError::speech_to_text(message));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(transcripts)
+}
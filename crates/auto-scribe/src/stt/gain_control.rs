@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+const FRAME_DURATION: Duration = Duration::from_millis(20);
+/// Frames quieter than this are treated as silence and keep the previous gain.
+const SPEECH_GATE_DBFS: f32 = -50.0;
+const MAX_GAIN_DB: f32 = 30.0;
+const MIN_GAIN_DB: f32 = -20.0;
+/// Per-frame smoothing: gain drops quickly on loud speech and rises slowly after it.
+const ATTACK: f32 = 0.5;
+const RELEASE: f32 = 0.05;
+const PEAK_CEILING: f32 = 0.98;
+
+/// Slowly varying gain that brings speech frames to `target_level_dbfs` RMS, with a hard
+/// ceiling so loud syllables cannot clip.
+pub(crate) fn normalize_loudness(samples: &mut [f32], sample_rate: u32, target_level_dbfs: f32) {
+    let frame_len = (FRAME_DURATION.as_secs_f32() * sample_rate as f32).max(1.0) as usize;
+    let mut gain_db = 0.0;
+    let mut previous_gain = 1.0;
+    let mut first_speech = true;
+
+    for frame in samples.chunks_mut(frame_len) {
+        let rms =
+            (frame.iter().map(|sample| sample * sample).sum::<f32>() / frame.len() as f32).sqrt();
+        let level_dbfs = 20.0 * rms.max(f32::MIN_POSITIVE).log10();
+        if level_dbfs > SPEECH_GATE_DBFS {
+            let desired_db = (target_level_dbfs - level_dbfs).clamp(MIN_GAIN_DB, MAX_GAIN_DB);
+            if first_speech {
+                gain_db = desired_db;
+                previous_gain = db_to_gain(gain_db);
+                first_speech = false;
+            } else {
+                let rate = if desired_db < gain_db {
+                    ATTACK
+                } else {
+                    RELEASE
+                };
+                gain_db += (desired_db - gain_db) * rate;
+            }
+        }
+
+        let gain = db_to_gain(gain_db);
+        let step = (gain - previous_gain) / frame.len() as f32;
+        for (index, sample) in frame.iter_mut().enumerate() {
+            let sample_gain = previous_gain + step * (index + 1) as f32;
+            *sample = (*sample * sample_gain).clamp(-PEAK_CEILING, PEAK_CEILING);
+        }
+        previous_gain = gain;
+    }
+}
+
+fn db_to_gain(db: f32) -> f32 {
+    10.0_f32.powf(db / 20.0)
+}
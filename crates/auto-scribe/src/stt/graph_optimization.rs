@@ -0,0 +1,54 @@
+use crate::stt::{SttError, SttResult};
+
+use ort::session::builder::GraphOptimizationLevel;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum GraphOptimization {
+    Disable,
+    Basic,
+    Extended,
+    Layout,
+    All,
+}
+
+impl GraphOptimization {
+    pub(crate) const ALL: [Self; 5] = [
+        Self::Disable,
+        Self::Basic,
+        Self::Extended,
+        Self::Layout,
+        Self::All,
+    ];
+
+    pub(crate) fn from_config_name(name: &str) -> SttResult<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.config_name().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| {
+                SttError::model_path(format!(
+                    "config [model].graph_optimization: unknown level {name:?}; expected one of {}",
+                    Self::ALL.map(Self::config_name).join(", ")
+                ))
+            })
+    }
+
+    pub(crate) fn config_name(self) -> &'static str {
+        match self {
+            Self::Disable => "disable",
+            Self::Basic => "basic",
+            Self::Extended => "extended",
+            Self::Layout => "layout",
+            Self::All => "all",
+        }
+    }
+
+    pub(crate) fn level(self) -> GraphOptimizationLevel {
+        match self {
+            Self::Disable => GraphOptimizationLevel::Disable,
+            Self::Basic => GraphOptimizationLevel::Level1,
+            Self::Extended => GraphOptimizationLevel::Level2,
+            Self::Layout => GraphOptimizationLevel::Level3,
+            Self::All => GraphOptimizationLevel::All,
+        }
+    }
+}
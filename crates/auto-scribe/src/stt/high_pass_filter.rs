@@ -0,0 +1,49 @@
+use std::f32::consts::{FRAC_1_SQRT_2, PI};
+
+/// Second-order Butterworth high-pass biquad that removes rumble and DC offset below speech.
+pub(crate) struct HighPassFilter {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl HighPassFilter {
+    pub(crate) fn new(cutoff_hz: f32, sample_rate: u32) -> Self {
+        let omega = 2.0 * PI * cutoff_hz / sample_rate as f32;
+        let (sin, cos) = omega.sin_cos();
+        let alpha = sin / (2.0 * FRAC_1_SQRT_2);
+        let a0 = 1.0 + alpha;
+
+        Self {
+            b0: (1.0 + cos) / 2.0 / a0,
+            b1: -(1.0 + cos) / a0,
+            b2: (1.0 + cos) / 2.0 / a0,
+            a1: -2.0 * cos / a0,
+            a2: (1.0 - alpha) / a0,
+            x1: 0.0,
+            x2: 0.0,
+            y1: 0.0,
+            y2: 0.0,
+        }
+    }
+
+    pub(crate) fn process(&mut self, samples: &mut [f32]) {
+        for sample in samples {
+            let input = *sample;
+            let output = self.b0 * input + self.b1 * self.x1 + self.b2 * self.x2
+                - self.a1 * self.y1
+                - self.a2 * self.y2;
+            self.x2 = self.x1;
+            self.x1 = input;
+            self.y2 = self.y1;
+            self.y1 = output;
+            *sample = output;
+        }
+    }
+}
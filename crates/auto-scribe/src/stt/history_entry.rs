@@ -0,0 +1,13 @@
+use crate::stt::Transcript;
+
+use std::{path::PathBuf, time::SystemTime};
+
+#[derive(Clone, Debug)]
+pub(crate) struct HistoryEntry {
+    /// Stays the same while newer transcripts push the entry down the history, so actions
+    /// started from the history window reach the entry they were started on.
+    pub(crate) id: usize,
+    pub(crate) completed_at: SystemTime,
+    pub(crate) transcript: Transcript,
+    pub(crate) recording_path: Option<PathBuf>,
+}
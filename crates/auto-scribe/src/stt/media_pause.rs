@@ -0,0 +1,285 @@
+use crate::stt::{SttError, SttResult};
+
+use std::mem;
+use zbus::{blocking::Connection, zvariant::OwnedValue};
+
+const MPRIS_NAME_PREFIX: &str = "org.mpris.MediaPlayer2.";
+const MPRIS_PATH: &str = "/org/mpris/MediaPlayer2";
+const MPRIS_PLAYER_INTERFACE: &str = "org.mpris.MediaPlayer2.Player";
+
+/// Pauses the MPRIS media players that are playing and resumes exactly those players on
+/// `restore` or drop.
+pub(crate) struct MediaPauseGuard {
+    connection: Connection,
+    /// Bus names of the players this guard paused and has not resumed yet.
+    paused: Vec<String>,
+}
+
+impl MediaPauseGuard {
+    pub(crate) fn pause_playing_players() -> SttResult<Self> {
+        let connection = Connection::session().map_err(|error| {
+            SttError::media_pause(format!("connect to the session bus: {error}"))
+        })?;
+        Self::pause_on_connection(connection)
+    }
+
+    /// Accepts any bus connection, so a mock player on a private bus can stand in for real
+    /// ones. Players whose state cannot be read or that refuse to pause are left alone.
+    pub(crate) fn pause_on_connection(connection: Connection) -> SttResult<Self> {
+        let mut guard = Self {
+            connection,
+            paused: Vec::new(),
+        };
+
+        for player in guard.players()? {
+            if guard
+                .playback_status(&player)
+                .is_ok_and(|status| status == "Playing")
+                && guard.call_player(&player, "Pause").is_ok()
+            {
+                guard.paused.push(player);
+            }
+        }
+
+        Ok(guard)
+    }
+
+    pub(crate) fn paused_players(&self) -> usize {
+        self.paused.len()
+    }
+
+    /// Players that have quit, or that were stopped or started in the meantime, are not
+    /// touched. On failure the players that did not resume stay recorded, so dropping the
+    /// guard tries once more.
+    pub(crate) fn restore(&mut self) -> SttResult<()> {
+        if self.paused.is_empty() {
+            return Ok(());
+        }
+
+        let running = self.players()?;
+        let mut errors = Vec::new();
+        for player in mem::take(&mut self.paused) {
+            if !running.contains(&player) {
+                continue;
+            }
+
+            let resumed = self.playback_status(&player).and_then(|status| {
+                if status == "Paused" {
+                    self.call_player(&player, "Play")
+                } else {
+                    Ok(())
+                }
+            });
+            if let Err(error) = resumed {
+                errors.push(format!("{player}: {error}"));
+                self.paused.push(player);
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(SttError::media_pause(errors.join("; ")))
+        }
+    }
+
+    fn players(&self) -> SttResult<Vec<String>> {
+        let reply = self
+            .connection
+            .call_method(
+                Some("org.freedesktop.DBus"),
+                "/org/freedesktop/DBus",
+                Some("org.freedesktop.DBus"),
+                "ListNames",
+                &(),
+            )
+            .map_err(|error| SttError::media_pause(format!("list bus names: {error}")))?;
+        let names = reply
+            .body()
+            .deserialize::<Vec<String>>()
+            .map_err(|error| SttError::media_pause(format!("read bus names: {error}")))?;
+
+        Ok(names
+            .into_iter()
+            .filter(|name| name.starts_with(MPRIS_NAME_PREFIX))
+            .collect())
+    }
+
+    fn playback_status(&self, player: &str) -> SttResult<String> {
+        let reply = self
+            .connection
+            .call_method(
+                Some(player),
+                MPRIS_PATH,
+                Some("org.freedesktop.DBus.Properties"),
+                "Get",
+                &(MPRIS_PLAYER_INTERFACE, "PlaybackStatus"),
+            )
+            .map_err(|error| {
+                SttError::media_pause(format!("read the playback status of {player}: {error}"))
+            })?;
+        let status = reply.body().deserialize::<OwnedValue>().map_err(|error| {
+            SttError::media_pause(format!("read the playback status of {player}: {error}"))
+        })?;
+
+        String::try_from(status).map_err(|error| {
+            SttError::media_pause(format!("read the playback status of {player}: {error}"))
+        })
+    }
+
+    fn call_player(&self, player: &str, method: &str) -> SttResult<()> {
+        self.connection
+            .call_method(
+                Some(player),
+                MPRIS_PATH,
+                Some(MPRIS_PLAYER_INTERFACE),
+                method,
+                &(),
+            )
+            .map(|_| ())
+            .map_err(|error| SttError::media_pause(format!("{method} {player}: {error}")))
+    }
+}
+
+impl Drop for MediaPauseGuard {
+    fn drop(&mut self) {
+        let _ = self.restore();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{
+        error::Error,
+        io::{BufRead, BufReader},
+        process::{Child, Command, Stdio},
+        sync::{Arc, Mutex},
+    };
+
+    /// A `dbus-daemon` of its own, so the test never touches the user's media players.
+    struct PrivateBus {
+        daemon: Child,
+        address: String,
+        /// Each mock player stays on the bus while its connection is open.
+        players: Vec<Connection>,
+    }
+
+    impl PrivateBus {
+        fn start() -> Option<Self> {
+            let mut daemon = Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--nopidfile", "--print-address=1"])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .ok()?;
+            let mut address = String::new();
+            BufReader::new(daemon.stdout.take()?)
+                .read_line(&mut address)
+                .ok()?;
+
+            Some(Self {
+                daemon,
+                address: address.trim().to_string(),
+                players: Vec::new(),
+            })
+        }
+
+        fn connect(&self) -> zbus::Result<Connection> {
+            zbus::blocking::connection::Builder::address(self.address.as_str())?.build()
+        }
+
+        fn serve_player(&mut self, name: &str, status: &str) -> zbus::Result<MockPlayer> {
+            let player = MockPlayer(Arc::new(Mutex::new(status.to_string())));
+            let connection = zbus::blocking::connection::Builder::address(self.address.as_str())?
+                .name(format!("{MPRIS_NAME_PREFIX}{name}"))?
+                .serve_at(MPRIS_PATH, player.clone())?
+                .build()?;
+            self.players.push(connection);
+            Ok(player)
+        }
+    }
+
+    impl Drop for PrivateBus {
+        fn drop(&mut self) {
+            let _ = self.daemon.kill();
+            let _ = self.daemon.wait();
+        }
+    }
+
+    #[derive(Clone)]
+    struct MockPlayer(Arc<Mutex<String>>);
+
+    impl MockPlayer {
+        fn status(&self) -> String {
+            self.0
+                .lock()
+                .map(|status| status.clone())
+                .unwrap_or_default()
+        }
+
+        fn set_status(&self, status: &str) {
+            if let Ok(mut current) = self.0.lock() {
+                *current = status.to_string();
+            }
+        }
+    }
+
+    #[zbus::interface(name = "org.mpris.MediaPlayer2.Player")]
+    impl MockPlayer {
+        fn pause(&self) {
+            self.set_status("Paused");
+        }
+
+        fn play(&self) {
+            self.set_status("Playing");
+        }
+
+        #[zbus(property)]
+        fn playback_status(&self) -> String {
+            self.status()
+        }
+    }
+
+    #[test]
+    fn pauses_playing_players_and_resumes_only_those() -> Result<(), Box<dyn Error>> {
+        let Some(mut bus) = PrivateBus::start() else {
+            eprintln!("skipping: dbus-daemon is not available");
+            return Ok(());
+        };
+        let playing = bus.serve_player("playing", "Playing")?;
+        let already_paused = bus.serve_player("already_paused", "Paused")?;
+        let stopped_meanwhile = bus.serve_player("stopped_meanwhile", "Playing")?;
+
+        let mut guard = MediaPauseGuard::pause_on_connection(bus.connect()?)?;
+        assert_eq!(guard.paused_players(), 2);
+        assert_eq!(playing.status(), "Paused");
+        assert_eq!(already_paused.status(), "Paused");
+        assert_eq!(stopped_meanwhile.status(), "Paused");
+
+        stopped_meanwhile.set_status("Stopped");
+        guard.restore()?;
+
+        assert_eq!(playing.status(), "Playing");
+        assert_eq!(already_paused.status(), "Paused");
+        assert_eq!(stopped_meanwhile.status(), "Stopped");
+        Ok(())
+    }
+
+    #[test]
+    fn dropping_the_guard_resumes_players() -> Result<(), Box<dyn Error>> {
+        let Some(mut bus) = PrivateBus::start() else {
+            eprintln!("skipping: dbus-daemon is not available");
+            return Ok(());
+        };
+        let playing = bus.serve_player("playing", "Playing")?;
+
+        let guard = MediaPauseGuard::pause_on_connection(bus.connect()?)?;
+        assert_eq!(playing.status(), "Paused");
+        drop(guard);
+
+        assert_eq!(playing.status(), "Playing");
+        Ok(())
+    }
+}
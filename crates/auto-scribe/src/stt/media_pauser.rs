@@ -0,0 +1,119 @@
+use crate::stt::{MediaPauseGuard, SttError, SttResult, WorkerEvent};
+
+use async_channel::Sender;
+use std::thread::{self, JoinHandle};
+
+enum MediaCommand {
+    Pause,
+    Resume,
+}
+
+/// Runs `MediaPauseGuard` on its own thread, so a media player that does not answer on
+/// D-Bus cannot freeze the UI. Commands run in the order they were sent, and failures come
+/// back as `WorkerEvent::MediaPauseError`.
+pub(crate) struct MediaPauser {
+    event_tx: Sender<WorkerEvent>,
+    commands: Option<Sender<MediaCommand>>,
+    thread: Option<JoinHandle<()>>,
+    /// A pause was sent and has not been followed by a resume yet.
+    paused: bool,
+}
+
+impl MediaPauser {
+    pub(crate) fn new(event_tx: Sender<WorkerEvent>) -> Self {
+        Self {
+            event_tx,
+            commands: None,
+            thread: None,
+            paused: false,
+        }
+    }
+
+    pub(crate) fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub(crate) fn pause(&mut self) -> SttResult<()> {
+        if self.paused {
+            return Ok(());
+        }
+
+        self.send(MediaCommand::Pause)?;
+        self.paused = true;
+        Ok(())
+    }
+
+    pub(crate) fn resume(&mut self) -> SttResult<()> {
+        if !self.paused {
+            return Ok(());
+        }
+
+        self.paused = false;
+        self.send(MediaCommand::Resume)
+    }
+
+    /// Resumes paused players and waits for the thread to finish, so they are not left
+    /// paused when the app exits.
+    pub(crate) fn shut_down(&mut self) {
+        let _ = self.resume();
+        self.commands = None;
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+
+    fn send(&mut self, command: MediaCommand) -> SttResult<()> {
+        if self.commands.is_none() {
+            self.spawn()?;
+        }
+
+        self.commands
+            .as_ref()
+            .ok_or_else(|| SttError::media_pause("media pause thread is not running"))?
+            .send_blocking(command)
+            .map_err(|_| SttError::media_pause("media pause thread stopped"))
+    }
+
+    fn spawn(&mut self) -> SttResult<()> {
+        let (commands, command_rx) = async_channel::unbounded();
+        let event_tx = self.event_tx.clone();
+        let thread = thread::Builder::new()
+            .name("auto-scribe-media-pause".to_string())
+            .spawn(move || {
+                let mut guard: Option<MediaPauseGuard> = None;
+                while let Ok(command) = command_rx.recv_blocking() {
+                    let result = match command {
+                        MediaCommand::Pause if guard.is_none() => {
+                            MediaPauseGuard::pause_playing_players()
+                                .map(|paused| {
+                                    guard = (paused.paused_players() > 0).then_some(paused)
+                                })
+                                .map_err(|error| format!("Pausing media failed: {error}"))
+                        }
+                        MediaCommand::Pause => Ok(()),
+                        MediaCommand::Resume => match guard.take() {
+                            Some(mut paused) => paused
+                                .restore()
+                                .map_err(|error| format!("Media resume failed: {error}")),
+                            None => Ok(()),
+                        },
+                    };
+                    if let Err(message) = result {
+                        let _ = event_tx.send_blocking(WorkerEvent::MediaPauseError(message));
+                    }
+                }
+                // Dropping a guard that is still armed resumes its players.
+            })
+            .map_err(|error| SttError::media_pause(format!("spawn media pause thread: {error}")))?;
+
+        self.commands = Some(commands);
+        self.thread = Some(thread);
+        Ok(())
+    }
+}
+
+impl Drop for MediaPauser {
+    fn drop(&mut self) {
+        self.shut_down();
+    }
+}
@@ -0,0 +1,143 @@
+use crate::stt::{
+    AudioRecorder, AudioRecording, AudioSource, MeetingChunk, SttError, SttResult,
+    TARGET_SAMPLE_RATE,
+};
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// Chunks are cut at the first pause after this much audio...
+const MIN_CHUNK_DURATION: Duration = Duration::from_secs(4);
+/// ...or at this length when nobody pauses.
+const MAX_CHUNK_DURATION: Duration = Duration::from_secs(20);
+const PAUSE_DURATION: Duration = Duration::from_millis(400);
+/// About -40 dBFS; quieter stretches count as pauses and quieter chunks are skipped.
+const SILENCE_RMS: f32 = 0.01;
+
+/// A long-running recording that is transcribed chunk by chunk into a text file.
+pub(crate) struct Meeting {
+    recorder: AudioRecorder,
+    source: AudioSource,
+    transcript_path: PathBuf,
+    started_at: Instant,
+    /// Samples already handed out per recorder input, for chunk offsets.
+    taken_samples: Vec<usize>,
+}
+
+impl Meeting {
+    pub(crate) fn start(
+        source: AudioSource,
+        monitor_source: &str,
+        meetings_dir: &Path,
+    ) -> SttResult<Self> {
+        fs::create_dir_all(meetings_dir).map_err(|error| {
+            SttError::audio_device(format!("create {}: {error}", meetings_dir.display()))
+        })?;
+        let started = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        let transcript_path = meetings_dir.join(format!("meeting-{started}.txt"));
+        fs::write(
+            &transcript_path,
+            format!("Meeting transcript; {}\n\n", source.label()),
+        )
+        .map_err(|error| {
+            SttError::audio_device(format!("write {}: {error}", transcript_path.display()))
+        })?;
+
+        let recorder = AudioRecorder::new(source, monitor_source)?;
+        recorder.start()?;
+        let taken_samples = vec![0; recorder.inputs().len()];
+
+        Ok(Self {
+            recorder,
+            source,
+            transcript_path,
+            started_at: Instant::now(),
+            taken_samples,
+        })
+    }
+
+    pub(crate) fn source(&self) -> AudioSource {
+        self.source
+    }
+
+    pub(crate) fn transcript_path(&self) -> &Path {
+        &self.transcript_path
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Cuts every input that reached a pause or the maximum chunk length. Silent chunks are
+    /// dropped but still advance the offsets.
+    pub(crate) fn take_chunks(&mut self) -> SttResult<Vec<(AudioRecording, MeetingChunk)>> {
+        self.take_chunks_when(is_chunk_ready)
+    }
+
+    /// Stops recording and returns whatever has not been handed out yet.
+    pub(crate) fn stop(mut self) -> SttResult<Vec<(AudioRecording, MeetingChunk)>> {
+        let chunks = self.take_chunks_when(|samples| !samples.is_empty())?;
+        self.recorder.stop()?;
+        Ok(chunks)
+    }
+
+    pub(crate) fn take_stream_error(&self) -> SttResult<Option<String>> {
+        self.recorder.take_stream_error()
+    }
+
+    fn take_chunks_when(
+        &mut self,
+        should_take: fn(&[f32]) -> bool,
+    ) -> SttResult<Vec<(AudioRecording, MeetingChunk)>> {
+        let mut chunks = Vec::new();
+        for (input, taken_samples) in self.recorder.inputs().iter().zip(&mut self.taken_samples) {
+            let Some(recording) = input.take_when(should_take)? else {
+                continue;
+            };
+
+            let start =
+                Duration::from_secs_f64(*taken_samples as f64 / f64::from(TARGET_SAMPLE_RATE));
+            *taken_samples += recording.samples.len();
+            if rms(&recording.samples) < SILENCE_RMS {
+                continue;
+            }
+
+            chunks.push((
+                recording,
+                MeetingChunk {
+                    channel: input.channel(),
+                    start,
+                    transcript_path: self.transcript_path.clone(),
+                },
+            ));
+        }
+        Ok(chunks)
+    }
+}
+
+fn is_chunk_ready(samples: &[f32]) -> bool {
+    let samples_for =
+        |duration: Duration| (duration.as_secs_f64() * f64::from(TARGET_SAMPLE_RATE)) as usize;
+    if samples.len() >= samples_for(MAX_CHUNK_DURATION) {
+        return true;
+    }
+    if samples.len() < samples_for(MIN_CHUNK_DURATION) {
+        return false;
+    }
+
+    let tail = &samples[samples.len() - samples_for(PAUSE_DURATION)..];
+    rms(tail) < SILENCE_RMS
+}
+
+fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    (samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len() as f32).sqrt()
+}
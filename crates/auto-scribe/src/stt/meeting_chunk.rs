@@ -0,0 +1,56 @@
+use crate::stt::{CaptureChannel, SttError, SttResult, Transcript};
+
+use std::{fs::OpenOptions, io::Write, path::PathBuf, time::Duration};
+
+/// Where a piece of a meeting recording belongs in the rolling transcript file.
+#[derive(Clone, Debug)]
+pub(crate) struct MeetingChunk {
+    pub(crate) channel: CaptureChannel,
+    /// Offset of the chunk from the start of the meeting.
+    pub(crate) start: Duration,
+    pub(crate) transcript_path: PathBuf,
+}
+
+impl MeetingChunk {
+    /// Appends one `[HH:MM:SS] Channel: text` line per transcript segment.
+    pub(crate) fn append(&self, transcript: &Transcript) -> SttResult<()> {
+        let lines = transcript
+            .segments
+            .iter()
+            .map(|segment| (self.start + segment.start, segment.text()))
+            .filter(|(_, text)| !text.is_empty())
+            .map(|(offset, text)| {
+                format!(
+                    "[{}] {}: {text}\n",
+                    format_offset(offset),
+                    self.channel.label()
+                )
+            })
+            .collect::<String>();
+        if lines.is_empty() {
+            return Ok(());
+        }
+
+        OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&self.transcript_path)
+            .and_then(|mut file| file.write_all(lines.as_bytes()))
+            .map_err(|error| {
+                SttError::speech_to_text(format!(
+                    "append to {}: {error}",
+                    self.transcript_path.display()
+                ))
+            })
+    }
+}
+
+fn format_offset(offset: Duration) -> String {
+    let seconds = offset.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
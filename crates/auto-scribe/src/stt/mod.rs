@@ -1,36 +1,145 @@
+mod audio_processing;
 mod audio_recorder;
 mod audio_recording;
+mod audio_source;
+mod capture_channel;
+mod capture_input;
+mod capture_stream;
+mod diarization_config;
+mod diarizer;
+mod download_config;
+mod execution_provider;
+mod file_transcription;
+mod gain_control;
+mod graph_optimization;
+mod high_pass_filter;
+mod history_entry;
+mod media_pause;
+mod media_pauser;
+mod meeting;
+mod meeting_chunk;
+mod model_checksum;
 mod model_config;
 mod model_dir;
 mod model_download;
+mod model_import;
+mod model_inventory;
+mod monitor_capture;
+mod noise_suppression;
+mod optimized_model_cache;
+mod pactl_speaker_backend;
+mod provider_failure;
+#[cfg(target_os = "linux")]
+mod pulse_speaker_backend;
+mod queued_recording;
 mod recorder_state;
+mod recording_store;
+mod runtime_options;
 mod session;
+mod sink_volume;
 mod snapshot;
+mod speaker_assignment;
+mod speaker_backend;
+mod speaker_command;
+mod speaker_mode;
 mod speaker_mute;
+mod speaker_restore;
+mod speaker_restore_state;
+mod speaker_turn;
 mod state;
+mod streaming_resampler;
 mod stt_error;
 mod stt_result;
+mod transcript;
+mod transcript_assembler;
+mod transcript_segment;
+mod transcript_word;
+mod wav_file;
 mod worker_event;
+mod worker_frame;
+mod worker_health;
+mod worker_process;
 mod worker_request;
 mod worker_runtime;
+mod wpctl_speaker_backend;
 
 // ---------------------------------------------------------------------------------------------- //
 
+pub(crate) use audio_processing::AudioProcessing;
 pub(crate) use audio_recorder::AudioRecorder;
 pub(crate) use audio_recording::AudioRecording;
+pub(crate) use audio_source::AudioSource;
+pub(crate) use capture_channel::CaptureChannel;
+pub(crate) use capture_input::{CaptureInput, record_input_data};
+pub(crate) use capture_stream::CaptureStream;
+pub(crate) use diarization_config::DiarizationConfig;
+pub(crate) use diarizer::Diarizer;
+pub(crate) use download_config::{DownloadConfig, DownloadProxy};
+pub(crate) use execution_provider::{
+    ExecutionProvider, execution_provider_list_label, toggle_execution_provider,
+};
+pub(crate) use file_transcription::{compare_processing, transcribe_file};
+pub(crate) use gain_control::normalize_loudness;
+pub(crate) use graph_optimization::GraphOptimization;
+pub(crate) use high_pass_filter::HighPassFilter;
+pub(crate) use history_entry::HistoryEntry;
+pub(crate) use media_pause::MediaPauseGuard;
+pub(crate) use media_pauser::MediaPauser;
+pub(crate) use meeting::Meeting;
+pub(crate) use meeting_chunk::MeetingChunk;
+pub(crate) use model_checksum::{
+    ModelChecksumStatus, verify_or_record_model_checksums, write_model_checksums,
+};
 pub(crate) use model_config::ModelConfig;
 pub(crate) use model_dir::validate_model_dir;
-pub(crate) use model_download::spawn_model_download;
+pub(crate) use model_download::{install_staged_model, spawn_model_download, staging_dir};
+pub(crate) use model_import::{import_extract_dir, import_model, spawn_model_import};
+pub(crate) use model_inventory::{
+    InstalledModel, list_installed_models, record_model_last_used, remove_installed_model,
+    remove_orphaned_staging_dirs,
+};
+pub(crate) use monitor_capture::MonitorCapture;
+pub(crate) use noise_suppression::suppress_noise;
+pub(crate) use optimized_model_cache::OptimizedModelCache;
+pub(crate) use pactl_speaker_backend::PactlSpeakerBackend;
+pub(crate) use provider_failure::describe_provider_failure;
+#[cfg(target_os = "linux")]
+pub(crate) use pulse_speaker_backend::PulseSpeakerBackend;
+pub(crate) use queued_recording::QueuedRecording;
 pub(crate) use recorder_state::RecorderState;
+pub(crate) use recording_store::RecordingStore;
+pub(crate) use runtime_options::RuntimeOptions;
 pub(crate) use session::Session;
+pub(crate) use sink_volume::SinkVolume;
 pub(crate) use snapshot::Snapshot;
+pub(crate) use speaker_assignment::assign_speakers;
+pub(crate) use speaker_backend::SpeakerBackend;
+pub(crate) use speaker_command::command_output;
+pub(crate) use speaker_mode::SpeakerMode;
 pub(crate) use speaker_mute::SpeakerMuteGuard;
+pub(crate) use speaker_restore::SpeakerRestore;
+pub(crate) use speaker_restore_state::SpeakerRestoreState;
+pub(crate) use speaker_turn::SpeakerTurn;
 pub(crate) use state::State;
+pub(crate) use streaming_resampler::StreamingResampler;
 pub(crate) use stt_error::SttError;
 pub(crate) use stt_result::SttResult;
+pub(crate) use transcript::Transcript;
+pub(crate) use transcript_assembler::TranscriptAssembler;
+pub(crate) use transcript_segment::TranscriptSegment;
+pub(crate) use transcript_word::TranscriptWord;
+pub(crate) use wav_file::{read_wav, write_wav};
 pub(crate) use worker_event::WorkerEvent;
+pub(crate) use worker_frame::{
+    encode_request, read_event, read_request, write_event, write_request,
+};
+pub(crate) use worker_health::WorkerHealth;
+pub(crate) use worker_process::{run_stt_worker_process, spawn_stt_worker_process};
 pub(crate) use worker_request::WorkerRequest;
-pub(crate) use worker_runtime::spawn_stt_worker;
+pub(crate) use worker_runtime::{
+    prepare_audio, resample, run_supervised_stt_worker, spawn_stt_worker,
+};
+pub(crate) use wpctl_speaker_backend::WpctlSpeakerBackend;
 
 use std::time::Duration;
 
@@ -39,6 +148,8 @@ pub(crate) const DEFAULT_MODEL_DIRECTORY: &str = "models/nemotron-speech-streami
 pub(crate) const DEFAULT_MODEL_BASE_URL: &str =
     "https://huggingface.co/altunenes/parakeet-rs/resolve/main/nemotron-speech-streaming-en-0.6b";
 pub(crate) const TARGET_SAMPLE_RATE: u32 = 16_000;
+/// Nemotron decodes 560 ms of 16 kHz audio per streaming step.
+pub(crate) const DECODER_CHUNK_SAMPLES: usize = 8_960;
 pub(crate) const MAX_RECORDING_SECONDS: usize = 60;
 pub(crate) const REQUIRED_MODEL_FILES: [&str; 4] = [
     "encoder.onnx",
@@ -46,3 +157,5 @@ pub(crate) const REQUIRED_MODEL_FILES: [&str; 4] = [
     "decoder_joint.onnx",
     "tokenizer.model",
 ];
+pub(crate) const STT_WORKER_ARG: &str = "--stt-worker";
+pub(crate) const MODEL_CHECKSUM_FILE: &str = "SHA256SUMS";
@@ -0,0 +1,191 @@
+use crate::stt::{MODEL_CHECKSUM_FILE, REQUIRED_MODEL_FILES, SttError, SttResult};
+
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io,
+    path::Path,
+};
+
+const SHA256_HEX_LEN: usize = 64;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ModelChecksumStatus {
+    Verified,
+    Recorded,
+}
+
+impl ModelChecksumStatus {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Verified => "checksums verified",
+            Self::Recorded => {
+                "no SHA256SUMS manifest; unverified import allowed, checksums recorded"
+            }
+        }
+    }
+}
+
+/// Imports must carry a `SHA256SUMS` manifest; without one the files are only accepted, and their
+/// checksums recorded, when `allow_unverified` is set.
+pub(crate) fn verify_or_record_model_checksums(
+    model_dir: &Path,
+    allow_unverified: bool,
+) -> SttResult<ModelChecksumStatus> {
+    if model_dir.join(MODEL_CHECKSUM_FILE).is_file() {
+        verify_model_checksums(model_dir)?;
+        return Ok(ModelChecksumStatus::Verified);
+    }
+
+    if !allow_unverified {
+        return Err(SttError::model_path(format!(
+            "the model import has no {MODEL_CHECKSUM_FILE} manifest; add one in `sha256sum` format, \
+             or pass --allow-unverified (set [model].allow_unverified_import = true for imports from \
+             the app) to accept the files without verification"
+        )));
+    }
+
+    write_model_checksums(model_dir)?;
+    Ok(ModelChecksumStatus::Recorded)
+}
+
+pub(crate) fn verify_model_checksums(model_dir: &Path) -> SttResult<()> {
+    let manifest_path = model_dir.join(MODEL_CHECKSUM_FILE);
+    let manifest = fs::read_to_string(&manifest_path).map_err(|error| {
+        SttError::model_path(format!("read {}: {error}", manifest_path.display()))
+    })?;
+    let expected_digests = parse_checksum_manifest(&manifest, &manifest_path)?;
+
+    for file_name in REQUIRED_MODEL_FILES {
+        let Some(expected_digest) = expected_digests.get(file_name) else {
+            return Err(SttError::model_path(format!(
+                "{} has no checksum for {file_name}",
+                manifest_path.display()
+            )));
+        };
+
+        let file_path = model_dir.join(file_name);
+        let actual_digest = file_sha256(&file_path)?;
+        if !actual_digest.eq_ignore_ascii_case(expected_digest) {
+            return Err(SttError::model_path(format!(
+                "checksum mismatch for {}: expected {expected_digest}, found {actual_digest}",
+                file_path.display()
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn write_model_checksums(model_dir: &Path) -> SttResult<()> {
+    let manifest_path = model_dir.join(MODEL_CHECKSUM_FILE);
+    let mut manifest = String::new();
+
+    for file_name in REQUIRED_MODEL_FILES {
+        let digest = file_sha256(&model_dir.join(file_name))?;
+        manifest.push_str(&format!("{digest}  {file_name}\n"));
+    }
+
+    fs::write(&manifest_path, manifest).map_err(|error| {
+        SttError::model_path(format!("write {}: {error}", manifest_path.display()))
+    })
+}
+
+fn parse_checksum_manifest(
+    manifest: &str,
+    manifest_path: &Path,
+) -> SttResult<BTreeMap<String, String>> {
+    let mut digests = BTreeMap::new();
+
+    for (line_index, line) in manifest.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let Some((digest, file_name)) = line.split_once(char::is_whitespace) else {
+            return Err(SttError::model_path(format!(
+                "{} line {}: expected `<sha256>  <file>`",
+                manifest_path.display(),
+                line_index + 1
+            )));
+        };
+
+        if digest.len() != SHA256_HEX_LEN || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(SttError::model_path(format!(
+                "{} line {}: invalid SHA-256 digest",
+                manifest_path.display(),
+                line_index + 1
+            )));
+        }
+
+        let file_name = file_name.trim().trim_start_matches('*');
+        digests.insert(file_name.to_string(), digest.to_string());
+    }
+
+    Ok(digests)
+}
+
+fn file_sha256(file_path: &Path) -> SttResult<String> {
+    let mut file = File::open(file_path)
+        .map_err(|error| SttError::model_path(format!("open {}: {error}", file_path.display())))?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)
+        .map_err(|error| SttError::model_path(format!("hash {}: {error}", file_path.display())))?;
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model_dir_with_files(name: &str) -> SttResult<std::path::PathBuf> {
+        let model_dir = std::env::temp_dir().join(format!(
+            "auto-scribe-checksum-{name}-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&model_dir).map_err(|error| SttError::model_path(error.to_string()))?;
+        for file_name in REQUIRED_MODEL_FILES {
+            fs::write(model_dir.join(file_name), file_name.as_bytes())
+                .map_err(|error| SttError::model_path(error.to_string()))?;
+        }
+        Ok(model_dir)
+    }
+
+    fn remove(model_dir: &Path) -> SttResult<()> {
+        fs::remove_dir_all(model_dir).map_err(|error| SttError::model_path(error.to_string()))
+    }
+
+    #[test]
+    fn rejects_a_missing_manifest_unless_unverified_imports_are_allowed() -> SttResult<()> {
+        let model_dir = model_dir_with_files("missing")?;
+
+        assert!(verify_or_record_model_checksums(&model_dir, false).is_err());
+        assert!(!model_dir.join(MODEL_CHECKSUM_FILE).exists());
+        assert_eq!(
+            verify_or_record_model_checksums(&model_dir, true)?,
+            ModelChecksumStatus::Recorded
+        );
+        assert!(model_dir.join(MODEL_CHECKSUM_FILE).is_file());
+        remove(&model_dir)
+    }
+
+    #[test]
+    fn verifies_a_manifest_and_rejects_modified_files() -> SttResult<()> {
+        let model_dir = model_dir_with_files("verify")?;
+        write_model_checksums(&model_dir)?;
+
+        assert_eq!(
+            verify_or_record_model_checksums(&model_dir, false)?,
+            ModelChecksumStatus::Verified
+        );
+        if let Some(file_name) = REQUIRED_MODEL_FILES.first() {
+            fs::write(model_dir.join(file_name), b"tampered")
+                .map_err(|error| SttError::model_path(error.to_string()))?;
+        }
+        assert!(verify_or_record_model_checksums(&model_dir, true).is_err());
+        remove(&model_dir)
+    }
+}
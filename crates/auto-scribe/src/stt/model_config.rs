@@ -1,17 +1,43 @@
-use crate::stt::{DEFAULT_MODEL_BASE_URL, DEFAULT_MODEL_DIRECTORY, SttError, SttResult};
+use crate::stt::{
+    AudioProcessing, AudioSource, DEFAULT_MODEL_BASE_URL, DEFAULT_MODEL_DIRECTORY,
+    DiarizationConfig, DownloadConfig, ExecutionProvider, RecordingStore, RuntimeOptions,
+    SpeakerMode, SttError, SttResult,
+};
+use crate::{hotkey::DEFAULT_CANCEL_HOTKEY, output::OutputProfile};
 
 use std::{
     env, fs,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
+const DEFAULT_DUCK_VOLUME_PERCENT: u8 = 20;
+const DEFAULT_MONITOR_SOURCE: &str = "@DEFAULT_MONITOR@";
+const MEETINGS_DIRECTORY: &str = "meetings";
+
 #[derive(Clone, Debug)]
 pub(crate) struct ModelConfig {
+    app_data_dir: PathBuf,
     config_path: PathBuf,
     model_dir: PathBuf,
     model_base_url: String,
-    use_gpu: bool,
-    auto_mute_speakers: bool,
+    execution_providers: Vec<ExecutionProvider>,
+    runtime_options: RuntimeOptions,
+    idle_unload_after: Option<Duration>,
+    worker_process: bool,
+    allow_unverified_import: bool,
+    speaker_mode: SpeakerMode,
+    duck_volume_percent: u8,
+    pause_media: bool,
+    recording_store: Option<RecordingStore>,
+    audio_processing: AudioProcessing,
+    audio_source: AudioSource,
+    monitor_source: String,
+    meeting_source: AudioSource,
+    diarization: DiarizationConfig,
+    cancel_hotkey: Option<String>,
+    download: DownloadConfig,
+    output_profiles: Vec<OutputProfile>,
 }
 
 impl ModelConfig {
@@ -28,7 +54,10 @@ impl ModelConfig {
             })?;
         }
 
-        let parsed = read_config_table(&config_path)?;
+        let mut parsed = read_config_table(&config_path)?;
+        if migrate_use_gpu(&mut parsed) {
+            write_config_table(&config_path, &parsed)?;
+        }
 
         let model_table = parsed.get("model").and_then(toml::Value::as_table);
         let configured_dir = model_table
@@ -39,8 +68,15 @@ impl ModelConfig {
             .and_then(|table| table.get("base_url"))
             .and_then(toml::Value::as_str)
             .unwrap_or(DEFAULT_MODEL_BASE_URL);
-        let use_gpu = model_table
-            .and_then(|table| table.get("use_gpu"))
+        let execution_providers = execution_providers_from_table(model_table)?;
+        let runtime_options = RuntimeOptions::from_table(model_table, &app_data_dir)?;
+        let idle_unload_after = idle_unload_after_from_table(model_table)?;
+        let worker_process = model_table
+            .and_then(|table| table.get("worker_process"))
+            .and_then(toml::Value::as_bool)
+            .unwrap_or(false);
+        let allow_unverified_import = model_table
+            .and_then(|table| table.get("allow_unverified_import"))
             .and_then(toml::Value::as_bool)
             .unwrap_or(false);
 
@@ -50,10 +86,37 @@ impl ModelConfig {
             .unwrap_or(configured_model_dir);
         let model_base_url = configured_base_url.trim_end_matches('/').to_string();
         let audio_table = parsed.get("audio").and_then(toml::Value::as_table);
-        let auto_mute_speakers = audio_table
-            .and_then(|table| table.get("auto_mute_speakers"))
+        let speaker_mode = speaker_mode_from_table(audio_table)?;
+        let duck_volume_percent = duck_volume_percent_from_table(audio_table)?;
+        let pause_media = audio_table
+            .and_then(|table| table.get("pause_media"))
             .and_then(toml::Value::as_bool)
             .unwrap_or(false);
+        let recording_store = RecordingStore::from_table(audio_table, &app_data_dir)?;
+        let audio_processing = AudioProcessing::from_table(audio_table)?;
+        let audio_source = audio_source_from_table(audio_table, "audio", AudioSource::Microphone)?;
+        let monitor_source = audio_table
+            .and_then(|table| table.get("monitor_source"))
+            .and_then(toml::Value::as_str)
+            .map(str::trim)
+            .filter(|source| !source.is_empty())
+            .unwrap_or(DEFAULT_MONITOR_SOURCE)
+            .to_string();
+        let meeting_table = parsed.get("meeting").and_then(toml::Value::as_table);
+        let meeting_source =
+            audio_source_from_table(meeting_table, "meeting", AudioSource::MicrophoneAndMonitor)?;
+        let diarization_table = parsed.get("diarization").and_then(toml::Value::as_table);
+        let diarization = DiarizationConfig::from_table(diarization_table, &app_data_dir)?;
+        let hotkey_table = parsed.get("hotkey").and_then(toml::Value::as_table);
+        let cancel_hotkey = hotkey_table
+            .and_then(|table| table.get("cancel"))
+            .and_then(toml::Value::as_str)
+            .unwrap_or(DEFAULT_CANCEL_HOTKEY)
+            .trim();
+        let cancel_hotkey = (!cancel_hotkey.is_empty()).then(|| cancel_hotkey.to_string());
+        let download_table = parsed.get("download").and_then(toml::Value::as_table);
+        let download = DownloadConfig::from_table(download_table, &app_data_dir)?;
+        let output_profiles = OutputProfile::list_from_value(parsed.get("output_profile"))?;
 
         if model_base_url.is_empty() {
             return Err(SttError::model_path(
@@ -62,11 +125,27 @@ impl ModelConfig {
         }
 
         Ok(Self {
+            app_data_dir,
             config_path,
             model_dir,
             model_base_url,
-            use_gpu,
-            auto_mute_speakers,
+            execution_providers,
+            runtime_options,
+            idle_unload_after,
+            worker_process,
+            allow_unverified_import,
+            speaker_mode,
+            duck_volume_percent,
+            pause_media,
+            recording_store,
+            audio_processing,
+            audio_source,
+            monitor_source,
+            meeting_source,
+            diarization,
+            cancel_hotkey,
+            download,
+            output_profiles,
         })
     }
 
@@ -78,44 +157,162 @@ impl ModelConfig {
         &self.model_dir
     }
 
-    pub(crate) fn model_url(&self, file_name: &str) -> String {
-        format!("{}/{}", self.model_base_url, file_name)
+    pub(crate) fn models_root(&self) -> PathBuf {
+        self.app_data_dir.join("models")
     }
 
-    pub(crate) fn use_gpu(&self) -> bool {
-        self.use_gpu
+    pub(crate) fn model_urls(&self, file_name: &str) -> Vec<String> {
+        std::iter::once(&self.model_base_url)
+            .chain(self.download.mirrors())
+            .map(|base_url| format!("{base_url}/{file_name}"))
+            .collect()
     }
 
-    pub(crate) fn set_use_gpu(&mut self, enabled: bool) -> SttResult<()> {
+    pub(crate) fn download(&self) -> &DownloadConfig {
+        &self.download
+    }
+
+    pub(crate) fn execution_providers(&self) -> &[ExecutionProvider] {
+        &self.execution_providers
+    }
+
+    pub(crate) fn runtime_options(&self) -> &RuntimeOptions {
+        &self.runtime_options
+    }
+
+    pub(crate) fn idle_unload_after(&self) -> Option<Duration> {
+        self.idle_unload_after
+    }
+
+    pub(crate) fn worker_process(&self) -> bool {
+        self.worker_process
+    }
+
+    pub(crate) fn allow_unverified_import(&self) -> bool {
+        self.allow_unverified_import
+    }
+
+    /// Saves the provider order; duplicates are dropped and an empty list means CPU only.
+    pub(crate) fn set_execution_providers(
+        &mut self,
+        providers: &[ExecutionProvider],
+    ) -> SttResult<()> {
+        let mut execution_providers = Vec::new();
+        for &provider in providers {
+            if !execution_providers.contains(&provider) {
+                execution_providers.push(provider);
+            }
+        }
+        if execution_providers.is_empty() {
+            execution_providers.push(ExecutionProvider::Cpu);
+        }
+        if execution_providers == self.execution_providers {
+            return Ok(());
+        }
+
         let mut parsed = read_config_table(&self.config_path)?;
-        upsert_model_use_gpu(&mut parsed, enabled);
+        upsert_model_execution_providers(&mut parsed, &execution_providers);
         self.write_config(parsed)?;
-        self.use_gpu = enabled;
+        self.execution_providers = execution_providers;
         Ok(())
     }
 
-    pub(crate) fn auto_mute_speakers(&self) -> bool {
-        self.auto_mute_speakers
+    pub(crate) fn speaker_mode(&self) -> SpeakerMode {
+        self.speaker_mode
+    }
+
+    pub(crate) fn duck_volume_percent(&self) -> u8 {
+        self.duck_volume_percent
     }
 
-    pub(crate) fn set_auto_mute_speakers(&mut self, enabled: bool) -> SttResult<()> {
+    pub(crate) fn set_speaker_mode(&mut self, mode: SpeakerMode) -> SttResult<()> {
         let mut parsed = read_config_table(&self.config_path)?;
-        upsert_audio_auto_mute(&mut parsed, enabled);
+        upsert_audio_speaker_mode(&mut parsed, mode);
         self.write_config(parsed)?;
-        self.auto_mute_speakers = enabled;
+        self.speaker_mode = mode;
         Ok(())
     }
 
-    fn write_config(&self, parsed: toml::Table) -> SttResult<()> {
-        let config_text = toml::to_string_pretty(&parsed).map_err(|error| {
-            SttError::model_path(format!("serialize {}: {error}", self.config_path.display()))
-        })?;
+    pub(crate) fn pause_media(&self) -> bool {
+        self.pause_media
+    }
+
+    pub(crate) fn set_pause_media(&mut self, enabled: bool) -> SttResult<()> {
+        let mut parsed = read_config_table(&self.config_path)?;
+        upsert_audio_pause_media(&mut parsed, enabled);
+        self.write_config(parsed)?;
+        self.pause_media = enabled;
+        Ok(())
+    }
 
-        fs::write(&self.config_path, config_text).map_err(|error| {
-            SttError::model_path(format!("write {}: {error}", self.config_path.display()))
-        })?;
+    pub(crate) fn output_profiles(&self) -> &[OutputProfile] {
+        &self.output_profiles
+    }
+
+    pub(crate) fn set_output_profiles(&mut self, profiles: Vec<OutputProfile>) -> SttResult<()> {
+        let mut parsed = read_config_table(&self.config_path)?;
+        if profiles.is_empty() {
+            parsed.remove("output_profile");
+        } else {
+            parsed.insert(
+                "output_profile".to_string(),
+                toml::Value::Array(
+                    profiles
+                        .iter()
+                        .map(|profile| toml::Value::Table(profile.to_table()))
+                        .collect(),
+                ),
+            );
+        }
+        self.write_config(parsed)?;
+        self.output_profiles = profiles;
         Ok(())
     }
+
+    pub(crate) fn recording_store(&self) -> Option<&RecordingStore> {
+        self.recording_store.as_ref()
+    }
+
+    pub(crate) fn audio_processing(&self) -> AudioProcessing {
+        self.audio_processing
+    }
+
+    pub(crate) fn audio_source(&self) -> AudioSource {
+        self.audio_source
+    }
+
+    pub(crate) fn monitor_source(&self) -> &str {
+        &self.monitor_source
+    }
+
+    pub(crate) fn meeting_source(&self) -> AudioSource {
+        self.meeting_source
+    }
+
+    pub(crate) fn meetings_dir(&self) -> PathBuf {
+        self.app_data_dir.join(MEETINGS_DIRECTORY)
+    }
+
+    pub(crate) fn diarization(&self) -> &DiarizationConfig {
+        &self.diarization
+    }
+
+    pub(crate) fn cancel_hotkey(&self) -> Option<&str> {
+        self.cancel_hotkey.as_deref()
+    }
+
+    fn write_config(&self, parsed: toml::Table) -> SttResult<()> {
+        write_config_table(&self.config_path, &parsed)
+    }
+}
+
+fn write_config_table(config_path: &Path, parsed: &toml::Table) -> SttResult<()> {
+    let config_text = toml::to_string_pretty(parsed).map_err(|error| {
+        SttError::model_path(format!("serialize {}: {error}", config_path.display()))
+    })?;
+
+    fs::write(config_path, config_text)
+        .map_err(|error| SttError::model_path(format!("write {}: {error}", config_path.display())))
 }
 
 fn read_config_table(config_path: &Path) -> SttResult<toml::Table> {
@@ -128,7 +325,140 @@ fn read_config_table(config_path: &Path) -> SttResult<toml::Table> {
         .map_err(|error| SttError::model_path(format!("parse {}: {error}", config_path.display())))
 }
 
-fn upsert_model_use_gpu(parsed: &mut toml::Table, enabled: bool) {
+fn execution_providers_from_table(
+    model_table: Option<&toml::Table>,
+) -> SttResult<Vec<ExecutionProvider>> {
+    let Some(value) = model_table.and_then(|table| table.get("execution_providers")) else {
+        return Ok(vec![ExecutionProvider::Cpu]);
+    };
+
+    let Some(names) = value.as_array() else {
+        return Err(SttError::model_path(
+            "config [model].execution_providers must be an array of strings",
+        ));
+    };
+
+    let mut execution_providers = Vec::new();
+    for name in names {
+        let Some(name) = name.as_str() else {
+            return Err(SttError::model_path(
+                "config [model].execution_providers must be an array of strings",
+            ));
+        };
+        let provider = ExecutionProvider::from_config_name(name)?;
+        if !execution_providers.contains(&provider) {
+            execution_providers.push(provider);
+        }
+    }
+
+    if execution_providers.is_empty() {
+        execution_providers.push(ExecutionProvider::Cpu);
+    }
+
+    Ok(execution_providers)
+}
+
+fn idle_unload_after_from_table(model_table: Option<&toml::Table>) -> SttResult<Option<Duration>> {
+    let Some(value) = model_table.and_then(|table| table.get("idle_unload_minutes")) else {
+        return Ok(None);
+    };
+
+    match value.as_integer() {
+        Some(0) => Ok(None),
+        Some(minutes) => u64::try_from(minutes)
+            .map(|minutes| Some(Duration::from_secs(minutes.saturating_mul(60))))
+            .map_err(|_| {
+                SttError::model_path(
+                    "config [model].idle_unload_minutes must be 0 (never) or a positive number of minutes",
+                )
+            }),
+        None => Err(SttError::model_path(
+            "config [model].idle_unload_minutes must be an integer",
+        )),
+    }
+}
+
+fn audio_source_from_table(
+    table: Option<&toml::Table>,
+    table_name: &str,
+    default: AudioSource,
+) -> SttResult<AudioSource> {
+    let Some(value) = table.and_then(|table| table.get("source")) else {
+        return Ok(default);
+    };
+
+    value
+        .as_str()
+        .and_then(AudioSource::from_config_name)
+        .ok_or_else(|| {
+            SttError::model_path(format!(
+                "config [{table_name}].source must be one of: microphone, monitor, both"
+            ))
+        })
+}
+
+/// Falls back to the older `auto_mute_speakers` switch when `speaker_mode` is not set.
+fn speaker_mode_from_table(table: Option<&toml::Table>) -> SttResult<SpeakerMode> {
+    let Some(value) = table.and_then(|table| table.get("speaker_mode")) else {
+        let auto_mute = table
+            .and_then(|table| table.get("auto_mute_speakers"))
+            .and_then(toml::Value::as_bool)
+            .unwrap_or(false);
+        return Ok(if auto_mute {
+            SpeakerMode::Mute
+        } else {
+            SpeakerMode::Off
+        });
+    };
+
+    value
+        .as_str()
+        .and_then(SpeakerMode::from_config_name)
+        .ok_or_else(|| {
+            SttError::model_path("config [audio].speaker_mode must be one of: off, mute, duck")
+        })
+}
+
+fn duck_volume_percent_from_table(table: Option<&toml::Table>) -> SttResult<u8> {
+    let Some(value) = table.and_then(|table| table.get("duck_volume_percent")) else {
+        return Ok(DEFAULT_DUCK_VOLUME_PERCENT);
+    };
+
+    value
+        .as_integer()
+        .and_then(|percent| u8::try_from(percent).ok())
+        .filter(|percent| *percent <= 100)
+        .ok_or_else(|| {
+            SttError::model_path("config [audio].duck_volume_percent must be between 0 and 100")
+        })
+}
+
+/// Rewrites the retired `[model].use_gpu` switch as `execution_providers`, which wins if both are
+/// set. Returns whether the table changed.
+fn migrate_use_gpu(parsed: &mut toml::Table) -> bool {
+    let Some(model_table) = parsed.get_mut("model").and_then(toml::Value::as_table_mut) else {
+        return false;
+    };
+    let Some(use_gpu) = model_table.remove("use_gpu") else {
+        return false;
+    };
+    if model_table.contains_key("execution_providers") {
+        return true;
+    }
+
+    let execution_providers = if use_gpu.as_bool() == Some(true) {
+        vec![ExecutionProvider::Cuda, ExecutionProvider::Cpu]
+    } else {
+        vec![ExecutionProvider::Cpu]
+    };
+    upsert_model_execution_providers(parsed, &execution_providers);
+    true
+}
+
+fn upsert_model_execution_providers(
+    parsed: &mut toml::Table,
+    execution_providers: &[ExecutionProvider],
+) {
     let model = parsed
         .entry("model".to_string())
         .or_insert_with(|| toml::Value::Table(toml::Table::new()));
@@ -138,11 +468,19 @@ fn upsert_model_use_gpu(parsed: &mut toml::Table, enabled: bool) {
     }
 
     if let Some(model_table) = model.as_table_mut() {
-        model_table.insert("use_gpu".to_string(), toml::Value::Boolean(enabled));
+        model_table.insert(
+            "execution_providers".to_string(),
+            toml::Value::Array(
+                execution_providers
+                    .iter()
+                    .map(|provider| toml::Value::String(provider.config_name().to_string()))
+                    .collect(),
+            ),
+        );
     }
 }
 
-fn upsert_audio_auto_mute(parsed: &mut toml::Table, enabled: bool) {
+fn upsert_audio_speaker_mode(parsed: &mut toml::Table, mode: SpeakerMode) {
     let audio = parsed
         .entry("audio".to_string())
         .or_insert_with(|| toml::Value::Table(toml::Table::new()));
@@ -152,13 +490,28 @@ fn upsert_audio_auto_mute(parsed: &mut toml::Table, enabled: bool) {
     }
 
     if let Some(audio_table) = audio.as_table_mut() {
+        audio_table.remove("auto_mute_speakers");
         audio_table.insert(
-            "auto_mute_speakers".to_string(),
-            toml::Value::Boolean(enabled),
+            "speaker_mode".to_string(),
+            toml::Value::String(mode.config_name().to_string()),
         );
     }
 }
 
+fn upsert_audio_pause_media(parsed: &mut toml::Table, enabled: bool) {
+    let audio = parsed
+        .entry("audio".to_string())
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+
+    if !audio.is_table() {
+        *audio = toml::Value::Table(toml::Table::new());
+    }
+
+    if let Some(audio_table) = audio.as_table_mut() {
+        audio_table.insert("pause_media".to_string(), toml::Value::Boolean(enabled));
+    }
+}
+
 fn app_data_dir() -> SttResult<PathBuf> {
     if let Some(data_home) = env::var_os("XDG_DATA_HOME")
         && !data_home.is_empty()
@@ -207,10 +560,116 @@ fn default_config_text() -> String {
 [model]
 directory = "{DEFAULT_MODEL_DIRECTORY}"
 base_url = "{DEFAULT_MODEL_BASE_URL}"
-use_gpu = false
+# Tried in order: cpu, cuda, tensorrt, openvino, rocm, migraphx, xnnpack. CPU is always the last resort.
+execution_providers = ["cpu"]
+# ONNX Runtime thread pools; 0 lets ONNX Runtime choose.
+intra_op_threads = 0
+inter_op_threads = 0
+# One of: disable, basic, extended, layout, all.
+graph_optimization = "all"
+# Save the optimized model under cache/optimized-models and load it on later starts.
+optimized_model_cache = false
+# Unload the model after this many idle minutes; the next hotkey press reloads it. 0 keeps it loaded.
+idle_unload_minutes = 0
+# Run inference in a separate `auto-scribe --stt-worker` process so native crashes cannot take down the app.
+worker_process = false
+# Accept imported models without a SHA256SUMS manifest; their checksums are recorded but not verified.
+allow_unverified_import = false
 
 [audio]
-auto_mute_speakers = false
+# Quiet the default speaker sink while recording: off, mute, or duck to duck_volume_percent.
+speaker_mode = "off"
+duck_volume_percent = {DEFAULT_DUCK_VOLUME_PERCENT}
+# Pause MPRIS media players that are playing while recording and resume them afterwards.
+pause_media = false
+# Save each transcribed recording as a 16 kHz mono WAV under recordings/ for replay and re-transcription.
+keep_recordings = false
+# Retention for saved recordings; 0 removes the limit.
+max_recordings = 200
+max_recording_age_days = 30
+# Clean-up before inference: an 80 Hz high-pass filter, RNNoise noise suppression, and
+# automatic gain control towards target_level_dbfs.
+high_pass_filter = false
+noise_suppression = false
+automatic_gain_control = false
+target_level_dbfs = -20.0
+# What the hotkey records: microphone, monitor (system audio) or both.
+source = "microphone"
+# PulseAudio/PipeWire source used for system audio; see `pactl list short sources`.
+monitor_source = "{DEFAULT_MONITOR_SOURCE}"
+
+[meeting]
+# What meeting mode records; with both, lines are labelled Microphone or System.
+source = "both"
+
+[diarization]
+# Label speakers as "Speaker 1", "Speaker 2", ... in recordings at least min_recording_seconds long.
+enabled = false
+# Sortformer ONNX model; relative paths are resolved under the app data directory.
+model_file = "models/diar_streaming_sortformer_4spk-v2.onnx"
+min_recording_seconds = 20
+
+[hotkey]
+# Cancels the current recording or transcription without touching the clipboard. Empty disables it.
+cancel = "{DEFAULT_CANCEL_HOTKEY}"
+
+[download]
+# Empty uses HTTPS_PROXY/HTTP_PROXY/ALL_PROXY and NO_PROXY from the environment; "none" disables proxies.
+proxy = ""
+no_proxy = []
+# PEM bundle that replaces the built-in root certificates.
+ca_bundle = ""
+# Base URLs tried in order after [model].base_url fails.
+mirrors = []
+
+# Output profiles are matched against the focused window's app ID or WM_CLASS; the first match
+# decides how a transcript is delivered, and without one it is copied to the clipboard as is.
+# [[output_profile]]
+# name = "Terminal"
+# apps = ["kitty", "foot", "Alacritty", "org.gnome.Terminal"]
+# # clipboard, or type to send keystrokes with wtype (Wayland) or xdotool (X11).
+# output = "clipboard"
+# # Applied in order: sentence_case, no_final_period, shell_safe.
+# stages = ["no_final_period", "shell_safe"]
+# # Put before every line, for example "// " for code comments.
+# line_prefix = ""
+# trailing_newline = false
 "#
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migrated(config: &str) -> SttResult<(bool, Vec<ExecutionProvider>)> {
+        let mut parsed = config
+            .parse::<toml::Table>()
+            .map_err(|error| SttError::model_path(error.to_string()))?;
+        let changed = migrate_use_gpu(&mut parsed);
+        let model_table = parsed.get("model").and_then(toml::Value::as_table);
+        assert!(model_table.is_none_or(|table| !table.contains_key("use_gpu")));
+        Ok((changed, execution_providers_from_table(model_table)?))
+    }
+
+    #[test]
+    fn migrates_the_retired_use_gpu_switch() -> SttResult<()> {
+        use ExecutionProvider::{Cpu, Cuda, OpenVino};
+
+        assert_eq!(
+            migrated("[model]\nuse_gpu = true")?,
+            (true, vec![Cuda, Cpu])
+        );
+        assert_eq!(migrated("[model]\nuse_gpu = false")?, (true, vec![Cpu]));
+        assert_eq!(
+            migrated("[model]\nuse_gpu = true\nexecution_providers = [\"openvino\"]")?,
+            (true, vec![OpenVino])
+        );
+        assert_eq!(
+            migrated("[model]\ndirectory = \"models\"")?,
+            (false, vec![Cpu])
+        );
+        assert_eq!(migrated("")?, (false, vec![Cpu]));
+        Ok(())
+    }
+}
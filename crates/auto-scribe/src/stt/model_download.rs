@@ -1,9 +1,11 @@
 use crate::stt::{
-    ModelConfig, REQUIRED_MODEL_FILES, SttError, SttResult, WorkerEvent, validate_model_dir,
+    DownloadConfig, DownloadProxy, ModelConfig, REQUIRED_MODEL_FILES, SttError, SttResult,
+    WorkerEvent, validate_model_dir, write_model_checksums,
 };
 
 use async_channel::Sender;
 use std::{
+    env,
     fs::{self, File},
     io::{Read, Write},
     path::{Path, PathBuf},
@@ -11,8 +13,9 @@ use std::{
     time::Duration,
 };
 use ureq::{
-    Agent,
-    tls::{TlsConfig, TlsProvider},
+    Agent, Proxy,
+    http::Uri,
+    tls::{PemItem, RootCerts, TlsConfig, TlsProvider, parse_pem},
 };
 
 const DOWNLOAD_BUFFER_SIZE: usize = 1024 * 1024;
@@ -34,7 +37,7 @@ pub(crate) fn spawn_model_download(
 }
 
 fn download_model(config: &ModelConfig, event_tx: &Sender<WorkerEvent>) -> SttResult<()> {
-    let agent = download_agent();
+    let root_certs = load_root_certs(config.download())?;
     let staging_dir = staging_dir(config.model_dir())?;
 
     if staging_dir.exists() {
@@ -48,9 +51,9 @@ fn download_model(config: &ModelConfig, event_tx: &Sender<WorkerEvent>) -> SttRe
 
     let total_files = REQUIRED_MODEL_FILES.len();
     for (file_index, &file_name) in REQUIRED_MODEL_FILES.iter().enumerate() {
-        download_file(
-            &agent,
+        download_file_from_sources(
             config,
+            root_certs.as_ref(),
             file_name,
             &staging_dir,
             file_index,
@@ -60,36 +63,171 @@ fn download_model(config: &ModelConfig, event_tx: &Sender<WorkerEvent>) -> SttRe
     }
 
     validate_model_dir(&staging_dir)?;
+    write_model_checksums(&staging_dir)?;
     install_staged_model(&staging_dir, config.model_dir())?;
     event_tx
         .send_blocking(WorkerEvent::ModelDownloadFinished)
         .map_err(|_| SttError::worker_channel("model download receiver has disconnected"))
 }
 
-fn download_agent() -> Agent {
-    Agent::config_builder()
+fn download_file_from_sources(
+    config: &ModelConfig,
+    root_certs: Option<&RootCerts>,
+    file_name: &str,
+    staging_dir: &Path,
+    completed_files: usize,
+    total_files: usize,
+    event_tx: &Sender<WorkerEvent>,
+) -> SttResult<()> {
+    let urls = config.model_urls(file_name);
+    let total_attempts = urls.len();
+    let mut failures = Vec::new();
+
+    for (attempt_index, url) in urls.iter().enumerate() {
+        let (agent, route) = download_agent(config.download(), root_certs, url)?;
+        event_tx
+            .send_blocking(WorkerEvent::ModelDownloadAttempt {
+                file_name: file_name.to_string(),
+                source: url_host(url),
+                route: route.clone(),
+                attempt: attempt_index + 1,
+                total_attempts,
+                previous_error: failures.last().cloned(),
+            })
+            .map_err(|_| SttError::worker_channel("model download receiver has disconnected"))?;
+
+        match download_file(
+            &agent,
+            url,
+            file_name,
+            staging_dir,
+            completed_files,
+            total_files,
+            event_tx,
+        ) {
+            Ok(()) => return Ok(()),
+            Err(error @ SttError::WorkerChannel { .. }) => return Err(error),
+            Err(error) => failures.push(format!("{} via {route}: {error}", url_host(url))),
+        }
+    }
+
+    Err(SttError::model_path(format!(
+        "every download source failed for {file_name}: {}",
+        failures.join("; ")
+    )))
+}
+
+fn download_agent(
+    settings: &DownloadConfig,
+    root_certs: Option<&RootCerts>,
+    url: &str,
+) -> SttResult<(Agent, String)> {
+    let proxy = download_proxy(settings, url)?;
+    let route = proxy
+        .as_ref()
+        .map(|proxy| format!("proxy {}:{}", proxy.host(), proxy.port()))
+        .unwrap_or_else(|| "direct".to_string());
+    let mut tls_config = TlsConfig::builder().provider(TlsProvider::NativeTls);
+    if let Some(root_certs) = root_certs {
+        tls_config = tls_config.root_certs(root_certs.clone());
+    }
+
+    let agent = Agent::config_builder()
         .timeout_global(Some(Duration::from_secs(60 * 60)))
-        .tls_config(
-            TlsConfig::builder()
-                .provider(TlsProvider::NativeTls)
-                .build(),
-        )
+        .proxy(proxy)
+        .tls_config(tls_config.build())
         .build()
-        .into()
+        .into();
+
+    Ok((agent, route))
+}
+
+fn download_proxy(settings: &DownloadConfig, url: &str) -> SttResult<Option<Proxy>> {
+    let host = url
+        .parse::<Uri>()
+        .ok()
+        .and_then(|uri| uri.host().map(str::to_ascii_lowercase))
+        .unwrap_or_default();
+
+    if host_bypasses_proxy(&host, settings.no_proxy())
+        || host_bypasses_proxy(&host, &env_no_proxy())
+    {
+        return Ok(None);
+    }
+
+    match settings.proxy() {
+        DownloadProxy::Environment => Ok(Proxy::try_from_env()),
+        DownloadProxy::Disabled => Ok(None),
+        DownloadProxy::Explicit(proxy) => Proxy::new(proxy).map(Some).map_err(|error| {
+            SttError::model_path(format!("config [download].proxy {proxy}: {error}"))
+        }),
+    }
+}
+
+fn env_no_proxy() -> Vec<String> {
+    ["NO_PROXY", "no_proxy"]
+        .into_iter()
+        .find_map(|name| env::var(name).ok())
+        .map(|value| value.split(',').map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+fn host_bypasses_proxy(host: &str, patterns: &[String]) -> bool {
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.trim().to_ascii_lowercase();
+        if pattern == "*" {
+            return true;
+        }
+
+        let suffix = pattern.trim_start_matches('*').trim_start_matches('.');
+        !suffix.is_empty() && (host == suffix || host.ends_with(&format!(".{suffix}")))
+    })
+}
+
+fn load_root_certs(settings: &DownloadConfig) -> SttResult<Option<RootCerts>> {
+    let Some(ca_bundle) = settings.ca_bundle() else {
+        return Ok(None);
+    };
+
+    let pem = fs::read(ca_bundle)
+        .map_err(|error| SttError::model_path(format!("read {}: {error}", ca_bundle.display())))?;
+    let certificates = parse_pem(&pem)
+        .filter_map(|item| match item {
+            Ok(PemItem::Certificate(certificate)) => Some(Ok(certificate)),
+            Ok(_) => None,
+            Err(error) => Some(Err(error)),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| SttError::model_path(format!("parse {}: {error}", ca_bundle.display())))?;
+
+    if certificates.is_empty() {
+        return Err(SttError::model_path(format!(
+            "{} contains no PEM certificates",
+            ca_bundle.display()
+        )));
+    }
+
+    Ok(Some(RootCerts::new_with_certs(&certificates)))
+}
+
+fn url_host(url: &str) -> String {
+    url.parse::<Uri>()
+        .ok()
+        .and_then(|uri| uri.host().map(str::to_string))
+        .unwrap_or_else(|| url.to_string())
 }
 
 fn download_file(
     agent: &Agent,
-    config: &ModelConfig,
+    url: &str,
     file_name: &str,
     staging_dir: &Path,
     completed_files: usize,
     total_files: usize,
     event_tx: &Sender<WorkerEvent>,
 ) -> SttResult<()> {
-    let url = config.model_url(file_name);
     let mut response = agent
-        .get(&url)
+        .get(url)
         .call()
         .map_err(|error| SttError::model_path(format!("download {url}: {error}")))?;
     let file_total_bytes = content_length(response.headers());
@@ -168,7 +306,7 @@ fn send_progress(
         .map_err(|_| SttError::worker_channel("model download receiver has disconnected"))
 }
 
-fn install_staged_model(staging_dir: &Path, model_dir: &Path) -> SttResult<()> {
+pub(crate) fn install_staged_model(staging_dir: &Path, model_dir: &Path) -> SttResult<()> {
     if let Some(parent) = model_dir.parent() {
         fs::create_dir_all(parent).map_err(|error| {
             SttError::model_path(format!("create {}: {error}", parent.display()))
@@ -190,7 +328,7 @@ fn install_staged_model(staging_dir: &Path, model_dir: &Path) -> SttResult<()> {
     })
 }
 
-fn staging_dir(model_dir: &Path) -> SttResult<PathBuf> {
+pub(crate) fn staging_dir(model_dir: &Path) -> SttResult<PathBuf> {
     let Some(parent) = model_dir.parent() else {
         return Err(SttError::model_path(format!(
             "model directory has no parent: {}",
@@ -0,0 +1,215 @@
+use crate::stt::{
+    MODEL_CHECKSUM_FILE, ModelChecksumStatus, ModelConfig, REQUIRED_MODEL_FILES, SttError,
+    SttResult, WorkerEvent, install_staged_model, staging_dir, validate_model_dir,
+    verify_or_record_model_checksums,
+};
+
+use async_channel::Sender;
+use flate2::read::GzDecoder;
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+    thread,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ImportSourceKind {
+    Directory,
+    TarGz,
+    Zip,
+}
+
+pub(crate) fn spawn_model_import(
+    config: ModelConfig,
+    source: PathBuf,
+    event_tx: Sender<WorkerEvent>,
+) -> SttResult<()> {
+    thread::Builder::new()
+        .name("auto-scribe-model-import".to_string())
+        .spawn(move || {
+            let allow_unverified = config.allow_unverified_import();
+            let event = match import_model(&config, &source, allow_unverified) {
+                Ok(checksum_status) => WorkerEvent::ModelImportFinished(checksum_status),
+                Err(error) => WorkerEvent::ModelImportError(error.to_string()),
+            };
+            let _ = event_tx.send_blocking(event);
+        })
+        .map(|_| ())
+        .map_err(|error| SttError::model_path(format!("spawn model import thread: {error}")))
+}
+
+pub(crate) fn import_model(
+    config: &ModelConfig,
+    source: &Path,
+    allow_unverified: bool,
+) -> SttResult<ModelChecksumStatus> {
+    let source_kind = import_source_kind(source)?;
+    let staging_dir = staging_dir(config.model_dir())?;
+    let extract_dir = import_extract_dir(config.model_dir())?;
+
+    recreate_dir(&staging_dir)?;
+    let result = stage_model_files(source, source_kind, &staging_dir, &extract_dir)
+        .and_then(|()| validate_model_dir(&staging_dir))
+        .and_then(|()| verify_or_record_model_checksums(&staging_dir, allow_unverified))
+        .and_then(|checksum_status| {
+            install_staged_model(&staging_dir, config.model_dir()).map(|()| checksum_status)
+        });
+
+    let _ = remove_dir_if_exists(&extract_dir);
+    if result.is_err() {
+        let _ = remove_dir_if_exists(&staging_dir);
+    }
+
+    result
+}
+
+fn import_source_kind(source: &Path) -> SttResult<ImportSourceKind> {
+    if source.is_dir() {
+        return Ok(ImportSourceKind::Directory);
+    }
+
+    if !source.is_file() {
+        return Err(SttError::model_path(format!(
+            "model import source does not exist: {}",
+            source.display()
+        )));
+    }
+
+    let file_name = source
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    if file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz") {
+        return Ok(ImportSourceKind::TarGz);
+    }
+
+    if file_name.ends_with(".zip") {
+        return Ok(ImportSourceKind::Zip);
+    }
+
+    Err(SttError::model_path(format!(
+        "unsupported model import source {}; expected a directory, .tar.gz or .zip",
+        source.display()
+    )))
+}
+
+fn stage_model_files(
+    source: &Path,
+    source_kind: ImportSourceKind,
+    staging_dir: &Path,
+    extract_dir: &Path,
+) -> SttResult<()> {
+    match source_kind {
+        ImportSourceKind::Directory => {
+            let model_root = find_model_root(source)?;
+            transfer_model_files(&model_root, staging_dir, false)
+        }
+        ImportSourceKind::TarGz => {
+            recreate_dir(extract_dir)?;
+            unpack_tar_gz(source, extract_dir)?;
+            let model_root = find_model_root(extract_dir)?;
+            transfer_model_files(&model_root, staging_dir, true)
+        }
+        ImportSourceKind::Zip => {
+            recreate_dir(extract_dir)?;
+            unpack_zip(source, extract_dir)?;
+            let model_root = find_model_root(extract_dir)?;
+            transfer_model_files(&model_root, staging_dir, true)
+        }
+    }
+}
+
+fn unpack_tar_gz(archive_path: &Path, extract_dir: &Path) -> SttResult<()> {
+    let file = File::open(archive_path).map_err(|error| {
+        SttError::model_path(format!("open {}: {error}", archive_path.display()))
+    })?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    archive.set_preserve_permissions(false);
+
+    archive.unpack(extract_dir).map_err(|error| {
+        SttError::model_path(format!("extract {}: {error}", archive_path.display()))
+    })
+}
+
+fn unpack_zip(archive_path: &Path, extract_dir: &Path) -> SttResult<()> {
+    let file = File::open(archive_path).map_err(|error| {
+        SttError::model_path(format!("open {}: {error}", archive_path.display()))
+    })?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|error| {
+        SttError::model_path(format!("read {}: {error}", archive_path.display()))
+    })?;
+
+    archive.extract(extract_dir).map_err(|error| {
+        SttError::model_path(format!("extract {}: {error}", archive_path.display()))
+    })
+}
+
+fn find_model_root(root: &Path) -> SttResult<PathBuf> {
+    if validate_model_dir(root).is_ok() {
+        return Ok(root.to_path_buf());
+    }
+
+    let entries = fs::read_dir(root)
+        .map_err(|error| SttError::model_path(format!("read {}: {error}", root.display())))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() && validate_model_dir(&path).is_ok() {
+            return Ok(path);
+        }
+    }
+
+    Err(SttError::model_path(format!(
+        "{} does not contain the required model files: {}",
+        root.display(),
+        REQUIRED_MODEL_FILES.join(", ")
+    )))
+}
+
+fn transfer_model_files(model_root: &Path, staging_dir: &Path, move_files: bool) -> SttResult<()> {
+    let checksum_file = model_root
+        .join(MODEL_CHECKSUM_FILE)
+        .is_file()
+        .then_some(MODEL_CHECKSUM_FILE);
+
+    for file_name in REQUIRED_MODEL_FILES.into_iter().chain(checksum_file) {
+        let source_path = model_root.join(file_name);
+        let target_path = staging_dir.join(file_name);
+
+        if move_files && fs::rename(&source_path, &target_path).is_ok() {
+            continue;
+        }
+
+        fs::copy(&source_path, &target_path).map_err(|error| {
+            SttError::model_path(format!(
+                "copy {} to {}: {error}",
+                source_path.display(),
+                target_path.display()
+            ))
+        })?;
+    }
+
+    Ok(())
+}
+
+fn recreate_dir(dir: &Path) -> SttResult<()> {
+    remove_dir_if_exists(dir)?;
+    fs::create_dir_all(dir)
+        .map_err(|error| SttError::model_path(format!("create {}: {error}", dir.display())))
+}
+
+fn remove_dir_if_exists(dir: &Path) -> SttResult<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+
+    fs::remove_dir_all(dir)
+        .map_err(|error| SttError::model_path(format!("remove {}: {error}", dir.display())))
+}
+
+pub(crate) fn import_extract_dir(model_dir: &Path) -> SttResult<PathBuf> {
+    let staging_dir = staging_dir(model_dir)?;
+    Ok(staging_dir.with_extension("import"))
+}
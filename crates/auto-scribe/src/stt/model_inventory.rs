@@ -0,0 +1,187 @@
+use crate::stt::{SttError, SttResult, validate_model_dir};
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+const LAST_USED_FILE: &str = ".last-used";
+
+#[derive(Clone, Debug)]
+pub(crate) struct InstalledModel {
+    pub(crate) name: String,
+    pub(crate) path: PathBuf,
+    pub(crate) size_bytes: u64,
+    pub(crate) validation_error: Option<String>,
+    pub(crate) last_used: Option<SystemTime>,
+    pub(crate) active: bool,
+}
+
+pub(crate) fn list_installed_models(
+    models_root: &Path,
+    active_model_dir: &Path,
+) -> SttResult<Vec<InstalledModel>> {
+    let mut model_dirs = Vec::new();
+
+    if models_root.is_dir() {
+        let entries = fs::read_dir(models_root).map_err(|error| {
+            SttError::model_path(format!("read {}: {error}", models_root.display()))
+        })?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() && !is_hidden(&path) {
+                model_dirs.push(path);
+            }
+        }
+    }
+
+    if active_model_dir.is_dir() && !model_dirs.iter().any(|path| path == active_model_dir) {
+        model_dirs.push(active_model_dir.to_path_buf());
+    }
+
+    model_dirs.sort();
+
+    Ok(model_dirs
+        .into_iter()
+        .map(|path| InstalledModel {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string()),
+            size_bytes: dir_size(&path),
+            validation_error: validate_model_dir(&path)
+                .err()
+                .map(|error| error.to_string()),
+            last_used: fs::metadata(path.join(LAST_USED_FILE))
+                .and_then(|metadata| metadata.modified())
+                .ok(),
+            active: path == active_model_dir,
+            path,
+        })
+        .collect())
+}
+
+pub(crate) fn record_model_last_used(model_dir: &Path) -> SttResult<()> {
+    let marker_path = model_dir.join(LAST_USED_FILE);
+    fs::write(&marker_path, b"")
+        .map_err(|error| SttError::model_path(format!("write {}: {error}", marker_path.display())))
+}
+
+/// Removes the given staging directories if an interrupted download or import left them
+/// behind. Only exact paths are taken, so folders the app did not create are never touched.
+pub(crate) fn remove_orphaned_staging_dirs(staging_dirs: &[PathBuf]) -> SttResult<Vec<PathBuf>> {
+    let mut removed = Vec::new();
+
+    for staging_dir in staging_dirs {
+        if !staging_dir.is_dir() || removed.contains(staging_dir) {
+            continue;
+        }
+
+        fs::remove_dir_all(staging_dir).map_err(|error| {
+            SttError::model_path(format!("remove {}: {error}", staging_dir.display()))
+        })?;
+        removed.push(staging_dir.clone());
+    }
+
+    Ok(removed)
+}
+
+/// Deletes a model directory that sits directly in `models_root`. A configured `model_dir`
+/// elsewhere, or a link out of the models folder, belongs to the user and is refused.
+pub(crate) fn remove_installed_model(models_root: &Path, model_dir: &Path) -> SttResult<()> {
+    let canonicalize = |path: &Path| {
+        path.canonicalize()
+            .map_err(|error| SttError::model_path(format!("resolve {}: {error}", path.display())))
+    };
+    let models_root = canonicalize(models_root)?;
+    if canonicalize(model_dir)?.parent() != Some(models_root.as_path()) {
+        return Err(SttError::model_path(format!(
+            "{} is not inside {}; delete it yourself if it is no longer needed",
+            model_dir.display(),
+            models_root.display()
+        )));
+    }
+
+    fs::remove_dir_all(model_dir)
+        .map_err(|error| SttError::model_path(format!("remove {}: {error}", model_dir.display())))
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.'))
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = fs::symlink_metadata(entry.path()).ok()?;
+            if metadata.is_dir() {
+                Some(dir_size(&entry.path()))
+            } else {
+                Some(metadata.len())
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stt::{import_extract_dir, staging_dir};
+
+    #[test]
+    fn removes_only_the_staging_dirs_of_the_given_model() -> SttResult<()> {
+        let parent =
+            std::env::temp_dir().join(format!("auto-scribe-staging-{}", std::process::id()));
+        let model_dir = parent.join("nemotron");
+        let staging = staging_dir(&model_dir)?;
+        let extract = import_extract_dir(&model_dir)?;
+        let unrelated = parent.join(".backup.download");
+        for dir in [&staging, &extract, &unrelated] {
+            fs::create_dir_all(dir).map_err(|error| SttError::model_path(error.to_string()))?;
+        }
+
+        let removed = remove_orphaned_staging_dirs(&[staging.clone(), extract.clone()])?;
+
+        assert_eq!(removed, vec![staging.clone(), extract.clone()]);
+        assert!(!staging.exists() && !extract.exists());
+        assert!(unrelated.is_dir());
+        fs::remove_dir_all(&parent).map_err(|error| SttError::model_path(error.to_string()))
+    }
+
+    #[test]
+    fn deletes_only_model_dirs_inside_the_models_root() -> SttResult<()> {
+        let parent =
+            std::env::temp_dir().join(format!("auto-scribe-delete-{}", std::process::id()));
+        let models_root = parent.join("models");
+        let model_dir = models_root.join("nemotron");
+        let outside = parent.join("my-models").join("nemotron");
+        let linked = models_root.join("linked");
+        for dir in [&model_dir, &outside] {
+            fs::create_dir_all(dir).map_err(|error| SttError::model_path(error.to_string()))?;
+        }
+        std::os::unix::fs::symlink(&outside, &linked)
+            .map_err(|error| SttError::model_path(error.to_string()))?;
+
+        assert!(remove_installed_model(&models_root, &outside).is_err());
+        assert!(remove_installed_model(&models_root, &linked).is_err());
+        assert!(remove_installed_model(&models_root, &models_root).is_err());
+        assert!(
+            remove_installed_model(&models_root, &models_root.join("..").join("my-models"))
+                .is_err()
+        );
+        assert!(outside.is_dir() && models_root.is_dir());
+
+        remove_installed_model(&models_root, &model_dir)?;
+        assert!(!model_dir.exists());
+        fs::remove_dir_all(&parent).map_err(|error| SttError::model_path(error.to_string()))
+    }
+}
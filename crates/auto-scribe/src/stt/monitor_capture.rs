@@ -0,0 +1,129 @@
+use crate::stt::{RecorderState, SttError, SttResult, TARGET_SAMPLE_RATE, record_input_data};
+
+use std::{
+    io::{ErrorKind, Read},
+    process::{Child, ChildStdout, Command, Stdio},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+const PAREC: &str = "parec";
+const READ_BUFFER_BYTES: usize = 4096;
+
+/// Records a PulseAudio/PipeWire monitor source with `parec`, which works on both servers
+/// through pipewire-pulse. The process only runs while a recording is active.
+pub(crate) struct MonitorCapture {
+    source_name: String,
+    state: Arc<Mutex<RecorderState>>,
+    child: Mutex<Option<Child>>,
+}
+
+impl MonitorCapture {
+    pub(crate) fn new(source_name: &str, state: Arc<Mutex<RecorderState>>) -> SttResult<Self> {
+        match Command::new(PAREC)
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+        {
+            Ok(_) => {}
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                return Err(SttError::audio_device(
+                    "parec was not found; install pulseaudio-utils to capture system audio",
+                ));
+            }
+            Err(error) => return Err(SttError::audio_device(format!("{PAREC}: {error}"))),
+        }
+
+        Ok(Self {
+            source_name: source_name.to_string(),
+            state,
+            child: Mutex::new(None),
+        })
+    }
+
+    pub(crate) fn start(&self) -> SttResult<()> {
+        self.stop();
+
+        let mut child = Command::new(PAREC)
+            .arg(format!("--device={}", self.source_name))
+            .arg("--format=float32le")
+            .arg(format!("--rate={TARGET_SAMPLE_RATE}"))
+            .arg("--channels=1")
+            .arg("--latency-msec=100")
+            .arg("--raw")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|error| SttError::audio_device(format!("start {PAREC}: {error}")))?;
+        let Some(stdout) = child.stdout.take() else {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(SttError::audio_device(format!(
+                "{PAREC} has no stdout pipe"
+            )));
+        };
+
+        let state = Arc::clone(&self.state);
+        let source_name = self.source_name.clone();
+        thread::Builder::new()
+            .name("auto-scribe-monitor-capture".to_string())
+            .spawn(move || read_samples(stdout, &source_name, &state))
+            .map_err(|error| SttError::audio_device(error.to_string()))?;
+
+        if let Ok(mut slot) = self.child.lock() {
+            *slot = Some(child);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn stop(&self) {
+        let Ok(mut slot) = self.child.lock() else {
+            return;
+        };
+        if let Some(mut child) = slot.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+impl Drop for MonitorCapture {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn read_samples(mut stdout: ChildStdout, source_name: &str, state: &Arc<Mutex<RecorderState>>) {
+    let mut buffer = [0; READ_BUFFER_BYTES];
+    let mut pending = Vec::with_capacity(READ_BUFFER_BYTES + 4);
+    let mut samples = Vec::with_capacity(READ_BUFFER_BYTES / 4);
+
+    loop {
+        let read = match stdout.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(_) => break,
+        };
+
+        pending.extend_from_slice(&buffer[..read]);
+        let whole_samples = pending.len() / 4 * 4;
+        samples.clear();
+        samples.extend(
+            pending[..whole_samples]
+                .chunks_exact(4)
+                .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+        );
+        pending.drain(..whole_samples);
+        record_input_data(&samples, 1, state);
+    }
+
+    // A stop kills parec after clearing `recording`; anything else is a lost source.
+    if let Ok(mut state) = state.lock()
+        && state.recording
+    {
+        state.last_stream_error = Some(format!("{PAREC} stopped recording {source_name}"));
+    }
+}
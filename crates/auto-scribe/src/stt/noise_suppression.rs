@@ -0,0 +1,40 @@
+use crate::stt::{SttResult, resample};
+
+use nnnoiseless::DenoiseState;
+
+/// RNNoise only runs on 48 kHz audio.
+const RNNOISE_SAMPLE_RATE: u32 = 48_000;
+const FRAME_SIZE: usize = DenoiseState::FRAME_SIZE;
+
+/// Runs RNNoise over the recording and returns it at the original sample rate.
+pub(crate) fn suppress_noise(samples: Vec<f32>, sample_rate: u32) -> SttResult<Vec<f32>> {
+    if samples.is_empty() {
+        return Ok(samples);
+    }
+
+    let mut input = resample(samples, sample_rate, RNNOISE_SAMPLE_RATE)?;
+    let input_len = input.len();
+    // RNNoise delays its output by one frame; the padding flushes the last real frame.
+    input.extend([0.0; FRAME_SIZE]);
+
+    let mut state = DenoiseState::new();
+    let mut frame_in = [0.0; FRAME_SIZE];
+    let mut frame_out = [0.0; FRAME_SIZE];
+    let mut output = Vec::with_capacity(input.len());
+    for chunk in input.chunks(FRAME_SIZE) {
+        frame_in.fill(0.0);
+        for (scaled, sample) in frame_in.iter_mut().zip(chunk) {
+            *scaled = sample * f32::from(i16::MAX);
+        }
+        state.process_frame(&mut frame_out, &frame_in);
+        output.extend(
+            frame_out[..chunk.len()]
+                .iter()
+                .map(|sample| sample / f32::from(i16::MAX)),
+        );
+    }
+    output.drain(..FRAME_SIZE.min(output.len()));
+    output.truncate(input_len);
+
+    resample(output, RNNOISE_SAMPLE_RATE, sample_rate)
+}
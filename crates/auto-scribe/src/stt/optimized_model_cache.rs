@@ -0,0 +1,177 @@
+use crate::stt::{
+    ExecutionProvider, GraphOptimization, REQUIRED_MODEL_FILES, SttError, SttResult,
+    validate_model_dir,
+};
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::UNIX_EPOCH,
+};
+
+const SOURCE_STAMP_FILE: &str = ".source";
+const STAGING_SUFFIX: &str = ".staging";
+/// parakeet-rs builds one session per ONNX file, encoder first. Its configure hook is not told
+/// which file a session is for, so sessions are matched to these names by the order they start.
+const SESSION_MODEL_FILES: [&str; 2] = ["encoder.onnx", "decoder_joint.onnx"];
+const COPIED_MODEL_FILES: [&str; 1] = ["tokenizer.model"];
+
+#[derive(Clone, Debug)]
+pub(crate) struct OptimizedModelCache {
+    dir: PathBuf,
+    staging_dir: PathBuf,
+    source_stamp: String,
+    /// Sessions configured so far; shared by the clones handed to the session configure hook.
+    sessions_started: Arc<AtomicUsize>,
+}
+
+impl OptimizedModelCache {
+    pub(crate) fn new(
+        cache_root: &Path,
+        model_dir: &Path,
+        provider: ExecutionProvider,
+        graph_optimization: GraphOptimization,
+    ) -> Self {
+        let model_name = model_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "model".to_string());
+        let variant = format!(
+            "{}-{}",
+            provider.config_name(),
+            graph_optimization.config_name()
+        );
+        let dir = cache_root.join(model_name).join(&variant);
+        let staging_dir = dir.with_file_name(format!("{variant}{STAGING_SUFFIX}"));
+
+        Self {
+            dir,
+            staging_dir,
+            source_stamp: source_stamp(model_dir),
+            sessions_started: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub(crate) fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub(crate) fn is_current(&self) -> bool {
+        validate_model_dir(&self.dir).is_ok()
+            && fs::read_to_string(self.dir.join(SOURCE_STAMP_FILE))
+                .is_ok_and(|stamp| stamp == self.source_stamp)
+    }
+
+    pub(crate) fn prepare_staging(&self) -> SttResult<()> {
+        if self.staging_dir.exists() {
+            fs::remove_dir_all(&self.staging_dir).map_err(|error| {
+                SttError::model_path(format!("remove {}: {error}", self.staging_dir.display()))
+            })?;
+        }
+
+        fs::create_dir_all(&self.staging_dir).map_err(|error| {
+            SttError::model_path(format!("create {}: {error}", self.staging_dir.display()))
+        })
+    }
+
+    /// Where the next session should save its optimized graph. `None` once more sessions start
+    /// than there are model files, and `commit` then refuses to write the cache.
+    pub(crate) fn next_staged_session_path(&self) -> Option<PathBuf> {
+        let session_index = self.sessions_started.fetch_add(1, Ordering::Relaxed);
+        SESSION_MODEL_FILES
+            .get(session_index)
+            .map(|file_name| self.staging_dir.join(file_name))
+    }
+
+    pub(crate) fn commit(&self, model_dir: &Path) -> SttResult<()> {
+        let sessions_started = self.sessions_started.load(Ordering::Relaxed);
+        if sessions_started != SESSION_MODEL_FILES.len() {
+            return Err(SttError::model_path(format!(
+                "{sessions_started} ONNX sessions were built, expected {}, so the optimized files \
+                 cannot be matched to model files",
+                SESSION_MODEL_FILES.len()
+            )));
+        }
+
+        for file_name in COPIED_MODEL_FILES {
+            let target_path = self.staging_dir.join(file_name);
+            fs::copy(model_dir.join(file_name), &target_path).map_err(|error| {
+                SttError::model_path(format!("write {}: {error}", target_path.display()))
+            })?;
+        }
+
+        let stamp_path = self.staging_dir.join(SOURCE_STAMP_FILE);
+        fs::write(&stamp_path, &self.source_stamp).map_err(|error| {
+            SttError::model_path(format!("write {}: {error}", stamp_path.display()))
+        })?;
+        validate_model_dir(&self.staging_dir)?;
+
+        self.remove();
+        fs::rename(&self.staging_dir, &self.dir).map_err(|error| {
+            SttError::model_path(format!(
+                "move {} to {}: {error}",
+                self.staging_dir.display(),
+                self.dir.display()
+            ))
+        })
+    }
+
+    pub(crate) fn discard_staging(&self) {
+        let _ = fs::remove_dir_all(&self.staging_dir);
+    }
+
+    pub(crate) fn remove(&self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn source_stamp(model_dir: &Path) -> String {
+    let mut lines = vec![model_dir.display().to_string()];
+    for file_name in REQUIRED_MODEL_FILES {
+        let metadata = fs::metadata(model_dir.join(file_name)).ok();
+        let size_bytes = metadata.as_ref().map(fs::Metadata::len).unwrap_or_default();
+        let modified_secs = metadata
+            .and_then(|metadata| metadata.modified().ok())
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|modified| modified.as_secs())
+            .unwrap_or_default();
+        lines.push(format!("{file_name} {size_bytes} {modified_secs}"));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refuses_to_commit_unless_each_model_file_had_one_session() {
+        let root =
+            std::env::temp_dir().join(format!("auto-scribe-optimized-{}", std::process::id()));
+        let model_dir = root.join("nemotron");
+        let cache = OptimizedModelCache::new(
+            &root.join("cache"),
+            &model_dir,
+            ExecutionProvider::Cpu,
+            GraphOptimization::All,
+        );
+
+        let first = cache.next_staged_session_path();
+        assert!(first.is_some_and(|path| path.ends_with("encoder.onnx")));
+        assert!(cache.commit(&model_dir).is_err());
+
+        let staged_paths = cache.clone();
+        assert!(staged_paths.next_staged_session_path().is_some());
+        assert!(staged_paths.next_staged_session_path().is_none());
+        let error = cache
+            .commit(&model_dir)
+            .err()
+            .map(|error| error.to_string());
+        assert!(error.is_some_and(|error| error.contains("3 ONNX sessions")));
+        assert!(!cache.dir().exists());
+    }
+}
@@ -0,0 +1,82 @@
+use crate::stt::{SinkVolume, SpeakerBackend, SttError, SttResult, command_output};
+
+/// PulseAudio's `pactl`, which also works against `pipewire-pulse`, addressing sinks by name.
+#[derive(Debug, Default)]
+pub(crate) struct PactlSpeakerBackend;
+
+impl SpeakerBackend for PactlSpeakerBackend {
+    fn name(&self) -> &'static str {
+        "pactl"
+    }
+
+    fn default_sink(&mut self) -> SttResult<String> {
+        let sink = command_output("pactl", &["get-default-sink"])?;
+        if sink.is_empty() {
+            return Err(SttError::speaker_mute("pactl reports no default sink"));
+        }
+        Ok(sink)
+    }
+
+    fn is_muted(&mut self, sink: &str) -> SttResult<bool> {
+        // "Mute: yes"; `command_output` runs with LC_ALL=C so the words are not translated.
+        let output = command_output("pactl", &["get-sink-mute", sink])?;
+        let value = output
+            .split_once(':')
+            .map(|(_, value)| value.trim())
+            .unwrap_or(output.trim());
+        match value.to_ascii_lowercase().as_str() {
+            "yes" => Ok(true),
+            "no" => Ok(false),
+            _ => Err(SttError::speaker_mute(format!(
+                "unexpected pactl get-sink-mute output {output:?}"
+            ))),
+        }
+    }
+
+    fn set_muted(&mut self, sink: &str, muted: bool) -> SttResult<()> {
+        command_output(
+            "pactl",
+            &["set-sink-mute", sink, if muted { "1" } else { "0" }],
+        )
+        .map(|_| ())
+    }
+
+    /// Reads the raw per-channel values from a line such as
+    /// "Volume: front-left: 26214 /  40% / -23.88 dB,   front-right: 26214 /  40% / -23.88 dB".
+    fn volume(&mut self, sink: &str) -> SttResult<SinkVolume> {
+        let output = command_output("pactl", &["get-sink-volume", sink])?;
+        let channels = output
+            .lines()
+            .next()
+            .and_then(|line| line.strip_prefix("Volume:"))
+            .and_then(|line| {
+                line.split(',')
+                    .map(|channel| {
+                        channel
+                            .rsplit_once(':')
+                            .and_then(|(_, value)| value.split('/').next())
+                            .and_then(|raw| raw.trim().parse::<u32>().ok())
+                    })
+                    .collect::<Option<Vec<_>>>()
+            })
+            .filter(|channels| !channels.is_empty())
+            .ok_or_else(|| {
+                SttError::speaker_mute(format!(
+                    "unexpected pactl get-sink-volume output {output:?}"
+                ))
+            })?;
+        Ok(SinkVolume::new(channels))
+    }
+
+    /// Plain integers are taken as raw volumes, one per channel.
+    fn set_volume(&mut self, sink: &str, volume: &SinkVolume) -> SttResult<()> {
+        let raw_volumes = volume
+            .channels()
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>();
+        let mut args = vec!["set-sink-volume", sink];
+        args.extend(raw_volumes.iter().map(String::as_str));
+        command_output("pactl", &args).map(|_| ())
+    }
+}
@@ -0,0 +1,41 @@
+const MISSING_LIBRARY_MARKER: &str = "cannot open shared object file";
+const LIBRARY_SUFFIXES: [&str; 2] = [".so", ".dll"];
+
+pub(crate) fn describe_provider_failure(error_message: &str) -> String {
+    match missing_library(error_message) {
+        Some(library) => format!("missing {library}"),
+        None => error_message.trim().to_string(),
+    }
+}
+
+fn missing_library(error_message: &str) -> Option<&str> {
+    let segments = error_message.split(": ").collect::<Vec<_>>();
+
+    let reported_missing = segments.windows(2).find_map(|pair| {
+        pair[1]
+            .trim_start()
+            .starts_with(MISSING_LIBRARY_MARKER)
+            .then(|| library_name(pair[0]))
+            .flatten()
+    });
+
+    reported_missing.or_else(|| {
+        error_message
+            .split(|c: char| c.is_whitespace() || c == ':' || c == '\'' || c == '"')
+            .find(|token| is_library_name(token))
+    })
+}
+
+fn library_name(segment: &str) -> Option<&str> {
+    segment
+        .split_whitespace()
+        .last()
+        .map(|token| token.trim_matches(|c| c == '\'' || c == '"'))
+        .filter(|token| is_library_name(token))
+}
+
+fn is_library_name(token: &str) -> bool {
+    LIBRARY_SUFFIXES
+        .iter()
+        .any(|suffix| token.ends_with(suffix) || token.contains(&format!("{suffix}.")))
+}
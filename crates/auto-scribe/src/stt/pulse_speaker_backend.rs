@@ -0,0 +1,87 @@
+use crate::stt::{SinkVolume, SpeakerBackend, SttError, SttResult};
+
+use libpulse_binding::volume::Volume;
+use pulsectl::controllers::{DeviceControl, SinkController, types::DeviceInfo};
+
+/// Talks the PulseAudio protocol through libpulse, which PipeWire serves through
+/// `pipewire-pulse`. No external tools or output parsing are involved.
+pub(crate) struct PulseSpeakerBackend {
+    controller: SinkController,
+}
+
+impl PulseSpeakerBackend {
+    pub(crate) fn connect() -> SttResult<Self> {
+        let controller = SinkController::create().map_err(|error| {
+            SttError::speaker_mute(format!("connect to the PulseAudio server: {error}"))
+        })?;
+        Ok(Self { controller })
+    }
+
+    fn sink(&mut self, sink: &str) -> SttResult<DeviceInfo> {
+        self.controller
+            .get_device_by_name(sink)
+            .map_err(|error| SttError::speaker_mute(format!("look up sink {sink}: {error}")))
+    }
+}
+
+impl SpeakerBackend for PulseSpeakerBackend {
+    fn name(&self) -> &'static str {
+        "PulseAudio"
+    }
+
+    fn default_sink(&mut self) -> SttResult<String> {
+        self.controller
+            .get_default_device()
+            .map_err(|error| SttError::speaker_mute(format!("look up the default sink: {error}")))?
+            .name
+            .ok_or_else(|| SttError::speaker_mute("the default sink has no name"))
+    }
+
+    fn is_muted(&mut self, sink: &str) -> SttResult<bool> {
+        Ok(self.sink(sink)?.mute)
+    }
+
+    fn set_muted(&mut self, sink: &str, muted: bool) -> SttResult<()> {
+        self.controller.set_device_mute_by_name(sink, muted);
+
+        // The controller does not report failures, so the result is read back.
+        if self.sink(sink)?.mute != muted {
+            return Err(SttError::speaker_mute(format!(
+                "sink {sink} did not change its mute state"
+            )));
+        }
+        Ok(())
+    }
+
+    fn volume(&mut self, sink: &str) -> SttResult<SinkVolume> {
+        let channels = self.sink(sink)?.volume;
+        Ok(SinkVolume::new(
+            channels.get().iter().map(|volume| volume.0).collect(),
+        ))
+    }
+
+    fn set_volume(&mut self, sink: &str, volume: &SinkVolume) -> SttResult<()> {
+        let mut channels = self.sink(sink)?.volume;
+        if channels.get().len() != volume.channels().len() {
+            return Err(SttError::speaker_mute(format!(
+                "sink {sink} has {} channels, not {}",
+                channels.get().len(),
+                volume.channels().len()
+            )));
+        }
+        for (channel, &level) in channels.get_mut().iter_mut().zip(volume.channels()) {
+            *channel = Volume(level);
+        }
+        self.controller.set_device_volume_by_name(sink, &channels);
+
+        if !self
+            .volume(sink)?
+            .is_close_to(volume, SinkVolume::READ_BACK_TOLERANCE)
+        {
+            return Err(SttError::speaker_mute(format!(
+                "sink {sink} did not change its volume"
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use crate::stt::{AudioRecording, MeetingChunk};
+
+use std::path::PathBuf;
+
+pub(crate) struct QueuedRecording {
+    pub(crate) recording: AudioRecording,
+    pub(crate) replayed: bool,
+    /// Set when the recording was loaded from a saved WAV, so it is not saved again.
+    pub(crate) saved_path: Option<PathBuf>,
+    /// Set for meeting mode chunks, whose transcripts go to the meeting file instead of the
+    /// clipboard and history.
+    pub(crate) meeting_chunk: Option<MeetingChunk>,
+}
+
+impl QueuedRecording {
+    pub(crate) fn new(recording: AudioRecording) -> Self {
+        Self {
+            recording,
+            replayed: false,
+            saved_path: None,
+            meeting_chunk: None,
+        }
+    }
+}
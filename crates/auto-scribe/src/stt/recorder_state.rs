@@ -1,19 +1,28 @@
-use crate::stt::TARGET_SAMPLE_RATE;
+use crate::stt::{StreamingResampler, TARGET_SAMPLE_RATE};
 
 pub(crate) struct RecorderState {
     pub(crate) recording: bool,
+    /// Captured audio, already mixed down to mono and resampled to 16 kHz.
     pub(crate) samples: Vec<f32>,
-    pub(crate) max_samples: usize,
+    /// `None` when the device already captures at 16 kHz.
+    pub(crate) resampler: Option<StreamingResampler>,
+    /// Scratch buffer for one callback's mono frames before resampling.
+    pub(crate) mono_frames: Vec<f32>,
+    pub(crate) captured_frames: usize,
+    pub(crate) max_frames: usize,
     pub(crate) clipped: bool,
     pub(crate) last_stream_error: Option<String>,
 }
 
 impl RecorderState {
-    pub(crate) fn new(max_samples: usize) -> Self {
+    pub(crate) fn new(max_frames: usize) -> Self {
         Self {
             recording: false,
             samples: Vec::with_capacity(TARGET_SAMPLE_RATE as usize * 4),
-            max_samples,
+            resampler: None,
+            mono_frames: Vec::new(),
+            captured_frames: 0,
+            max_frames,
             clipped: false,
             last_stream_error: None,
         }
@@ -0,0 +1,144 @@
+use crate::stt::{AudioRecording, SttError, SttResult, prepare_audio, write_wav};
+
+use std::{
+    cmp::Reverse,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const RECORDINGS_DIRECTORY: &str = "recordings";
+const DEFAULT_MAX_RECORDINGS: u64 = 200;
+const DEFAULT_MAX_AGE_DAYS: u64 = 30;
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Saved recordings under the app data directory, enabled by `[audio].keep_recordings`.
+#[derive(Clone, Debug)]
+pub(crate) struct RecordingStore {
+    directory: PathBuf,
+    max_recordings: Option<usize>,
+    max_age: Option<Duration>,
+}
+
+impl RecordingStore {
+    pub(crate) fn from_table(
+        audio_table: Option<&toml::Table>,
+        app_data_dir: &Path,
+    ) -> SttResult<Option<Self>> {
+        let keep_recordings = audio_table
+            .and_then(|table| table.get("keep_recordings"))
+            .and_then(toml::Value::as_bool)
+            .unwrap_or(false);
+        if !keep_recordings {
+            return Ok(None);
+        }
+
+        let max_recordings =
+            limit_from_table(audio_table, "max_recordings", DEFAULT_MAX_RECORDINGS)?
+                .map(|limit| usize::try_from(limit).unwrap_or(usize::MAX));
+        let max_age =
+            limit_from_table(audio_table, "max_recording_age_days", DEFAULT_MAX_AGE_DAYS)?
+                .map(|days| Duration::from_secs(days.saturating_mul(SECONDS_PER_DAY)));
+
+        Ok(Some(Self {
+            directory: app_data_dir.join(RECORDINGS_DIRECTORY),
+            max_recordings,
+            max_age,
+        }))
+    }
+
+    /// Writes the recording as a 16 kHz mono WAV with the transcript text beside it, then
+    /// applies the retention limits.
+    pub(crate) fn save(
+        &self,
+        recording: &AudioRecording,
+        completed_at: SystemTime,
+        transcript: &str,
+    ) -> SttResult<PathBuf> {
+        fs::create_dir_all(&self.directory).map_err(|error| {
+            SttError::audio_device(format!("create {}: {error}", self.directory.display()))
+        })?;
+
+        let millis = completed_at
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis())
+            .unwrap_or_default();
+        let path = self.directory.join(format!("recording-{millis}.wav"));
+        let samples = prepare_audio(recording.clone())?;
+        write_wav(&path, &samples)?;
+
+        let transcript_path = path.with_extension("txt");
+        fs::write(&transcript_path, format!("{transcript}\n")).map_err(|error| {
+            SttError::audio_device(format!("write {}: {error}", transcript_path.display()))
+        })?;
+
+        self.prune()?;
+        Ok(path)
+    }
+
+    /// Removes saved recordings beyond the count limit or older than the age limit, newest
+    /// kept first, and returns how many were removed.
+    pub(crate) fn prune(&self) -> SttResult<usize> {
+        let entries = match fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(error) => {
+                return Err(SttError::audio_device(format!(
+                    "read {}: {error}",
+                    self.directory.display()
+                )));
+            }
+        };
+
+        let mut recordings = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "wav"))
+            .filter_map(|path| {
+                let modified = fs::metadata(&path).and_then(|metadata| metadata.modified());
+                modified.ok().map(|modified| (modified, path))
+            })
+            .collect::<Vec<_>>();
+        recordings.sort_by_key(|(modified, _)| Reverse(*modified));
+
+        let now = SystemTime::now();
+        let mut removed = 0;
+        for (index, (modified, path)) in recordings.into_iter().enumerate() {
+            let over_count = self.max_recordings.is_some_and(|limit| index >= limit);
+            let too_old = self.max_age.is_some_and(|max_age| {
+                now.duration_since(modified)
+                    .is_ok_and(|elapsed| elapsed > max_age)
+            });
+            if !over_count && !too_old {
+                continue;
+            }
+
+            fs::remove_file(&path).map_err(|error| {
+                SttError::audio_device(format!("remove {}: {error}", path.display()))
+            })?;
+            let _ = fs::remove_file(path.with_extension("txt"));
+            removed += 1;
+        }
+
+        Ok(removed)
+    }
+}
+
+/// Reads a non-negative retention limit where 0 means unlimited.
+fn limit_from_table(
+    audio_table: Option<&toml::Table>,
+    key: &str,
+    default: u64,
+) -> SttResult<Option<u64>> {
+    let Some(value) = audio_table.and_then(|table| table.get(key)) else {
+        return Ok(Some(default));
+    };
+
+    match value.as_integer().map(u64::try_from) {
+        Some(Ok(0)) => Ok(None),
+        Some(Ok(limit)) => Ok(Some(limit)),
+        _ => Err(SttError::model_path(format!(
+            "config [audio].{key} must be 0 (unlimited) or a positive integer"
+        ))),
+    }
+}
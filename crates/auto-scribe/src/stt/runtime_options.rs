@@ -0,0 +1,95 @@
+use crate::stt::{GraphOptimization, SttError, SttResult};
+
+use std::path::{Path, PathBuf};
+
+const OPTIMIZED_MODEL_CACHE_DIRECTORY: &str = "cache/optimized-models";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct RuntimeOptions {
+    intra_op_threads: Option<usize>,
+    inter_op_threads: Option<usize>,
+    graph_optimization: GraphOptimization,
+    optimized_model_cache: Option<PathBuf>,
+}
+
+impl RuntimeOptions {
+    pub(crate) fn from_table(
+        model_table: Option<&toml::Table>,
+        app_data_dir: &Path,
+    ) -> SttResult<Self> {
+        let intra_op_threads = thread_count(model_table, "intra_op_threads")?;
+        let inter_op_threads = thread_count(model_table, "inter_op_threads")?;
+        let graph_optimization = match model_table.and_then(|table| table.get("graph_optimization"))
+        {
+            None => GraphOptimization::All,
+            Some(value) => {
+                let Some(name) = value.as_str() else {
+                    return Err(SttError::model_path(
+                        "config [model].graph_optimization must be a string",
+                    ));
+                };
+                GraphOptimization::from_config_name(name)?
+            }
+        };
+        let optimized_model_cache = model_table
+            .and_then(|table| table.get("optimized_model_cache"))
+            .and_then(toml::Value::as_bool)
+            .unwrap_or(false)
+            .then(|| app_data_dir.join(OPTIMIZED_MODEL_CACHE_DIRECTORY));
+
+        Ok(Self {
+            intra_op_threads,
+            inter_op_threads,
+            graph_optimization,
+            optimized_model_cache,
+        })
+    }
+
+    pub(crate) fn intra_op_threads(&self) -> Option<usize> {
+        self.intra_op_threads
+    }
+
+    pub(crate) fn inter_op_threads(&self) -> Option<usize> {
+        self.inter_op_threads
+    }
+
+    pub(crate) fn graph_optimization(&self) -> GraphOptimization {
+        self.graph_optimization
+    }
+
+    pub(crate) fn optimized_model_cache(&self) -> Option<&Path> {
+        self.optimized_model_cache.as_deref()
+    }
+
+    pub(crate) fn threads_label(&self) -> String {
+        format!(
+            "intra-op {}, inter-op {}",
+            thread_count_label(self.intra_op_threads),
+            thread_count_label(self.inter_op_threads)
+        )
+    }
+}
+
+fn thread_count(model_table: Option<&toml::Table>, key: &str) -> SttResult<Option<usize>> {
+    let Some(value) = model_table.and_then(|table| table.get(key)) else {
+        return Ok(None);
+    };
+
+    match value.as_integer() {
+        Some(0) => Ok(None),
+        Some(count) => usize::try_from(count).map(Some).map_err(|_| {
+            SttError::model_path(format!(
+                "config [model].{key} must be 0 (automatic) or a positive thread count"
+            ))
+        }),
+        None => Err(SttError::model_path(format!(
+            "config [model].{key} must be an integer"
+        ))),
+    }
+}
+
+fn thread_count_label(count: Option<usize>) -> String {
+    count
+        .map(|count| count.to_string())
+        .unwrap_or_else(|| "auto".to_string())
+}
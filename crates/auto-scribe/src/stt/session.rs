@@ -1,10 +1,26 @@
+use crate::output::OutputProfile;
 use crate::stt::{
-    AudioRecorder, MIN_RECORDING_DURATION, ModelConfig, Snapshot, SpeakerMuteGuard, State,
-    SttError, SttResult, WorkerEvent, WorkerRequest, spawn_model_download, spawn_stt_worker,
+    AudioRecorder, AudioRecording, ExecutionProvider, HistoryEntry, InstalledModel,
+    MIN_RECORDING_DURATION, MediaPauser, Meeting, MeetingChunk, ModelConfig, QueuedRecording,
+    Snapshot, SpeakerMode, SpeakerMuteGuard, State, SttError, SttResult, WorkerEvent, WorkerHealth,
+    WorkerRequest, execution_provider_list_label, import_extract_dir, list_installed_models,
+    read_wav, remove_installed_model, remove_orphaned_staging_dirs, spawn_model_download,
+    spawn_model_import, spawn_stt_worker, spawn_stt_worker_process, staging_dir,
     validate_model_dir,
 };
 
 use async_channel::{Receiver, Sender};
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, SystemTime},
+};
+
+const MAX_HISTORY_ENTRIES: usize = 50;
 
 pub(crate) struct Session {
     recorder: Option<AudioRecorder>,
@@ -14,37 +30,84 @@ pub(crate) struct Session {
     worker_ready: bool,
     state: State,
     transcript: String,
+    history: VecDeque<HistoryEntry>,
+    next_history_id: usize,
+    meeting: Option<Meeting>,
     status: String,
     popup_recording_active: bool,
+    pending_recordings: VecDeque<QueuedRecording>,
+    in_flight_recordings: VecDeque<QueuedRecording>,
+    cancel_generation: Arc<AtomicU64>,
+    discarded_results: usize,
+    worker_health: WorkerHealth,
+    worker_restart_pending: bool,
+    worker_restart_delay: Option<Duration>,
     download_file_name: String,
     download_completed_files: usize,
     download_total_files: usize,
     download_file_bytes: u64,
     download_file_total_bytes: Option<u64>,
+    download_attempt_label: String,
     speaker_mute: Option<SpeakerMuteGuard>,
-    worker_use_gpu: Option<bool>,
+    media_pauser: MediaPauser,
+    worker_execution_providers: Option<Vec<ExecutionProvider>>,
+    active_provider: Option<ExecutionProvider>,
+    provider_fallback: Option<String>,
+    optimized_model_cache_status: Option<String>,
+    installed_models: Vec<InstalledModel>,
 }
 
 impl Session {
     pub(crate) fn new() -> SttResult<(Self, Receiver<WorkerEvent>)> {
         let model_config = ModelConfig::load()?;
+        let orphan_cleanup = remove_orphaned_staging(&model_config);
+        let speaker_recovery = SpeakerMuteGuard::restore_stale_state();
         let (event_tx, event_rx) = async_channel::unbounded();
-        let (worker_tx, mut state, mut status) =
-            start_worker_if_model_is_installed(&model_config, event_tx.clone());
-        let worker_use_gpu = worker_tx.as_ref().map(|_| model_config.use_gpu());
-
-        let recorder = match AudioRecorder::new() {
-            Ok(recorder) => Some(recorder),
+        let cancel_generation = Arc::new(AtomicU64::new(0));
+        let (worker_tx, mut state, mut status) = start_worker_if_model_is_installed(
+            &model_config,
+            cancel_generation.clone(),
+            event_tx.clone(),
+        );
+        match orphan_cleanup {
+            Ok(0) => {}
+            Ok(removed) => {
+                status = format!("{status}; removed {removed} orphaned model staging directories")
+            }
+            Err(error) => status = format!("{status}; model staging cleanup failed: {error}"),
+        }
+        match speaker_recovery {
+            Ok(None) => {}
+            Ok(Some(recovered)) => {
+                status = format!("{status}; recovered from an unclean exit and {recovered}")
+            }
             Err(error) => {
-                if state == State::ModelMissing {
-                    status = format!("{status}; audio recorder unavailable: {error}");
-                } else {
-                    state = State::Error;
-                    status = error.to_string();
-                }
-                None
+                status =
+                    format!("{status}; restoring speakers after an unclean exit failed: {error}")
             }
-        };
+        }
+        let worker_execution_providers = worker_tx
+            .as_ref()
+            .map(|_| model_config.execution_providers().to_vec());
+
+        let recorder =
+            match AudioRecorder::new(model_config.audio_source(), model_config.monitor_source()) {
+                Ok(recorder) => Some(recorder),
+                Err(error) => {
+                    if state == State::ModelMissing {
+                        status = format!("{status}; audio recorder unavailable: {error}");
+                    } else {
+                        state = State::Error;
+                        status = error.to_string();
+                    }
+                    None
+                }
+            };
+
+        let installed_models =
+            list_installed_models(&model_config.models_root(), model_config.model_dir())
+                .unwrap_or_default();
+        let media_pauser = MediaPauser::new(event_tx.clone());
 
         Ok((
             Self {
@@ -55,15 +118,31 @@ impl Session {
                 worker_ready: false,
                 state,
                 transcript: String::new(),
+                history: VecDeque::new(),
+                next_history_id: 0,
+                meeting: None,
                 status,
                 popup_recording_active: false,
+                pending_recordings: VecDeque::new(),
+                in_flight_recordings: VecDeque::new(),
+                cancel_generation,
+                discarded_results: 0,
+                worker_health: WorkerHealth::default(),
+                worker_restart_pending: false,
+                worker_restart_delay: None,
                 download_file_name: String::new(),
                 download_completed_files: 0,
                 download_total_files: 0,
                 download_file_bytes: 0,
                 download_file_total_bytes: None,
+                download_attempt_label: String::new(),
                 speaker_mute: None,
-                worker_use_gpu,
+                media_pauser,
+                worker_execution_providers,
+                active_provider: None,
+                provider_fallback: None,
+                optimized_model_cache_status: None,
+                installed_models,
             },
             event_rx,
         ))
@@ -78,26 +157,64 @@ impl Session {
             status: self.status.clone(),
             model_can_download: self.state == State::ModelMissing,
             model_downloading: self.state == State::Downloading,
+            model_importing: self.state == State::Importing,
             model_download_files_percent: self.download_files_percent(),
             model_download_files_label: self.download_files_label(),
             model_download_file_percent: self.download_file_percent(),
             model_download_file_known: self.download_file_total_bytes.is_some(),
             model_download_file_label: self.download_file_label(),
+            model_unloaded: self.state == State::Unloaded,
+            worker_restarting: self.worker_restart_pending,
             model_dir: self.model_config.model_dir().display().to_string(),
             config_path: self.model_config.config_path().display().to_string(),
-            use_gpu: self.model_config.use_gpu(),
-            auto_mute_speakers: self.model_config.auto_mute_speakers(),
+            enabled_providers: self.model_config.execution_providers().to_vec(),
+            execution_providers: execution_provider_list_label(
+                self.model_config.execution_providers(),
+            ),
+            active_provider: self.active_provider.map(ExecutionProvider::label),
+            provider_fallback: self.provider_fallback.clone(),
+            onnx_threads: self.model_config.runtime_options().threads_label(),
+            graph_optimization: self
+                .model_config
+                .runtime_options()
+                .graph_optimization()
+                .config_name(),
+            optimized_model_cache: self.optimized_model_cache_label(),
+            speaker_mode: self.model_config.speaker_mode(),
+            pause_media: self.model_config.pause_media(),
+            meeting_label: self.meeting.as_ref().map(|meeting| {
+                let seconds = meeting.elapsed().as_secs();
+                format!(
+                    "Recording {} for {}:{:02} into {}",
+                    meeting.source().label(),
+                    seconds / 60,
+                    seconds % 60,
+                    meeting.transcript_path().display()
+                )
+            }),
         }
     }
 
     pub(crate) fn popup_label(&self) -> String {
+        let queued_recordings = self.queued_recordings();
         match self.state {
             State::Loading => "STT loading".to_string(),
+            State::Unloaded => "Model unloaded".to_string(),
             State::ModelMissing => "Model missing".to_string(),
             State::Downloading => "Downloading model".to_string(),
+            State::Importing => "Importing model".to_string(),
             State::Idle if self.transcript.is_empty() => "Hotkey active".to_string(),
             State::Idle => "Transcription complete".to_string(),
+            State::Recording if queued_recordings > 0 => {
+                format!("Listening... ({queued_recordings} queued)")
+            }
             State::Recording => "Listening...".to_string(),
+            State::Transcribing if !self.worker_ready => {
+                format!("Loading model... ({queued_recordings} queued)")
+            }
+            State::Transcribing if queued_recordings > 1 => {
+                format!("Transcribing... ({queued_recordings} queued)")
+            }
             State::Transcribing => "Transcribing...".to_string(),
             State::Error => "STT unavailable".to_string(),
         }
@@ -107,12 +224,110 @@ impl Session {
         self.transcript.clone()
     }
 
+    /// Transcripts from this session, newest first.
+    pub(crate) fn history(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.history.iter()
+    }
+
+    pub(crate) fn history_entry(&self, id: usize) -> Option<&HistoryEntry> {
+        self.history.iter().find(|entry| entry.id == id)
+    }
+
+    pub(crate) fn rename_speaker(&mut self, entry_id: usize, speaker: usize, name: &str) -> bool {
+        self.history
+            .iter_mut()
+            .find(|entry| entry.id == entry_id)
+            .is_some_and(|entry| entry.transcript.rename_speaker(speaker, name))
+    }
+
+    pub(crate) fn output_profiles(&self) -> &[OutputProfile] {
+        self.model_config.output_profiles()
+    }
+
+    /// Replaces the profile at `index`, or adds `profile` when `index` is `None`.
+    pub(crate) fn save_output_profile(&mut self, index: Option<usize>, profile: OutputProfile) {
+        let mut profiles = self.model_config.output_profiles().to_vec();
+        let name = profile.name.clone();
+        match index.and_then(|index| profiles.get_mut(index)) {
+            Some(existing) => *existing = profile,
+            None => profiles.push(profile),
+        }
+
+        self.status = match self.model_config.set_output_profiles(profiles) {
+            Ok(()) => format!("Saved output profile {name}"),
+            Err(error) => format!("Failed to save output profile {name}: {error}"),
+        };
+    }
+
+    pub(crate) fn delete_output_profile(&mut self, index: usize) {
+        let mut profiles = self.model_config.output_profiles().to_vec();
+        if index >= profiles.len() {
+            return;
+        }
+        let profile = profiles.remove(index);
+
+        self.status = match self.model_config.set_output_profiles(profiles) {
+            Ok(()) => format!("Deleted output profile {}", profile.name),
+            Err(error) => format!("Failed to delete output profile {}: {error}", profile.name),
+        };
+    }
+
+    pub(crate) fn cancel_hotkey(&self) -> Option<&str> {
+        self.model_config.cancel_hotkey()
+    }
+
+    pub(crate) fn can_cancel(&self) -> bool {
+        self.popup_recording_active
+            || self.state == State::Recording
+            || self.queued_recordings() > 0
+    }
+
+    pub(crate) fn cancel(&mut self) -> bool {
+        if !self.can_cancel() {
+            return false;
+        }
+
+        if self.popup_recording_active || self.state == State::Recording {
+            self.popup_recording_active = false;
+            if let Some(recorder) = &self.recorder {
+                let _ = recorder.stop();
+            }
+            self.restore_speakers_after_recording();
+            self.resume_media_after_recording();
+        }
+
+        // Results already queued in the worker arrive in order; the first
+        // `discarded_results` of them belong to the cancelled recordings.
+        let generation = self.cancel_generation.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Some(worker_tx) = &self.worker_tx {
+            let _ = worker_tx.try_send(WorkerRequest::Cancel { generation });
+        }
+        self.discarded_results += self.in_flight_recordings.len();
+        // Meeting chunks are not what the user cancelled, so they are sent again.
+        let mut meeting_chunks = std::mem::take(&mut self.in_flight_recordings)
+            .into_iter()
+            .filter(|queued| queued.meeting_chunk.is_some())
+            .collect::<VecDeque<_>>();
+        self.pending_recordings
+            .retain(|queued| queued.meeting_chunk.is_some());
+        meeting_chunks.append(&mut self.pending_recordings);
+        while self.worker_ready
+            && let Some(queued) = meeting_chunks.pop_front()
+        {
+            self.send_recording(queued);
+        }
+        self.pending_recordings.append(&mut meeting_chunks);
+        self.settle_after_recording();
+        self.status = "Cancelled; clipboard unchanged".to_string();
+        true
+    }
+
     pub(crate) fn should_keep_popup_open_after_release(&self) -> bool {
         self.state == State::Transcribing
     }
 
     pub(crate) fn start_model_download(&mut self) {
-        if self.state == State::Downloading {
+        if matches!(self.state, State::Downloading | State::Importing) {
             return;
         }
 
@@ -121,8 +336,88 @@ impl Session {
             return;
         }
 
+        self.begin_model_download();
+    }
+
+    pub(crate) fn redownload_model(&mut self) {
+        if matches!(
+            self.state,
+            State::Downloading
+                | State::Importing
+                | State::Loading
+                | State::Recording
+                | State::Transcribing
+        ) {
+            self.status = self.model_busy_status();
+            return;
+        }
+
+        self.begin_model_download();
+    }
+
+    pub(crate) fn installed_models(&self) -> &[InstalledModel] {
+        &self.installed_models
+    }
+
+    pub(crate) fn models_root(&self) -> PathBuf {
+        self.model_config.models_root()
+    }
+
+    pub(crate) fn refresh_installed_models(&mut self) {
+        match list_installed_models(
+            &self.model_config.models_root(),
+            self.model_config.model_dir(),
+        ) {
+            Ok(installed_models) => self.installed_models = installed_models,
+            Err(error) => self.status = format!("Failed to list installed models: {error}"),
+        }
+    }
+
+    pub(crate) fn delete_model(&mut self, model_dir: &Path) {
+        if !self
+            .installed_models
+            .iter()
+            .any(|installed_model| installed_model.path == model_dir)
+        {
+            self.status = format!("{} is not an installed model", model_dir.display());
+            return;
+        }
+
+        let deleting_active_model = model_dir == self.model_config.model_dir();
+        if deleting_active_model
+            && matches!(
+                self.state,
+                State::Downloading
+                    | State::Importing
+                    | State::Loading
+                    | State::Recording
+                    | State::Transcribing
+            )
+        {
+            self.status = self.model_busy_status();
+            return;
+        }
+
+        if let Err(error) = remove_installed_model(&self.model_config.models_root(), model_dir) {
+            self.status = format!("Failed to delete model: {error}");
+            self.refresh_installed_models();
+            return;
+        }
+
+        if deleting_active_model {
+            self.worker_tx = None;
+            self.worker_execution_providers = None;
+            self.worker_ready = false;
+            self.state = State::ModelMissing;
+        }
+
+        self.status = format!("Deleted model {}", model_dir.display());
+        self.refresh_installed_models();
+    }
+
+    fn begin_model_download(&mut self) {
         self.worker_tx = None;
-        self.worker_use_gpu = None;
+        self.worker_execution_providers = None;
         self.worker_ready = false;
         self.state = State::Downloading;
         self.status = format!(
@@ -134,6 +429,7 @@ impl Session {
         self.download_total_files = 0;
         self.download_file_bytes = 0;
         self.download_file_total_bytes = None;
+        self.download_attempt_label.clear();
 
         if let Err(error) = spawn_model_download(self.model_config.clone(), self.event_tx.clone()) {
             self.state = State::ModelMissing;
@@ -141,75 +437,179 @@ impl Session {
         }
     }
 
-    pub(crate) fn set_use_gpu(&mut self, enabled: bool) {
-        if let Err(error) = self.model_config.set_use_gpu(enabled) {
-            self.status = format!("Failed to save GPU setting: {error}");
-            return;
+    pub(crate) fn start_model_import(&mut self, source: PathBuf) {
+        match self.state {
+            State::Downloading => {
+                self.status = "Wait for the model download to finish before importing".to_string();
+                return;
+            }
+            State::Importing => {
+                self.status = "A model import is already running".to_string();
+                return;
+            }
+            State::Recording | State::Transcribing => {
+                self.status = "Wait for the current transcription before importing".to_string();
+                return;
+            }
+            State::Loading => {
+                self.status = "Wait for the model to finish loading before importing".to_string();
+                return;
+            }
+            State::Unloaded | State::ModelMissing | State::Idle | State::Error => {}
         }
 
-        if self.state == State::Downloading {
-            self.status = if enabled {
-                "GPU inference enabled; model download still running".to_string()
-            } else {
-                "GPU inference disabled; model download still running".to_string()
-            };
+        self.worker_tx = None;
+        self.worker_execution_providers = None;
+        self.worker_ready = false;
+        self.state = State::Importing;
+        self.status = format!(
+            "Importing STT model from {} to {}",
+            source.display(),
+            self.model_config.model_dir().display()
+        );
+
+        if let Err(error) =
+            spawn_model_import(self.model_config.clone(), source, self.event_tx.clone())
+        {
+            self.model_import_failed(error.to_string());
+        }
+    }
+
+    pub(crate) fn execution_providers(&self) -> &[ExecutionProvider] {
+        self.model_config.execution_providers()
+    }
+
+    pub(crate) fn set_execution_providers(&mut self, providers: &[ExecutionProvider]) {
+        if let Err(error) = self.model_config.set_execution_providers(providers) {
+            self.status = format!("Failed to save execution providers: {error}");
             return;
         }
 
-        if self.state == State::Loading {
-            self.status = if enabled {
-                "GPU inference enabled; it will apply after the current model load".to_string()
-            } else {
-                "GPU inference disabled; it will apply after the current model load".to_string()
-            };
+        let providers_label =
+            execution_provider_list_label(self.model_config.execution_providers());
+        let applies = match self.state {
+            State::Downloading => "; model download still running",
+            State::Importing => "; model import still running",
+            State::Loading => "; it will apply after the current model load",
+            State::Unloaded => "; it will apply when the model reloads",
+            State::Recording | State::Transcribing => "; it will apply after this transcription",
+            State::ModelMissing | State::Idle | State::Error => {
+                if validate_model_dir(self.model_config.model_dir()).is_ok() {
+                    self.start_worker();
+                    return;
+                }
+                ""
+            }
+        };
+        self.status = format!("Execution providers set to {providers_label}{applies}");
+    }
+
+    pub(crate) fn set_speaker_mode(&mut self, mode: SpeakerMode) {
+        if mode == self.model_config.speaker_mode() {
             return;
         }
 
-        if matches!(self.state, State::Recording | State::Transcribing) {
-            self.status = if enabled {
-                "GPU inference enabled; it will apply after this transcription".to_string()
-            } else {
-                "GPU inference disabled; it will apply after this transcription".to_string()
-            };
+        if let Err(error) = self.model_config.set_speaker_mode(mode) {
+            self.status = format!("Failed to save speaker setting: {error}");
             return;
         }
 
-        if validate_model_dir(self.model_config.model_dir()).is_ok() {
-            self.start_worker();
+        // Put the sink back before a different mode takes hold of it.
+        self.restore_speakers_after_recording();
+        if self.state == State::Recording && !self.mute_speakers_for_recording() {
             return;
         }
 
-        self.status = if enabled {
-            "GPU inference enabled".to_string()
-        } else {
-            "GPU inference disabled".to_string()
+        self.status = match mode {
+            SpeakerMode::Off => "Speakers stay as they are while recording".to_string(),
+            SpeakerMode::Mute => "Speakers are muted while recording".to_string(),
+            SpeakerMode::Duck => format!(
+                "Speakers are lowered to {}% while recording",
+                self.model_config.duck_volume_percent()
+            ),
         };
     }
 
-    pub(crate) fn set_auto_mute_speakers(&mut self, enabled: bool) {
-        if let Err(error) = self.model_config.set_auto_mute_speakers(enabled) {
-            self.status = format!("Failed to save auto-mute setting: {error}");
+    pub(crate) fn set_pause_media(&mut self, enabled: bool) {
+        if let Err(error) = self.model_config.set_pause_media(enabled) {
+            self.status = format!("Failed to save media pause setting: {error}");
             return;
         }
 
         if enabled {
-            if self.state == State::Recording
-                && self.speaker_mute.is_none()
-                && !self.mute_speakers_for_recording()
-            {
+            if self.state == State::Recording && !self.pause_media_for_recording() {
                 return;
             }
         } else {
-            self.restore_speakers_after_recording();
+            self.resume_media_after_recording();
         }
 
         self.status = if enabled {
-            "Speaker auto-mute enabled".to_string()
+            "Media players are paused while recording".to_string()
         } else {
-            "Speaker auto-mute disabled".to_string()
+            "Media players keep playing while recording".to_string()
         };
     }
 
+    pub(crate) fn idle_unload_after(&self) -> Option<Duration> {
+        self.model_config.idle_unload_after()
+    }
+
+    pub(crate) fn unload_idle_model(&mut self) {
+        if self.state != State::Idle
+            || !self.worker_ready
+            || self.popup_recording_active
+            || self.meeting.is_some()
+        {
+            return;
+        }
+
+        // Dropping the request sender ends the worker thread, which frees the model.
+        self.worker_tx = None;
+        self.worker_execution_providers = None;
+        self.worker_ready = false;
+        self.active_provider = None;
+        self.state = State::Unloaded;
+        self.status = "STT model unloaded while idle; the next hotkey press reloads it".to_string();
+    }
+
+    pub(crate) fn worker_health(&self) -> &WorkerHealth {
+        &self.worker_health
+    }
+
+    pub(crate) fn take_worker_restart_delay(&mut self) -> Option<Duration> {
+        self.worker_restart_delay.take()
+    }
+
+    pub(crate) fn restart_crashed_worker(&mut self) {
+        if !self.worker_restart_pending {
+            return;
+        }
+
+        if !matches!(
+            self.state,
+            State::Loading | State::Transcribing | State::Recording
+        ) {
+            self.worker_restart_pending = false;
+            return;
+        }
+
+        let recording = self.state == State::Recording;
+        self.start_worker();
+        self.worker_health.record_restart();
+        if self.worker_tx.is_none() {
+            self.pending_recordings.clear();
+            return;
+        }
+
+        if recording {
+            self.state = State::Recording;
+            self.status = "Listening for speech while the STT worker restarts".to_string();
+        } else if self.queued_recordings() > 0 {
+            self.settle_after_recording();
+        }
+    }
+
     pub(crate) fn popup_opened(&mut self) {
         self.poll_recorder_error();
 
@@ -218,6 +618,14 @@ impl Session {
             return;
         }
 
+        let reloading = self.state == State::Unloaded;
+        if reloading {
+            self.start_worker();
+            if self.worker_tx.is_none() {
+                return;
+            }
+        }
+
         let Some(recorder) = &self.recorder else {
             self.set_error(SttError::audio_device("audio recorder is unavailable"));
             return;
@@ -227,9 +635,14 @@ impl Session {
             Ok(()) => {
                 self.transcript.clear();
                 self.state = State::Recording;
-                self.status = "Listening for speech".to_string();
+                self.status = if reloading {
+                    "Listening for speech while the STT model reloads".to_string()
+                } else {
+                    "Listening for speech".to_string()
+                };
                 self.popup_recording_active = true;
                 let _ = self.mute_speakers_for_recording();
+                let _ = self.pause_media_for_recording();
             }
             Err(error) => self.set_error(error),
         }
@@ -246,6 +659,7 @@ impl Session {
 
         if self.state != State::Recording {
             self.restore_speakers_after_recording();
+            self.resume_media_after_recording();
             return;
         }
 
@@ -256,62 +670,254 @@ impl Session {
 
         let stop_result = recorder.stop();
         self.restore_speakers_after_recording();
+        self.resume_media_after_recording();
 
         match stop_result {
             Ok(recording) => {
                 if recording.is_shorter_than(MIN_RECORDING_DURATION) {
-                    self.state = State::Idle;
-                    self.status = "Ready".to_string();
+                    self.settle_after_recording();
                     return;
                 }
 
-                self.state = State::Transcribing;
-                self.status = "Transcribing captured audio".to_string();
                 if recording.clipped() {
                     self.transcript =
                         "recording hit the 60 second cap; transcribing captured audio".to_string();
                 }
 
-                let Some(worker_tx) = &self.worker_tx else {
-                    self.set_error(SttError::worker_channel("STT worker is unavailable"));
-                    return;
-                };
-
-                if worker_tx
-                    .try_send(WorkerRequest::Transcribe(recording))
-                    .is_err()
-                {
-                    self.set_error(SttError::worker_channel(
-                        "could not send recording to STT worker",
-                    ));
+                let queued = QueuedRecording::new(recording);
+                if self.worker_ready {
+                    self.send_recording(queued);
+                } else {
+                    self.pending_recordings.push_back(queued);
                 }
+                self.settle_after_recording();
             }
             Err(error) => self.set_error(error),
         }
     }
 
-    pub(crate) fn apply_worker_event(&mut self, event: WorkerEvent) {
+    /// Queues a saved recording for transcription with the current model. The transcript is
+    /// delivered like one from the hotkey.
+    pub(crate) fn retranscribe_recording(&mut self, path: PathBuf) {
+        self.poll_recorder_error();
+
+        if !self.can_transcribe() {
+            self.status = self.disabled_status();
+            return;
+        }
+
+        let recording = match read_wav(&path) {
+            Ok(recording) => recording,
+            Err(error) => {
+                self.status = format!("Re-transcribe failed: {error}");
+                return;
+            }
+        };
+
+        if self.state == State::Unloaded {
+            self.start_worker();
+            if self.worker_tx.is_none() {
+                return;
+            }
+        }
+
+        self.transcript.clear();
+        let mut queued = QueuedRecording::new(recording);
+        queued.saved_path = Some(path);
+        if self.worker_ready {
+            self.send_recording(queued);
+        } else {
+            self.pending_recordings.push_back(queued);
+        }
+        self.settle_after_recording();
+    }
+
+    pub(crate) fn meeting_active(&self) -> bool {
+        self.meeting.is_some()
+    }
+
+    /// Starts recording `[meeting].source` into a new transcript file under the app data
+    /// directory. Dictation keeps working alongside it.
+    pub(crate) fn start_meeting(&mut self) {
+        if self.meeting.is_some() {
+            return;
+        }
+        if !self.can_transcribe() {
+            self.status = self.disabled_status();
+            return;
+        }
+        if self.state == State::Unloaded {
+            self.start_worker();
+            if self.worker_tx.is_none() {
+                return;
+            }
+        }
+
+        match Meeting::start(
+            self.model_config.meeting_source(),
+            self.model_config.monitor_source(),
+            &self.model_config.meetings_dir(),
+        ) {
+            Ok(meeting) => {
+                self.status = format!(
+                    "Meeting started; writing {}",
+                    meeting.transcript_path().display()
+                );
+                self.meeting = Some(meeting);
+            }
+            Err(error) => self.status = format!("Meeting could not start: {error}"),
+        }
+    }
+
+    /// Queues the meeting's finished chunks; called about once a second while it runs.
+    pub(crate) fn poll_meeting(&mut self) {
+        let Some(meeting) = &mut self.meeting else {
+            return;
+        };
+
+        let chunks = match meeting.take_stream_error() {
+            Ok(None) => meeting.take_chunks(),
+            Ok(Some(message)) => Err(SttError::audio_stream(message)),
+            Err(error) => Err(error),
+        };
+        match chunks {
+            Ok(chunks) => self.queue_meeting_chunks(chunks),
+            Err(error) => {
+                self.stop_meeting();
+                self.status = format!("Meeting stopped: {error}");
+            }
+        }
+    }
+
+    pub(crate) fn stop_meeting(&mut self) {
+        let Some(meeting) = self.meeting.take() else {
+            return;
+        };
+
+        let transcript_path = meeting.transcript_path().to_path_buf();
+        match meeting.stop() {
+            Ok(chunks) => {
+                self.queue_meeting_chunks(chunks);
+                self.status = format!(
+                    "Meeting stopped; transcript in {}",
+                    transcript_path.display()
+                );
+            }
+            Err(error) => self.status = format!("Meeting stopped: {error}"),
+        }
+    }
+
+    pub(crate) fn apply_worker_event(&mut self, event: WorkerEvent) -> Option<String> {
         self.poll_recorder_error();
 
+        if self.discarded_results > 0
+            && matches!(
+                event,
+                WorkerEvent::Transcript(_)
+                    | WorkerEvent::TranscriptDiscarded
+                    | WorkerEvent::Error(_)
+            )
+        {
+            self.discarded_results -= 1;
+            return None;
+        }
+
+        let mut delivered_transcript = None;
         match event {
-            WorkerEvent::Ready => {
+            WorkerEvent::Ready(active_provider) => {
                 self.worker_ready = true;
-                if self.recorder.is_some() && self.state == State::Loading {
+                self.active_provider = Some(active_provider);
+                self.worker_health.record_ready();
+                if !self.pending_recordings.is_empty() {
+                    while self.worker_ready
+                        && let Some(queued) = self.pending_recordings.pop_front()
+                    {
+                        self.send_recording(queued);
+                    }
+                    if self.state == State::Transcribing || self.state == State::Loading {
+                        self.settle_after_recording();
+                    }
+                } else if self.recorder.is_some() && self.state == State::Loading {
                     self.state = State::Idle;
                     self.status = self.ready_status();
-                    self.restart_worker_if_gpu_setting_changed();
+                    self.restart_worker_if_provider_setting_changed();
+                }
+            }
+            WorkerEvent::ProviderFallback(reason) => {
+                self.provider_fallback = Some(reason);
+                self.status = self.loading_status();
+            }
+            WorkerEvent::OptimizedModelCache(status) => {
+                self.optimized_model_cache_status = Some(status);
+            }
+            WorkerEvent::Transcript(transcript)
+                if self
+                    .in_flight_recordings
+                    .front()
+                    .is_some_and(|queued| queued.meeting_chunk.is_some()) =>
+            {
+                if let Some(chunk) = self
+                    .in_flight_recordings
+                    .pop_front()
+                    .and_then(|queued| queued.meeting_chunk)
+                    && let Err(error) = chunk.append(&transcript)
+                {
+                    self.status = format!("Meeting transcript not written: {error}");
                 }
             }
             WorkerEvent::Transcript(transcript) => {
-                self.transcript = transcript;
-                self.state = State::Idle;
-                self.status = self.ready_status();
-                self.popup_recording_active = false;
-                self.restart_worker_if_gpu_setting_changed();
+                let finished = self.in_flight_recordings.pop_front();
+                let text = transcript.text();
+                delivered_transcript = Some(text.clone());
+                self.transcript = text;
+                let mut save_error = None;
+                if !self.transcript.is_empty() {
+                    let completed_at = SystemTime::now();
+                    let recording_path = match finished
+                        .map(|finished| self.save_recording(finished, completed_at))
+                    {
+                        Some(Ok(path)) => path,
+                        Some(Err(error)) => {
+                            save_error = Some(error);
+                            None
+                        }
+                        None => None,
+                    };
+                    self.next_history_id += 1;
+                    self.history.push_front(HistoryEntry {
+                        id: self.next_history_id,
+                        completed_at,
+                        transcript,
+                        recording_path,
+                    });
+                    self.history.truncate(MAX_HISTORY_ENTRIES);
+                }
+                if self.state == State::Transcribing {
+                    self.settle_after_recording();
+                }
+                if self.state == State::Idle {
+                    self.restart_worker_if_provider_setting_changed();
+                }
+                if let Some(error) = save_error {
+                    self.status = format!("{}; saving recording failed: {error}", self.status);
+                }
+            }
+            WorkerEvent::TranscriptDiscarded => {
+                self.in_flight_recordings.pop_front();
+                if self.state == State::Transcribing {
+                    self.settle_after_recording();
+                }
             }
+            WorkerEvent::Error(message) if self.worker_ready => self.recording_failed(message),
             WorkerEvent::Error(message) => {
                 self.set_error(SttError::speech_to_text(message));
             }
+            WorkerEvent::WorkerCrashed(reason) => {
+                // A worker that was already replaced or unloaded is not supervised.
+                if self.worker_tx.is_some() {
+                    self.worker_crashed(reason);
+                }
+            }
             WorkerEvent::ModelDownloadProgress {
                 file_name,
                 completed_files,
@@ -325,39 +931,79 @@ impl Session {
                 self.download_file_bytes = file_downloaded_bytes;
                 self.download_file_total_bytes = file_total_bytes;
                 self.state = State::Downloading;
-                self.status = self.download_file_label();
+                self.status = self.download_status();
+            }
+            WorkerEvent::ModelDownloadAttempt {
+                file_name,
+                source,
+                route,
+                attempt,
+                total_attempts,
+                previous_error,
+            } => {
+                self.download_attempt_label =
+                    format!("{source} via {route} (attempt {attempt}/{total_attempts})");
+                if let Some(previous_error) = previous_error {
+                    self.download_attempt_label
+                        .push_str(&format!("; previous source failed: {previous_error}"));
+                }
+                self.download_file_name = file_name;
+                self.state = State::Downloading;
+                self.status = self.download_status();
             }
             WorkerEvent::ModelDownloadFinished => {
+                self.download_attempt_label.clear();
                 self.download_file_name.clear();
                 self.download_completed_files = 0;
                 self.download_total_files = 0;
                 self.download_file_bytes = 0;
                 self.download_file_total_bytes = None;
+                self.refresh_installed_models();
                 self.start_worker();
             }
             WorkerEvent::ModelDownloadError(message) => {
+                self.download_attempt_label.clear();
                 self.download_file_name.clear();
                 self.download_completed_files = 0;
                 self.download_total_files = 0;
                 self.download_file_bytes = 0;
                 self.download_file_total_bytes = None;
-                self.worker_use_gpu = None;
+                self.worker_execution_providers = None;
                 self.worker_ready = false;
                 self.worker_tx = None;
                 self.state = State::ModelMissing;
                 self.status = format!("Model download failed: {message}");
             }
+            WorkerEvent::ModelImportFinished(checksum_status) => {
+                self.refresh_installed_models();
+                self.start_worker();
+                self.status = format!(
+                    "Imported STT model ({}); {}",
+                    checksum_status.label(),
+                    self.status
+                );
+            }
+            WorkerEvent::ModelImportError(message) => {
+                self.model_import_failed(message);
+            }
+            WorkerEvent::MediaPauseError(message) => {
+                self.status = message;
+            }
         }
+
+        delivered_transcript
     }
 
     pub(crate) fn stop_recording_for_shutdown(&mut self) {
         if !self.popup_recording_active && self.state != State::Recording {
+            self.media_pauser.shut_down();
             return;
         }
 
         self.popup_recording_active = false;
         let stop_result = self.recorder.as_ref().map(AudioRecorder::stop);
         self.restore_speakers_after_recording();
+        self.media_pauser.shut_down();
         match stop_result {
             Some(Ok(_recording)) => {
                 if self.state == State::Recording {
@@ -371,10 +1017,187 @@ impl Session {
     }
 
     fn can_record(&self) -> bool {
-        self.worker_ready
-            && self.worker_tx.is_some()
-            && self.recorder.is_some()
-            && self.state == State::Idle
+        self.recorder.is_some() && self.can_transcribe()
+    }
+
+    fn can_transcribe(&self) -> bool {
+        match self.state {
+            State::Idle => self.worker_ready && self.worker_tx.is_some(),
+            State::Loading | State::Transcribing => {
+                self.worker_tx.is_some() || self.worker_restart_pending
+            }
+            State::Unloaded => true,
+            State::ModelMissing
+            | State::Downloading
+            | State::Importing
+            | State::Recording
+            | State::Error => false,
+        }
+    }
+
+    /// Returns the saved WAV for a finished recording, writing it first when
+    /// `[audio].keep_recordings` is on.
+    fn save_recording(
+        &self,
+        finished: QueuedRecording,
+        completed_at: SystemTime,
+    ) -> SttResult<Option<PathBuf>> {
+        if finished.saved_path.is_some() {
+            return Ok(finished.saved_path);
+        }
+
+        let Some(recording_store) = self.model_config.recording_store() else {
+            return Ok(None);
+        };
+        recording_store
+            .save(&finished.recording, completed_at, &self.transcript)
+            .map(Some)
+    }
+
+    fn queue_meeting_chunks(&mut self, chunks: Vec<(AudioRecording, MeetingChunk)>) {
+        for (recording, chunk) in chunks {
+            let mut queued = QueuedRecording::new(recording);
+            queued.meeting_chunk = Some(chunk);
+            if self.worker_ready {
+                self.send_recording(queued);
+            } else {
+                self.pending_recordings.push_back(queued);
+            }
+        }
+    }
+
+    /// Dictation recordings waiting for or being transcribed; meeting chunks run in the
+    /// background and do not change the dictation state.
+    fn queued_recordings(&self) -> usize {
+        self.pending_recordings
+            .iter()
+            .chain(&self.in_flight_recordings)
+            .filter(|queued| queued.meeting_chunk.is_none())
+            .count()
+    }
+
+    fn settle_after_recording(&mut self) {
+        let queued_recordings = self.queued_recordings();
+        if queued_recordings == 0 {
+            if self.worker_ready {
+                self.state = State::Idle;
+                self.status = self.ready_status();
+            } else {
+                self.state = State::Loading;
+                self.status = "Loading STT model".to_string();
+            }
+            return;
+        }
+
+        self.state = State::Transcribing;
+        self.status = match (self.worker_ready, queued_recordings) {
+            (false, 1) => "Waiting for the STT model to load before transcribing".to_string(),
+            (false, _) => format!(
+                "Waiting for the STT model to load before transcribing {queued_recordings} recordings"
+            ),
+            (true, 1) => "Transcribing captured audio".to_string(),
+            (true, _) => format!(
+                "Transcribing captured audio; {} more recordings queued",
+                queued_recordings - 1
+            ),
+        };
+    }
+
+    fn send_recording(&mut self, queued: QueuedRecording) {
+        let Some(worker_tx) = &self.worker_tx else {
+            self.set_error(SttError::worker_channel("STT worker is unavailable"));
+            return;
+        };
+
+        // The session keeps its own copy so a crashed worker's recordings can be replayed.
+        let generation = self.cancel_generation.load(Ordering::Relaxed);
+        // Meeting chunks are cut at pauses, too short to tell speakers apart.
+        let diarization_model = queued
+            .meeting_chunk
+            .is_none()
+            .then(|| self.model_config.diarization().model_for(&queued.recording))
+            .flatten();
+        if worker_tx
+            .try_send(WorkerRequest::Transcribe {
+                recording: queued.recording.clone(),
+                processing: self.model_config.audio_processing(),
+                diarization_model,
+                generation,
+            })
+            .is_err()
+        {
+            self.pending_recordings.push_front(queued);
+            self.worker_crashed("STT worker request channel closed".to_string());
+            return;
+        }
+
+        self.in_flight_recordings.push_back(queued);
+    }
+
+    /// A loaded worker reports a failed transcription for the oldest in-flight recording. Only
+    /// that recording is dropped; the worker keeps its model and goes on with the next one.
+    fn recording_failed(&mut self, message: String) {
+        let failed = self.in_flight_recordings.pop_front();
+        if failed.is_some_and(|failed| failed.meeting_chunk.is_some()) {
+            self.status = format!("Meeting chunk not transcribed: {message}");
+            return;
+        }
+
+        if self.state == State::Transcribing {
+            self.settle_after_recording();
+        }
+        self.status = match self.state {
+            State::Transcribing | State::Recording => {
+                format!("Transcription failed: {message}; {}", self.status)
+            }
+            _ => format!("Transcription failed: {message}"),
+        };
+    }
+
+    fn worker_crashed(&mut self, reason: String) {
+        self.worker_tx = None;
+        self.worker_execution_providers = None;
+        self.worker_ready = false;
+        self.active_provider = None;
+        // Results for cancelled recordings died with the worker.
+        self.discarded_results = 0;
+
+        // The oldest in-flight recording is the one the worker was transcribing when it died.
+        let mut requeued = std::mem::take(&mut self.in_flight_recordings);
+        let mut dropped_recording = false;
+        if let Some(mut failed) = requeued.pop_front() {
+            if failed.replayed {
+                self.worker_health.record_dropped_recording();
+                dropped_recording = true;
+            } else {
+                failed.replayed = true;
+                self.worker_health.record_replayed_recording();
+                requeued.push_front(failed);
+            }
+        }
+        requeued.append(&mut self.pending_recordings);
+        self.pending_recordings = requeued;
+
+        let Some(restart_delay) = self.worker_health.record_crash(reason.clone()) else {
+            self.set_error(SttError::speech_to_text(format!(
+                "STT worker crashed repeatedly; last error: {reason}"
+            )));
+            return;
+        };
+
+        self.worker_restart_pending = true;
+        self.worker_restart_delay = Some(restart_delay);
+        if self.state != State::Recording {
+            self.settle_after_recording();
+        }
+        self.status = format!(
+            "STT worker crashed ({reason}); restarting in {}s",
+            restart_delay.as_secs()
+        );
+        if dropped_recording {
+            self.status
+                .push_str("; dropped a recording that had already been replayed once");
+        }
     }
 
     fn disabled_status(&self) -> String {
@@ -382,9 +1205,14 @@ impl Session {
             State::Error => self.status.clone(),
             State::ModelMissing => "Download the STT model before recording".to_string(),
             State::Downloading => "STT model download is still running".to_string(),
-            State::Loading => "STT worker is loading".to_string(),
+            State::Importing => "STT model import is still running".to_string(),
             State::Recording => "Already recording".to_string(),
-            State::Transcribing => "Transcription is still running".to_string(),
+            State::Loading | State::Transcribing | State::Unloaded if self.recorder.is_none() => {
+                "Audio recorder is unavailable".to_string()
+            }
+            State::Loading | State::Transcribing | State::Unloaded => {
+                "STT worker is unavailable".to_string()
+            }
             State::Idle if !self.worker_ready => "STT worker is loading".to_string(),
             State::Idle if self.recorder.is_none() => "Audio recorder is unavailable".to_string(),
             State::Idle => "STT is not ready".to_string(),
@@ -408,24 +1236,35 @@ impl Session {
             let _ = recorder.stop();
         }
         self.restore_speakers_after_recording();
+        self.resume_media_after_recording();
 
+        self.pending_recordings.clear();
+        self.in_flight_recordings.clear();
+        self.worker_restart_pending = false;
         self.state = State::Error;
         self.status = error.to_string();
         self.popup_recording_active = false;
     }
 
     fn mute_speakers_for_recording(&mut self) -> bool {
-        if !self.model_config.auto_mute_speakers() || self.speaker_mute.is_some() {
+        if self.speaker_mute.is_some() {
             return true;
         }
 
-        match SpeakerMuteGuard::mute_default_sink() {
+        let guard = match self.model_config.speaker_mode() {
+            SpeakerMode::Off => return true,
+            SpeakerMode::Mute => SpeakerMuteGuard::mute_default_sink(),
+            SpeakerMode::Duck => {
+                SpeakerMuteGuard::duck_default_sink(self.model_config.duck_volume_percent())
+            }
+        };
+        match guard {
             Ok(guard) => {
                 self.speaker_mute = Some(guard);
                 true
             }
             Err(error) => {
-                self.status = format!("Listening for speech; speaker auto-mute failed: {error}");
+                self.status = format!("Listening for speech; quieting speakers failed: {error}");
                 false
             }
         }
@@ -437,23 +1276,88 @@ impl Session {
         };
 
         if let Err(error) = speaker_mute.restore() {
-            self.status = format!("Speaker auto-mute restore failed: {error}");
+            self.status = format!("Speaker restore failed: {error}");
+        }
+    }
+
+    /// Only queues the pause; the D-Bus calls run on the `MediaPauser` thread and report
+    /// failures through `WorkerEvent::MediaPauseError`.
+    fn pause_media_for_recording(&mut self) -> bool {
+        if !self.model_config.pause_media() || self.media_pauser.is_paused() {
+            return true;
+        }
+
+        match self.media_pauser.pause() {
+            Ok(()) => true,
+            Err(error) => {
+                self.status = format!("Listening for speech; pausing media failed: {error}");
+                false
+            }
+        }
+    }
+
+    fn resume_media_after_recording(&mut self) {
+        if let Err(error) = self.media_pauser.resume() {
+            self.status = format!("Media resume failed: {error}");
         }
     }
 
     fn start_worker(&mut self) {
-        let (worker_tx, state, status) =
-            start_worker_if_model_is_installed(&self.model_config, self.event_tx.clone());
+        let (worker_tx, state, status) = start_worker_if_model_is_installed(
+            &self.model_config,
+            self.cancel_generation.clone(),
+            self.event_tx.clone(),
+        );
 
-        self.worker_use_gpu = worker_tx.as_ref().map(|_| self.model_config.use_gpu());
+        self.worker_execution_providers = worker_tx
+            .as_ref()
+            .map(|_| self.model_config.execution_providers().to_vec());
+        self.active_provider = None;
+        self.provider_fallback = None;
+        self.optimized_model_cache_status = None;
+        self.in_flight_recordings.clear();
+        self.discarded_results = 0;
+        self.worker_restart_pending = false;
+        self.worker_restart_delay = None;
         self.worker_tx = worker_tx;
         self.worker_ready = false;
         self.state = state;
         self.status = status;
     }
 
-    fn restart_worker_if_gpu_setting_changed(&mut self) {
-        if self.worker_use_gpu == Some(self.model_config.use_gpu()) {
+    fn model_busy_status(&self) -> String {
+        match self.state {
+            State::Downloading => "Wait for the model download to finish".to_string(),
+            State::Importing => "Wait for the model import to finish".to_string(),
+            State::Loading => "Wait for the model to finish loading".to_string(),
+            State::Recording | State::Transcribing => {
+                "Wait for the current transcription to finish".to_string()
+            }
+            State::Unloaded | State::ModelMissing | State::Idle | State::Error => {
+                "STT model is busy".to_string()
+            }
+        }
+    }
+
+    fn model_import_failed(&mut self, message: String) {
+        if validate_model_dir(self.model_config.model_dir()).is_ok() {
+            self.start_worker();
+            self.status = format!(
+                "Model import failed: {message}; keeping installed model; {}",
+                self.status
+            );
+            return;
+        }
+
+        self.state = State::ModelMissing;
+        self.status = format!("Model import failed: {message}");
+    }
+
+    fn restart_worker_if_provider_setting_changed(&mut self) {
+        if self.worker_execution_providers.as_deref()
+            == Some(self.model_config.execution_providers())
+            || !self.in_flight_recordings.is_empty()
+        {
             return;
         }
 
@@ -463,10 +1367,31 @@ impl Session {
     }
 
     fn ready_status(&self) -> String {
-        if self.model_config.use_gpu() {
-            "Ready; GPU inference".to_string()
-        } else {
-            "Ready; CPU inference".to_string()
+        let active_provider = self.active_provider.unwrap_or(ExecutionProvider::Cpu);
+        match &self.provider_fallback {
+            Some(reason) => format!(
+                "Ready; {} inference after fallback ({reason})",
+                active_provider.label()
+            ),
+            None => format!("Ready; {} inference", active_provider.label()),
+        }
+    }
+
+    fn loading_status(&self) -> String {
+        match &self.provider_fallback {
+            Some(reason) => format!("Preferred provider unavailable, loading STT model ({reason})"),
+            None => self.status.clone(),
+        }
+    }
+
+    fn optimized_model_cache_label(&self) -> String {
+        let Some(cache_root) = self.model_config.runtime_options().optimized_model_cache() else {
+            return "off".to_string();
+        };
+
+        match &self.optimized_model_cache_status {
+            Some(status) => format!("on; {status}"),
+            None => format!("on; {}", cache_root.display()),
         }
     }
 
@@ -496,6 +1421,18 @@ impl Session {
         0.0
     }
 
+    fn download_status(&self) -> String {
+        if self.download_attempt_label.is_empty() {
+            return self.download_file_label();
+        }
+
+        format!(
+            "{}; {}",
+            self.download_file_label(),
+            self.download_attempt_label
+        )
+    }
+
     fn download_file_label(&self) -> String {
         if self.state != State::Downloading {
             return format!(
@@ -528,8 +1465,16 @@ fn percent(downloaded_bytes: u64, total_bytes: u64) -> f32 {
     ((downloaded_bytes as f32 / total_bytes as f32) * 100.0).clamp(0.0, 100.0)
 }
 
+fn remove_orphaned_staging(model_config: &ModelConfig) -> SttResult<usize> {
+    let model_dir = model_config.model_dir();
+    let staging_dirs = [staging_dir(model_dir)?, import_extract_dir(model_dir)?];
+
+    remove_orphaned_staging_dirs(&staging_dirs).map(|removed| removed.len())
+}
+
 fn start_worker_if_model_is_installed(
     model_config: &ModelConfig,
+    cancel_generation: Arc<AtomicU64>,
     event_tx: Sender<WorkerEvent>,
 ) -> (Option<Sender<WorkerRequest>>, State, String) {
     let model_dir = model_config.model_dir().to_path_buf();
@@ -546,16 +1491,34 @@ fn start_worker_if_model_is_installed(
         );
     }
 
-    let use_gpu = model_config.use_gpu();
+    let execution_providers = model_config.execution_providers().to_vec();
+    let providers_label = execution_provider_list_label(&execution_providers);
+    let runtime_options = model_config.runtime_options().clone();
+
+    let worker = if model_config.worker_process() {
+        spawn_stt_worker_process(cancel_generation, event_tx)
+    } else {
+        spawn_stt_worker(
+            model_dir.clone(),
+            execution_providers,
+            runtime_options,
+            cancel_generation,
+            event_tx,
+        )
+    };
 
-    match spawn_stt_worker(model_dir.clone(), use_gpu, event_tx) {
+    match worker {
         Ok(worker_tx) => (
             Some(worker_tx),
             State::Loading,
             format!(
-                "Loading STT model from {} ({})",
+                "Loading STT model from {} ({providers_label}{})",
                 model_dir.display(),
-                if use_gpu { "GPU" } else { "CPU" },
+                if model_config.worker_process() {
+                    "; worker process"
+                } else {
+                    ""
+                },
             ),
         ),
         Err(error) => (None, State::Error, error.to_string()),
@@ -0,0 +1,65 @@
+/// Per-channel sink volume in PulseAudio units, where `SinkVolume::NORM` is 100 %.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct SinkVolume {
+    channels: Vec<u32>,
+}
+
+impl SinkVolume {
+    pub(crate) const NORM: u32 = 0x10000;
+    /// Sound servers may round a volume they were given, such as PipeWire storing it as a cubic
+    /// float, so a read-back is accepted within half a percent.
+    pub(crate) const READ_BACK_TOLERANCE: u32 = Self::NORM / 200;
+
+    pub(crate) fn new(channels: Vec<u32>) -> Self {
+        Self { channels }
+    }
+
+    pub(crate) fn channels(&self) -> &[u32] {
+        &self.channels
+    }
+
+    /// Same channel count, and every channel within `tolerance` of the other volume.
+    pub(crate) fn is_close_to(&self, other: &Self, tolerance: u32) -> bool {
+        self.channels.len() == other.channels.len()
+            && self
+                .channels
+                .iter()
+                .zip(&other.channels)
+                .all(|(&volume, &other)| volume.abs_diff(other) <= tolerance)
+    }
+
+    /// Lowers every channel to at most `percent` of full volume; quieter channels keep their
+    /// level so ducking never makes anything louder.
+    pub(crate) fn ducked(&self, percent: u8) -> Self {
+        let limit =
+            u32::try_from(u64::from(Self::NORM) * u64::from(percent) / 100).unwrap_or(u32::MAX);
+        Self {
+            channels: self
+                .channels
+                .iter()
+                .map(|&volume| volume.min(limit))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_each_channel_within_the_tolerance() {
+        let volume = SinkVolume::new(vec![0x8000, 0x4000]);
+        let tolerance = SinkVolume::READ_BACK_TOLERANCE;
+
+        assert!(volume.is_close_to(
+            &SinkVolume::new(vec![0x8000 + tolerance, 0x4000 - 1]),
+            tolerance
+        ));
+        assert!(!volume.is_close_to(
+            &SinkVolume::new(vec![0x8000, 0x4000 + tolerance + 1]),
+            tolerance
+        ));
+        assert!(!volume.is_close_to(&SinkVolume::new(vec![0x8000]), tolerance));
+    }
+}
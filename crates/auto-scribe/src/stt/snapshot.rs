@@ -1,3 +1,5 @@
+use crate::stt::{ExecutionProvider, SpeakerMode};
+
 #[derive(Clone, Debug)]
 pub(crate) struct Snapshot {
     pub(crate) state_label: &'static str,
@@ -7,13 +9,24 @@ pub(crate) struct Snapshot {
     pub(crate) status: String,
     pub(crate) model_can_download: bool,
     pub(crate) model_downloading: bool,
+    pub(crate) model_importing: bool,
     pub(crate) model_download_files_percent: f32,
     pub(crate) model_download_files_label: String,
     pub(crate) model_download_file_percent: f32,
     pub(crate) model_download_file_known: bool,
     pub(crate) model_download_file_label: String,
+    pub(crate) model_unloaded: bool,
+    pub(crate) worker_restarting: bool,
     pub(crate) model_dir: String,
     pub(crate) config_path: String,
-    pub(crate) use_gpu: bool,
-    pub(crate) auto_mute_speakers: bool,
+    pub(crate) enabled_providers: Vec<ExecutionProvider>,
+    pub(crate) execution_providers: String,
+    pub(crate) active_provider: Option<&'static str>,
+    pub(crate) provider_fallback: Option<String>,
+    pub(crate) onnx_threads: String,
+    pub(crate) graph_optimization: &'static str,
+    pub(crate) optimized_model_cache: String,
+    pub(crate) speaker_mode: SpeakerMode,
+    pub(crate) pause_media: bool,
+    pub(crate) meeting_label: Option<String>,
 }
@@ -0,0 +1,79 @@
+use crate::stt::{SpeakerTurn, Transcript, TranscriptSegment, TranscriptWord};
+
+/// Labels every word with the speaker whose turn overlaps it most, or the nearest turn when
+/// none does, and splits segments where the speaker changes. Speakers are renumbered in the
+/// order they first speak and named "Speaker 1", "Speaker 2" and so on.
+pub(crate) fn assign_speakers(transcript: Transcript, turns: &[SpeakerTurn]) -> Transcript {
+    if turns.is_empty() {
+        return transcript;
+    }
+
+    let mut model_speakers = Vec::new();
+    let mut segments = Vec::with_capacity(transcript.segments.len());
+    for segment in transcript.segments {
+        if segment.words.is_empty() {
+            segments.push(segment);
+            continue;
+        }
+
+        let split_start = segments.len();
+        for word in segment.words {
+            let speaker = word_speaker(&word, turns).map(|model_speaker| {
+                model_speakers
+                    .iter()
+                    .position(|&known| known == model_speaker)
+                    .unwrap_or_else(|| {
+                        model_speakers.push(model_speaker);
+                        model_speakers.len() - 1
+                    })
+            });
+
+            let continues_run = segments.len() > split_start;
+            match segments.last_mut() {
+                Some(current) if continues_run && current.speaker == speaker => {
+                    current.end = word.end;
+                    current.words.push(word);
+                }
+                _ => segments.push(TranscriptSegment {
+                    start: word.start,
+                    end: word.end,
+                    words: vec![word],
+                    speaker,
+                }),
+            }
+        }
+
+        // The split pieces keep the original segment's outer bounds.
+        if let Some(first) = segments.get_mut(split_start) {
+            first.start = segment.start;
+        }
+        if let Some(last) = segments.last_mut() {
+            last.end = segment.end;
+        }
+    }
+
+    Transcript {
+        segments,
+        speakers: (1..=model_speakers.len())
+            .map(|number| format!("Speaker {number}"))
+            .collect(),
+    }
+}
+
+fn word_speaker(word: &TranscriptWord, turns: &[SpeakerTurn]) -> Option<usize> {
+    let overlapping = turns
+        .iter()
+        .map(|turn| (turn.overlap(word.start, word.end), turn.speaker))
+        .filter(|(overlap, _)| !overlap.is_zero())
+        .max_by_key(|(overlap, _)| *overlap)
+        .map(|(_, speaker)| speaker);
+    if overlapping.is_some() {
+        return overlapping;
+    }
+
+    let middle = word.start + word.end.saturating_sub(word.start) / 2;
+    turns
+        .iter()
+        .min_by_key(|turn| turn.distance(middle))
+        .map(|turn| turn.speaker)
+}
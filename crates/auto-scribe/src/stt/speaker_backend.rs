@@ -0,0 +1,13 @@
+use crate::stt::{SinkVolume, SttResult};
+
+/// Controls output sinks for `SpeakerMuteGuard`. Sinks are addressed by the name or id the
+/// backend resolved for the default sink, so a restore reaches the sink that was muted even
+/// if the default changed in between.
+pub(crate) trait SpeakerBackend {
+    fn name(&self) -> &'static str;
+    fn default_sink(&mut self) -> SttResult<String>;
+    fn is_muted(&mut self, sink: &str) -> SttResult<bool>;
+    fn set_muted(&mut self, sink: &str, muted: bool) -> SttResult<()>;
+    fn volume(&mut self, sink: &str) -> SttResult<SinkVolume>;
+    fn set_volume(&mut self, sink: &str, volume: &SinkVolume) -> SttResult<()>;
+}
@@ -0,0 +1,34 @@
+use crate::stt::{SttError, SttResult};
+
+use std::process::Command;
+
+/// Runs a sound server command-line tool with untranslated output and returns its trimmed
+/// stdout.
+pub(crate) fn command_output(program: &str, args: &[&str]) -> SttResult<String> {
+    let output = Command::new(program)
+        .args(args)
+        .env("LC_ALL", "C")
+        .output()
+        .map_err(|error| SttError::speaker_mute(format!("{program}: {error}")))?;
+
+    if !output.status.success() {
+        return Err(command_error(program, output.status.code(), &output.stderr));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn command_error(program: &str, code: Option<i32>, stderr: &[u8]) -> SttError {
+    let stderr = String::from_utf8_lossy(stderr).trim().to_string();
+    let detail = if stderr.is_empty() {
+        "no stderr".to_string()
+    } else {
+        stderr
+    };
+
+    SttError::speaker_mute(format!(
+        "{program} failed with status {}; {detail}",
+        code.map(|code| code.to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    ))
+}
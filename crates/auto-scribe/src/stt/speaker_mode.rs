@@ -0,0 +1,34 @@
+/// What happens to the default speaker sink while recording, from `[audio].speaker_mode`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum SpeakerMode {
+    Off,
+    Mute,
+    /// Lower the sink to `[audio].duck_volume_percent`.
+    Duck,
+}
+
+impl SpeakerMode {
+    pub(crate) const ALL: [Self; 3] = [Self::Off, Self::Mute, Self::Duck];
+
+    pub(crate) fn from_config_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.config_name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub(crate) fn config_name(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Mute => "mute",
+            Self::Duck => "duck",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Mute => "Mute",
+            Self::Duck => "Duck",
+        }
+    }
+}
@@ -1,56 +1,195 @@
-use crate::stt::{SttError, SttResult};
+#[cfg(target_os = "linux")]
+use crate::stt::PulseSpeakerBackend;
+use crate::stt::{
+    PactlSpeakerBackend, SpeakerBackend, SpeakerRestore, SpeakerRestoreState, SttError, SttResult,
+    WpctlSpeakerBackend,
+};
 
-use std::process::Command;
-
-#[derive(Debug)]
+/// Mutes or ducks one sink and puts back its previous mute state or exact volume on
+/// `restore` or drop. While armed, the restore intent is also kept in a
+/// `SpeakerRestoreState` file so a later start can undo it after a crash.
 pub(crate) struct SpeakerMuteGuard {
-    backend: SpeakerMuteBackend,
-    restore_muted: bool,
-    active: bool,
-}
-
-#[derive(Clone, Copy, Debug)]
-enum SpeakerMuteBackend {
-    Wpctl,
-    Pactl,
+    backend: Box<dyn SpeakerBackend>,
+    sink: String,
+    /// `None` when the sink needed no change, or once it has been restored.
+    restore: Option<SpeakerRestore>,
 }
 
 impl SpeakerMuteGuard {
     pub(crate) fn mute_default_sink() -> SttResult<Self> {
-        let mut errors = Vec::new();
+        Self::with_first_working_backend(Self::mute_with_backend)
+    }
+
+    pub(crate) fn duck_default_sink(percent: u8) -> SttResult<Self> {
+        Self::with_first_working_backend(|backend| Self::duck_with_backend(backend, percent))
+    }
+
+    /// Mutes the backend's default sink, remembering whether it was already muted.
+    pub(crate) fn mute_with_backend(mut backend: Box<dyn SpeakerBackend>) -> SttResult<Self> {
+        let sink = backend.default_sink()?;
+        let restore = if backend.is_muted(&sink)? {
+            None
+        } else {
+            backend.set_muted(&sink, true)?;
+            Some(SpeakerRestore::Unmute)
+        };
+
+        Ok(Self::armed(backend, sink, restore))
+    }
+
+    /// Lowers the backend's default sink to `percent` of full volume, remembering the exact
+    /// volume of every channel.
+    pub(crate) fn duck_with_backend(
+        mut backend: Box<dyn SpeakerBackend>,
+        percent: u8,
+    ) -> SttResult<Self> {
+        let sink = backend.default_sink()?;
+        let volume = backend.volume(&sink)?;
+        let ducked = volume.ducked(percent);
+        let restore = if ducked == volume {
+            None
+        } else {
+            backend.set_volume(&sink, &ducked)?;
+            Some(SpeakerRestore::Volume(volume))
+        };
+
+        Ok(Self::armed(backend, sink, restore))
+    }
 
-        match Self::mute_with_backend(SpeakerMuteBackend::Wpctl) {
-            Ok(guard) => return Ok(guard),
-            Err(error) => errors.push(error.to_string()),
+    /// Puts back the sinks left muted or ducked by instances that exited without restoring
+    /// them, and returns a description of what was recovered.
+    pub(crate) fn restore_stale_state() -> SttResult<Option<String>> {
+        let mut recovered = Vec::new();
+        let mut errors = Vec::new();
+        for state in SpeakerRestoreState::take_stale() {
+            match state.and_then(|state| Self::restore_stale(&state)) {
+                Ok(description) => recovered.push(description),
+                Err(error) => errors.push(error.to_string()),
+            }
         }
 
-        match Self::mute_with_backend(SpeakerMuteBackend::Pactl) {
-            Ok(guard) => return Ok(guard),
-            Err(error) => errors.push(error.to_string()),
+        if !errors.is_empty() {
+            return Err(SttError::speaker_mute(errors.join("; ")));
         }
+        Ok((!recovered.is_empty()).then(|| recovered.join(" and ")))
+    }
 
-        Err(SttError::speaker_mute(errors.join("; ")))
+    /// One attempt only: the state file is already gone, so a sink that no longer exists
+    /// does not fail every later start.
+    fn restore_stale(state: &SpeakerRestoreState) -> SttResult<String> {
+        let mut guard = Self {
+            backend: backend_named(state.backend())?,
+            sink: state.sink().to_string(),
+            restore: Some(state.restore().clone()),
+        };
+        let result = guard.restore();
+        guard.restore = None;
+        result?;
+
+        Ok(match state.restore() {
+            SpeakerRestore::Unmute => format!("unmuted {}", state.sink()),
+            SpeakerRestore::Volume(_) => format!("restored the volume of {}", state.sink()),
+        })
     }
 
+    /// On failure the guard stays armed, so dropping it tries once more.
     pub(crate) fn restore(&mut self) -> SttResult<()> {
-        if !self.active {
+        let Some(restore) = &self.restore else {
             return Ok(());
-        }
+        };
 
-        set_muted(self.backend, self.restore_muted)?;
-        self.active = false;
-        Ok(())
+        match restore {
+            SpeakerRestore::Unmute => self.backend.set_muted(&self.sink, false)?,
+            SpeakerRestore::Volume(volume) => self.backend.set_volume(&self.sink, volume)?,
+        }
+        self.restore = None;
+        clear_restore_state()
     }
 
-    fn mute_with_backend(backend: SpeakerMuteBackend) -> SttResult<Self> {
-        let restore_muted = is_muted(backend)?;
-        set_muted(backend, true)?;
+    fn armed(
+        backend: Box<dyn SpeakerBackend>,
+        sink: String,
+        restore: Option<SpeakerRestore>,
+    ) -> Self {
+        if let Some(restore) = &restore {
+            // Losing crash recovery must not stop the recording; the guard still restores
+            // on release, error, shutdown and drop.
+            let _ = save_restore_state(&SpeakerRestoreState::new(
+                backend.name(),
+                &sink,
+                restore.clone(),
+            ));
+        }
 
-        Ok(Self {
+        Self {
             backend,
-            restore_muted,
-            active: true,
-        })
+            sink,
+            restore,
+        }
+    }
+
+    /// Tries the native PulseAudio client first and falls back to `wpctl`, then `pactl`.
+    fn with_first_working_backend(
+        engage: impl Fn(Box<dyn SpeakerBackend>) -> SttResult<Self>,
+    ) -> SttResult<Self> {
+        let candidates: Vec<(&'static str, SttResult<Box<dyn SpeakerBackend>>)> = vec![
+            #[cfg(target_os = "linux")]
+            (
+                "PulseAudio",
+                PulseSpeakerBackend::connect().map(|backend| Box::new(backend) as _),
+            ),
+            ("wpctl", Ok(Box::new(WpctlSpeakerBackend))),
+            ("pactl", Ok(Box::new(PactlSpeakerBackend))),
+        ];
+
+        Self::engage_first(candidates, engage)
+    }
+
+    /// Returns the guard of the first candidate that connects and engages, or every
+    /// candidate's error in order.
+    fn engage_first(
+        candidates: Vec<(&'static str, SttResult<Box<dyn SpeakerBackend>>)>,
+        engage: impl Fn(Box<dyn SpeakerBackend>) -> SttResult<Self>,
+    ) -> SttResult<Self> {
+        let mut errors = Vec::new();
+        for (name, backend) in candidates {
+            match backend.and_then(&engage) {
+                Ok(guard) => return Ok(guard),
+                Err(error) => errors.push(format!("{name}: {error}")),
+            }
+        }
+
+        Err(SttError::speaker_mute(errors.join("; ")))
+    }
+}
+
+/// Unit tests drive the guard with fake backends and must not touch the real state file.
+fn save_restore_state(state: &SpeakerRestoreState) -> SttResult<()> {
+    if cfg!(test) {
+        return Ok(());
+    }
+    state.save()
+}
+
+fn clear_restore_state() -> SttResult<()> {
+    if cfg!(test) {
+        return Ok(());
+    }
+    SpeakerRestoreState::clear()
+}
+
+fn backend_named(name: &str) -> SttResult<Box<dyn SpeakerBackend>> {
+    #[cfg(target_os = "linux")]
+    if name == "PulseAudio" {
+        return Ok(Box::new(PulseSpeakerBackend::connect()?));
+    }
+
+    match name {
+        "wpctl" => Ok(Box::new(WpctlSpeakerBackend)),
+        "pactl" => Ok(Box::new(PactlSpeakerBackend)),
+        _ => Err(SttError::speaker_mute(format!(
+            "unknown speaker backend {name:?}"
+        ))),
     }
 }
 
@@ -60,73 +199,189 @@ impl Drop for SpeakerMuteGuard {
     }
 }
 
-fn is_muted(backend: SpeakerMuteBackend) -> SttResult<bool> {
-    match backend {
-        SpeakerMuteBackend::Wpctl => {
-            let output = command_output("wpctl", ["get-volume", "@DEFAULT_AUDIO_SINK@"])?;
-            Ok(output.contains("[MUTED]"))
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stt::SinkVolume;
+
+    use std::{cell::RefCell, rc::Rc};
+
+    const SINK: &str = "alsa_output.fake";
+
+    /// What a sound server would hold for the default sink, shared with the test after the
+    /// backend has moved into the guard.
+    #[derive(Default)]
+    struct FakeSink {
+        muted: bool,
+        volume: Vec<u32>,
+        failing_writes: usize,
+        writes: Vec<String>,
+    }
+
+    struct FakeBackend {
+        name: &'static str,
+        sink: Rc<RefCell<FakeSink>>,
+    }
+
+    impl FakeBackend {
+        fn boxed(sink: &Rc<RefCell<FakeSink>>) -> Box<dyn SpeakerBackend> {
+            Box::new(Self {
+                name: "fake",
+                sink: sink.clone(),
+            })
         }
-        SpeakerMuteBackend::Pactl => {
-            let output = command_output("pactl", ["get-sink-mute", "@DEFAULT_SINK@"])?;
-            let value = output
-                .split_once(':')
-                .map(|(_, value)| value.trim())
-                .unwrap_or(output.trim());
-            Ok(value.eq_ignore_ascii_case("yes"))
+
+        fn write(&self, change: String) -> SttResult<()> {
+            let mut sink = self.sink.borrow_mut();
+            if sink.failing_writes > 0 {
+                sink.failing_writes -= 1;
+                return Err(SttError::speaker_mute("sink is gone"));
+            }
+            sink.writes.push(change);
+            Ok(())
         }
     }
-}
 
-fn set_muted(backend: SpeakerMuteBackend, muted: bool) -> SttResult<()> {
-    let muted_arg = if muted { "1" } else { "0" };
+    impl SpeakerBackend for FakeBackend {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn default_sink(&mut self) -> SttResult<String> {
+            Ok(SINK.to_string())
+        }
+
+        fn is_muted(&mut self, _sink: &str) -> SttResult<bool> {
+            Ok(self.sink.borrow().muted)
+        }
+
+        fn set_muted(&mut self, _sink: &str, muted: bool) -> SttResult<()> {
+            self.write(format!("muted={muted}"))?;
+            self.sink.borrow_mut().muted = muted;
+            Ok(())
+        }
 
-    match backend {
-        SpeakerMuteBackend::Wpctl => {
-            run_command("wpctl", ["set-mute", "@DEFAULT_AUDIO_SINK@", muted_arg])
+        fn volume(&mut self, _sink: &str) -> SttResult<SinkVolume> {
+            Ok(SinkVolume::new(self.sink.borrow().volume.clone()))
         }
-        SpeakerMuteBackend::Pactl => {
-            run_command("pactl", ["set-sink-mute", "@DEFAULT_SINK@", muted_arg])
+
+        fn set_volume(&mut self, _sink: &str, volume: &SinkVolume) -> SttResult<()> {
+            self.write(format!("volume={:?}", volume.channels()))?;
+            self.sink.borrow_mut().volume = volume.channels().to_vec();
+            Ok(())
         }
     }
-}
 
-fn command_output<const N: usize>(program: &str, args: [&str; N]) -> SttResult<String> {
-    let output = Command::new(program)
-        .args(args)
-        .output()
-        .map_err(|error| SttError::speaker_mute(format!("{program}: {error}")))?;
+    fn fake_sink(muted: bool, volume: &[u32]) -> Rc<RefCell<FakeSink>> {
+        Rc::new(RefCell::new(FakeSink {
+            muted,
+            volume: volume.to_vec(),
+            ..FakeSink::default()
+        }))
+    }
+
+    #[test]
+    fn mutes_and_unmutes_on_restore() -> SttResult<()> {
+        let sink = fake_sink(false, &[SinkVolume::NORM]);
+
+        let mut guard = SpeakerMuteGuard::mute_with_backend(FakeBackend::boxed(&sink))?;
+        assert!(sink.borrow().muted);
+        guard.restore()?;
 
-    if !output.status.success() {
-        return Err(command_error(program, output.status.code(), &output.stderr));
+        assert!(!sink.borrow().muted);
+        assert_eq!(sink.borrow().writes, ["muted=true", "muted=false"]);
+        Ok(())
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
-}
+    #[test]
+    fn already_muted_sink_stays_muted() -> SttResult<()> {
+        let sink = fake_sink(true, &[SinkVolume::NORM]);
 
-fn run_command<const N: usize>(program: &str, args: [&str; N]) -> SttResult<()> {
-    let output = Command::new(program)
-        .args(args)
-        .output()
-        .map_err(|error| SttError::speaker_mute(format!("{program}: {error}")))?;
+        let mut guard = SpeakerMuteGuard::mute_with_backend(FakeBackend::boxed(&sink))?;
+        guard.restore()?;
+        drop(guard);
 
-    if output.status.success() {
-        return Ok(());
+        assert!(sink.borrow().muted);
+        assert!(sink.borrow().writes.is_empty());
+        Ok(())
     }
 
-    Err(command_error(program, output.status.code(), &output.stderr))
-}
+    #[test]
+    fn failed_restore_stays_armed_and_drop_retries() -> SttResult<()> {
+        let sink = fake_sink(false, &[SinkVolume::NORM]);
+        let mut guard = SpeakerMuteGuard::mute_with_backend(FakeBackend::boxed(&sink))?;
+
+        sink.borrow_mut().failing_writes = 1;
+        assert!(guard.restore().is_err());
+        assert!(sink.borrow().muted);
+
+        drop(guard);
+        assert!(!sink.borrow().muted);
+        Ok(())
+    }
+
+    #[test]
+    fn duck_restores_the_exact_channel_volumes() -> SttResult<()> {
+        let original = [SinkVolume::NORM * 3 / 4, 0x3000];
+        let sink = fake_sink(false, &original);
+
+        let mut guard = SpeakerMuteGuard::duck_with_backend(FakeBackend::boxed(&sink), 20)?;
+        assert_eq!(sink.borrow().volume, [SinkVolume::NORM / 5, 0x3000]);
+        guard.restore()?;
+
+        assert_eq!(sink.borrow().volume, original);
+        Ok(())
+    }
+
+    #[test]
+    fn duck_leaves_a_quiet_sink_alone() -> SttResult<()> {
+        let sink = fake_sink(false, &[0x1000, 0x1000]);
+
+        let mut guard = SpeakerMuteGuard::duck_with_backend(FakeBackend::boxed(&sink), 20)?;
+        guard.restore()?;
 
-fn command_error(program: &str, code: Option<i32>, stderr: &[u8]) -> SttError {
-    let stderr = String::from_utf8_lossy(stderr).trim().to_string();
-    let detail = if stderr.is_empty() {
-        "no stderr".to_string()
-    } else {
-        stderr
-    };
-
-    SttError::speaker_mute(format!(
-        "{program} failed with status {}; {detail}",
-        code.map(|code| code.to_string())
-            .unwrap_or_else(|| "unknown".to_string())
-    ))
+        assert!(sink.borrow().writes.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn falls_back_to_the_next_backend_in_order() -> SttResult<()> {
+        let broken = fake_sink(false, &[SinkVolume::NORM]);
+        broken.borrow_mut().failing_writes = 1;
+        let working = fake_sink(false, &[SinkVolume::NORM]);
+        let unused = fake_sink(false, &[SinkVolume::NORM]);
+        let candidates = vec![
+            ("first", Err(SttError::speaker_mute("no server"))),
+            ("second", Ok(FakeBackend::boxed(&broken))),
+            ("third", Ok(FakeBackend::boxed(&working))),
+            ("fourth", Ok(FakeBackend::boxed(&unused))),
+        ];
+
+        let guard =
+            SpeakerMuteGuard::engage_first(candidates, SpeakerMuteGuard::mute_with_backend)?;
+
+        assert!(!broken.borrow().muted);
+        assert!(working.borrow().muted);
+        assert!(!unused.borrow().muted);
+        drop(guard);
+        assert!(!working.borrow().muted);
+        Ok(())
+    }
+
+    #[test]
+    fn reports_every_backend_error_in_order() {
+        let candidates = vec![
+            ("first", Err(SttError::speaker_mute("no server"))),
+            ("second", Err(SttError::speaker_mute("not installed"))),
+        ];
+
+        let error = SpeakerMuteGuard::engage_first(candidates, SpeakerMuteGuard::mute_with_backend)
+            .err()
+            .map(|error| error.to_string())
+            .unwrap_or_default();
+
+        let first = error.find("first: ");
+        let second = error.find("second: ");
+        assert!(first.is_some() && first < second, "{error}");
+    }
 }
@@ -0,0 +1,8 @@
+use crate::stt::SinkVolume;
+
+/// What `SpeakerMuteGuard` puts back on the sink it changed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum SpeakerRestore {
+    Unmute,
+    Volume(SinkVolume),
+}
@@ -0,0 +1,322 @@
+use crate::stt::{SinkVolume, SpeakerRestore, SttError, SttResult};
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+/// Each instance keeps its own `speaker-restore-<pid>.toml`, so two running instances never
+/// overwrite each other's restore intent.
+const STATE_FILE_PREFIX: &str = "speaker-restore-";
+const STATE_FILE_EXTENSION: &str = "toml";
+
+/// The restore intent of an armed `SpeakerMuteGuard`, written to `$XDG_RUNTIME_DIR` so the
+/// next start can put the sink back if this process dies before the guard restores it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct SpeakerRestoreState {
+    pid: u32,
+    backend: String,
+    sink: String,
+    restore: SpeakerRestore,
+}
+
+impl SpeakerRestoreState {
+    pub(crate) fn new(backend: &str, sink: &str, restore: SpeakerRestore) -> Self {
+        Self {
+            pid: process::id(),
+            backend: backend.to_string(),
+            sink: sink.to_string(),
+            restore,
+        }
+    }
+
+    pub(crate) fn backend(&self) -> &str {
+        &self.backend
+    }
+
+    pub(crate) fn sink(&self) -> &str {
+        &self.sink
+    }
+
+    pub(crate) fn restore(&self) -> &SpeakerRestore {
+        &self.restore
+    }
+
+    /// Writes through a temporary file so a crash mid-write never leaves a torn state file.
+    pub(crate) fn save(&self) -> SttResult<()> {
+        let dir = state_dir().ok_or_else(|| {
+            SttError::speaker_mute("XDG_RUNTIME_DIR is not set; cannot keep speaker restore state")
+        })?;
+        self.save_in(&dir)
+    }
+
+    /// Removes this process's state file.
+    pub(crate) fn clear() -> SttResult<()> {
+        let Some(dir) = state_dir() else {
+            return Ok(());
+        };
+        let path = dir.join(state_file_name(process::id()));
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(SttError::speaker_mute(format!(
+                "remove {}: {error}",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Reads and removes the state files of instances that are no longer running. A file that
+    /// cannot be read comes back as its error; it is removed too, so it fails only one start.
+    /// Without `$XDG_RUNTIME_DIR` nothing can have been saved, so there is nothing to take.
+    pub(crate) fn take_stale() -> Vec<SttResult<Self>> {
+        match state_dir() {
+            Some(dir) => take_stale_in(&dir, is_running),
+            None => Vec::new(),
+        }
+    }
+
+    fn save_in(&self, dir: &Path) -> SttResult<()> {
+        fs::create_dir_all(dir).map_err(|error| {
+            SttError::speaker_mute(format!("create {}: {error}", dir.display()))
+        })?;
+
+        let path = dir.join(state_file_name(self.pid));
+        let text = toml::to_string(&self.to_table()).map_err(|error| {
+            SttError::speaker_mute(format!("serialize {}: {error}", path.display()))
+        })?;
+        let partial_path = path.with_extension("toml.partial");
+        fs::write(&partial_path, text).map_err(|error| {
+            SttError::speaker_mute(format!("write {}: {error}", partial_path.display()))
+        })?;
+        fs::rename(&partial_path, &path)
+            .map_err(|error| SttError::speaker_mute(format!("write {}: {error}", path.display())))
+    }
+
+    fn read(path: &Path) -> SttResult<Self> {
+        let text = fs::read_to_string(path)
+            .map_err(|error| SttError::speaker_mute(format!("read {}: {error}", path.display())))?;
+        let table = text.parse::<toml::Table>().map_err(|error| {
+            SttError::speaker_mute(format!("parse {}: {error}", path.display()))
+        })?;
+        Self::from_table(&table)
+            .ok_or_else(|| SttError::speaker_mute(format!("{} is incomplete", path.display())))
+    }
+
+    fn to_table(&self) -> toml::Table {
+        let mut table = toml::Table::new();
+        table.insert("pid".to_string(), toml::Value::Integer(i64::from(self.pid)));
+        table.insert(
+            "backend".to_string(),
+            toml::Value::String(self.backend.clone()),
+        );
+        table.insert("sink".to_string(), toml::Value::String(self.sink.clone()));
+        match &self.restore {
+            SpeakerRestore::Unmute => {
+                table.insert(
+                    "restore".to_string(),
+                    toml::Value::String("unmute".to_string()),
+                );
+            }
+            SpeakerRestore::Volume(volume) => {
+                table.insert(
+                    "restore".to_string(),
+                    toml::Value::String("volume".to_string()),
+                );
+                table.insert(
+                    "volume".to_string(),
+                    toml::Value::Array(
+                        volume
+                            .channels()
+                            .iter()
+                            .map(|&channel| toml::Value::Integer(i64::from(channel)))
+                            .collect(),
+                    ),
+                );
+            }
+        }
+        table
+    }
+
+    fn from_table(table: &toml::Table) -> Option<Self> {
+        let pid = table
+            .get("pid")
+            .and_then(toml::Value::as_integer)
+            .and_then(|pid| u32::try_from(pid).ok())?;
+        let backend = table.get("backend").and_then(toml::Value::as_str)?;
+        let sink = table.get("sink").and_then(toml::Value::as_str)?;
+        let restore = match table.get("restore").and_then(toml::Value::as_str)? {
+            "unmute" => SpeakerRestore::Unmute,
+            "volume" => SpeakerRestore::Volume(SinkVolume::new(
+                table
+                    .get("volume")
+                    .and_then(toml::Value::as_array)?
+                    .iter()
+                    .map(|channel| {
+                        channel
+                            .as_integer()
+                            .and_then(|channel| u32::try_from(channel).ok())
+                    })
+                    .collect::<Option<Vec<_>>>()?,
+            )),
+            _ => return None,
+        };
+
+        Some(Self {
+            pid,
+            backend: backend.to_string(),
+            sink: sink.to_string(),
+            restore,
+        })
+    }
+}
+
+fn take_stale_in(
+    dir: &Path,
+    is_running: impl Fn(u32) -> bool,
+) -> Vec<SttResult<SpeakerRestoreState>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Vec::new(),
+        Err(error) => {
+            return vec![Err(SttError::speaker_mute(format!(
+                "read {}: {error}",
+                dir.display()
+            )))];
+        }
+    };
+
+    let mut stale_paths = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| state_file_pid(path).is_some_and(|pid| !is_running(pid)))
+        .collect::<Vec<_>>();
+    stale_paths.sort();
+
+    stale_paths
+        .into_iter()
+        .map(|path| {
+            let state = SpeakerRestoreState::read(&path);
+            fs::remove_file(&path).map_err(|error| {
+                SttError::speaker_mute(format!("remove {}: {error}", path.display()))
+            })?;
+            state
+        })
+        .collect()
+}
+
+fn state_dir() -> Option<PathBuf> {
+    env::var_os("XDG_RUNTIME_DIR")
+        .filter(|runtime_dir| !runtime_dir.is_empty())
+        .map(|runtime_dir| PathBuf::from(runtime_dir).join("auto-scribe"))
+}
+
+fn state_file_name(pid: u32) -> String {
+    format!("{STATE_FILE_PREFIX}{pid}.{STATE_FILE_EXTENSION}")
+}
+
+fn state_file_pid(path: &Path) -> Option<u32> {
+    if path.extension()? != STATE_FILE_EXTENSION {
+        return None;
+    }
+    path.file_stem()?
+        .to_str()?
+        .strip_prefix(STATE_FILE_PREFIX)?
+        .parse()
+        .ok()
+}
+
+/// A pid only counts as the writer while it runs a program with this process's name; after
+/// a crash the pid may have been reused by something else.
+fn is_running(pid: u32) -> bool {
+    pid == process::id()
+        || process_name("self").is_some_and(|name| process_name(&pid.to_string()) == Some(name))
+}
+
+fn process_name(pid: &str) -> Option<String> {
+    fs::read_to_string(Path::new("/proc").join(pid).join("comm"))
+        .ok()
+        .map(|name| name.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(pid: u32, restore: SpeakerRestore) -> SpeakerRestoreState {
+        SpeakerRestoreState {
+            pid,
+            backend: "PulseAudio".to_string(),
+            sink: "alsa_output.pci-0000_00_1f.3.analog-stereo".to_string(),
+            restore,
+        }
+    }
+
+    fn temp_dir(name: &str) -> SttResult<PathBuf> {
+        let dir = env::temp_dir().join(format!("auto-scribe-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).map_err(|error| SttError::speaker_mute(error.to_string()))?;
+        Ok(dir)
+    }
+
+    #[test]
+    fn states_round_trip_through_the_table() {
+        for restore in [
+            SpeakerRestore::Unmute,
+            SpeakerRestore::Volume(SinkVolume::new(vec![0x8000, 0x4000])),
+        ] {
+            let state = state(4242, restore);
+            assert_eq!(
+                SpeakerRestoreState::from_table(&state.to_table()),
+                Some(state)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_incomplete_tables() {
+        let complete = state(4242, SpeakerRestore::Volume(SinkVolume::new(vec![0x8000])));
+        for key in ["pid", "backend", "sink", "restore", "volume"] {
+            let mut table = complete.to_table();
+            table.remove(key);
+            assert_eq!(SpeakerRestoreState::from_table(&table), None, "{key}");
+        }
+
+        let mut table = complete.to_table();
+        table.insert(
+            "restore".to_string(),
+            toml::Value::String("mute".to_string()),
+        );
+        assert_eq!(SpeakerRestoreState::from_table(&table), None);
+
+        let mut table = complete.to_table();
+        table.insert("pid".to_string(), toml::Value::Integer(-1));
+        assert_eq!(SpeakerRestoreState::from_table(&table), None);
+    }
+
+    #[test]
+    fn takes_only_the_files_of_instances_that_are_gone() -> SttResult<()> {
+        let dir = temp_dir("speaker-restore")?;
+        let running = state(100, SpeakerRestore::Unmute);
+        let gone = state(200, SpeakerRestore::Volume(SinkVolume::new(vec![0x8000])));
+        running.save_in(&dir)?;
+        gone.save_in(&dir)?;
+        let incomplete = dir.join(state_file_name(300));
+        let unrelated = dir.join("speaker-restore.toml");
+        for path in [&incomplete, &unrelated] {
+            fs::write(path, "backend = \"wpctl\"\n")
+                .map_err(|error| SttError::speaker_mute(error.to_string()))?;
+        }
+
+        let taken = take_stale_in(&dir, |pid| pid == 100);
+
+        assert_eq!(taken.len(), 2);
+        assert!(matches!(&taken[0], Ok(state) if *state == gone));
+        assert!(taken[1].is_err());
+        assert!(dir.join(state_file_name(100)).is_file() && unrelated.is_file());
+        assert!(!dir.join(state_file_name(200)).exists() && !incomplete.exists());
+        assert!(take_stale_in(&dir, |pid| pid == 100).is_empty());
+        fs::remove_dir_all(&dir).map_err(|error| SttError::speaker_mute(error.to_string()))
+    }
+}
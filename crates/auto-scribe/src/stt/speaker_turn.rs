@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+/// A stretch of audio the diarization model attributes to one speaker. Speaker numbers are
+/// the model's output slots, not the order in which people first speak.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct SpeakerTurn {
+    pub(crate) speaker: usize,
+    pub(crate) start: Duration,
+    pub(crate) end: Duration,
+}
+
+impl SpeakerTurn {
+    pub(crate) fn overlap(&self, start: Duration, end: Duration) -> Duration {
+        self.end.min(end).saturating_sub(self.start.max(start))
+    }
+
+    /// Zero when `time` falls inside the turn.
+    pub(crate) fn distance(&self, time: Duration) -> Duration {
+        self.start
+            .saturating_sub(time)
+            .max(time.saturating_sub(self.end))
+    }
+}
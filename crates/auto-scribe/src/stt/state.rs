@@ -1,8 +1,10 @@
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub(crate) enum State {
     Loading,
+    Unloaded,
     ModelMissing,
     Downloading,
+    Importing,
     Idle,
     Recording,
     Transcribing,
@@ -13,8 +15,10 @@ impl State {
     pub(crate) fn as_str(self) -> &'static str {
         match self {
             Self::Loading => "loading",
+            Self::Unloaded => "unloaded",
             Self::ModelMissing => "model missing",
             Self::Downloading => "downloading",
+            Self::Importing => "importing",
             Self::Idle => "idle",
             Self::Recording => "recording",
             Self::Transcribing => "transcribing",
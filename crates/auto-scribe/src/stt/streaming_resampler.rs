@@ -0,0 +1,208 @@
+use crate::stt::{SttError, SttResult};
+
+use rubato::{
+    Async, FixedAsync, Resampler, SincInterpolationParameters, SincInterpolationType,
+    WindowFunction, audioadapter_buffers::owned::InterleavedOwned,
+};
+use std::mem;
+
+const CHUNK_FRAMES: usize = 1024;
+
+/// Mono sinc resampler fed from the capture callback. It keeps the filter state between
+/// callbacks, so a recording is already at the target rate when the hotkey is released.
+pub(crate) struct StreamingResampler {
+    resampler: Async<f32>,
+    ratio: f64,
+    pending: Vec<f32>,
+    input_frames: usize,
+    /// Leading frames still to come out of the resampler that are filter delay, not signal.
+    delay_frames: usize,
+    /// Signal frames appended to `output` so far.
+    output_frames: usize,
+    /// Reused for every chunk so the capture callback does not allocate.
+    input_buffer: Vec<f32>,
+    output_buffer: Vec<f32>,
+}
+
+impl StreamingResampler {
+    pub(crate) fn new(source_sample_rate: u32, target_sample_rate: u32) -> SttResult<Self> {
+        if source_sample_rate == 0 || target_sample_rate == 0 {
+            return Err(SttError::resampling(
+                "source and target sample rates must be greater than zero",
+            ));
+        }
+
+        let params = SincInterpolationParameters {
+            sinc_len: 256,
+            f_cutoff: 0.95,
+            interpolation: SincInterpolationType::Linear,
+            oversampling_factor: 256,
+            window: WindowFunction::BlackmanHarris2,
+        };
+        let ratio = f64::from(target_sample_rate) / f64::from(source_sample_rate);
+        let resampler =
+            Async::<f32>::new_sinc(ratio, 2.0, &params, CHUNK_FRAMES, 1, FixedAsync::Input)
+                .map_err(|error| SttError::resampling(error.to_string()))?;
+        let delay_frames = resampler.output_delay();
+        let output_buffer = vec![0.0; resampler.output_frames_max()];
+
+        Ok(Self {
+            resampler,
+            ratio,
+            pending: Vec::with_capacity(CHUNK_FRAMES * 2),
+            input_frames: 0,
+            delay_frames,
+            output_frames: 0,
+            input_buffer: Vec::with_capacity(CHUNK_FRAMES),
+            output_buffer,
+        })
+    }
+
+    /// Resamples every complete chunk of `samples` and appends the result to `output`; the
+    /// remainder waits for the next call. The filter delay is dropped as it comes out, so
+    /// `output` only ever receives signal and may be drained between calls.
+    pub(crate) fn push(&mut self, samples: &[f32], output: &mut Vec<f32>) -> SttResult<()> {
+        self.input_frames += samples.len();
+        self.pending.extend_from_slice(samples);
+
+        let mut consumed = 0;
+        while self.pending.len() - consumed >= CHUNK_FRAMES {
+            self.input_buffer.clear();
+            self.input_buffer
+                .extend_from_slice(&self.pending[consumed..consumed + CHUNK_FRAMES]);
+            self.process_input_buffer(output)?;
+            consumed += CHUNK_FRAMES;
+        }
+        self.pending.drain(..consumed);
+        Ok(())
+    }
+
+    /// Flushes the buffered input and the filter tail. Across all calls, `output` then has
+    /// received as many frames as a one-shot resample of the same input.
+    pub(crate) fn finish(mut self, output: &mut Vec<f32>) -> SttResult<()> {
+        let expected_frames = (self.input_frames as f64 * self.ratio).ceil() as usize;
+        let finish_start = output.len();
+
+        self.input_buffer.clear();
+        self.input_buffer.append(&mut self.pending);
+        while self.output_frames < expected_frames {
+            self.input_buffer.resize(CHUNK_FRAMES, 0.0);
+            self.process_input_buffer(output)?;
+            self.input_buffer.clear();
+        }
+
+        // Only frames appended by this call can be past the end of the signal.
+        let overshoot = (self.output_frames - expected_frames).min(output.len() - finish_start);
+        output.truncate(output.len() - overshoot);
+        Ok(())
+    }
+
+    /// Resamples the `CHUNK_FRAMES` in `input_buffer`. Both buffers are handed to rubato and
+    /// taken back afterwards, so their allocations are reused.
+    fn process_input_buffer(&mut self, output: &mut Vec<f32>) -> SttResult<()> {
+        let input = InterleavedOwned::new_from(mem::take(&mut self.input_buffer), 1, CHUNK_FRAMES)
+            .map_err(|error| SttError::resampling(error.to_string()))?;
+        let output_frames_max = self.resampler.output_frames_max();
+        let mut resampled_buffer = mem::take(&mut self.output_buffer);
+        resampled_buffer.resize(output_frames_max, 0.0);
+        let mut resampled = InterleavedOwned::new_from(resampled_buffer, 1, output_frames_max)
+            .map_err(|error| SttError::resampling(error.to_string()))?;
+
+        let result = self
+            .resampler
+            .process_into_buffer(&input, &mut resampled, None);
+        self.input_buffer = input.take_data();
+        self.output_buffer = resampled.take_data();
+        let (_, frames_out) = result.map_err(|error| SttError::resampling(error.to_string()))?;
+
+        let skipped = self.delay_frames.min(frames_out);
+        self.delay_frames -= skipped;
+        output.extend_from_slice(&self.output_buffer[skipped..frames_out]);
+        self.output_frames += frames_out - skipped;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stt::{TARGET_SAMPLE_RATE, resample};
+
+    const SOURCE_RATES: [u32; 2] = [44_100, 48_000];
+    const CASES_PER_RATE: u64 = 24;
+    const TOLERANCE: f32 = 1e-5;
+
+    /// xorshift64*, so every case can be reproduced from its seed.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+        }
+
+        fn below(&mut self, bound: usize) -> usize {
+            (self.next() % bound as u64) as usize
+        }
+
+        fn sample(&mut self) -> f32 {
+            (self.next() >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+        }
+    }
+
+    /// Pushes `input` in random callback sizes, with a consumer that sometimes drains `output`.
+    fn chunked(source_rate: u32, input: &[f32], rng: &mut Rng) -> SttResult<Vec<f32>> {
+        let mut resampler = StreamingResampler::new(source_rate, TARGET_SAMPLE_RATE)?;
+        let mut output = Vec::new();
+        let mut collected = Vec::new();
+        let mut offset = 0;
+        while offset < input.len() {
+            let len = (1 + rng.below(3_000)).min(input.len() - offset);
+            resampler.push(&input[offset..offset + len], &mut output)?;
+            offset += len;
+            if rng.below(4) == 0 {
+                collected.append(&mut output);
+            }
+        }
+        resampler.finish(&mut output)?;
+        collected.append(&mut output);
+        Ok(collected)
+    }
+
+    #[test]
+    fn chunked_push_matches_the_whole_recording_resample() -> SttResult<()> {
+        for source_rate in SOURCE_RATES {
+            for case in 0..CASES_PER_RATE {
+                let seed = u64::from(source_rate) * 1_000 + case + 1;
+                let mut rng = Rng(seed);
+                let input = (0..rng.below(40_000))
+                    .map(|index| (index as f32 * 0.01).sin() * 0.5 + rng.sample() * 0.1)
+                    .collect::<Vec<_>>();
+                // The whole-recording rubato path that `read_wav` uses is the reference.
+                let expected = resample(input.clone(), source_rate, TARGET_SAMPLE_RATE)?;
+                let collected = chunked(source_rate, &input, &mut rng)?;
+
+                let expected_len = (input.len() as f64 * f64::from(TARGET_SAMPLE_RATE)
+                    / f64::from(source_rate))
+                .ceil() as usize;
+                assert_eq!(expected.len(), expected_len, "seed {seed}");
+                assert_eq!(collected.len(), expected.len(), "seed {seed}");
+                let max_error = collected
+                    .iter()
+                    .zip(&expected)
+                    .map(|(chunked, whole)| (chunked - whole).abs())
+                    .fold(0.0, f32::max);
+                assert!(max_error <= TOLERANCE, "seed {seed}: max error {max_error}");
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn empty_input_produces_no_output() -> SttResult<()> {
+        assert!(chunked(48_000, &[], &mut Rng(1))?.is_empty());
+        Ok(())
+    }
+}
@@ -16,6 +16,12 @@ pub(crate) enum SttError {
         location: ErrorLocation,
     },
 
+    #[error("media pause error: {message} {location}")]
+    MediaPause {
+        message: String,
+        location: ErrorLocation,
+    },
+
     #[error("model path error: {message} {location}")]
     ModelPath {
         message: String,
@@ -70,6 +76,14 @@ impl SttError {
         }
     }
 
+    #[track_caller]
+    pub(crate) fn media_pause(message: impl Into<String>) -> Self {
+        Self::MediaPause {
+            message: message.into(),
+            location: ErrorLocation::from(Location::caller()),
+        }
+    }
+
     #[track_caller]
     pub(crate) fn model_path(message: impl Into<String>) -> Self {
         Self::ModelPath {
@@ -0,0 +1,79 @@
+use crate::stt::TranscriptSegment;
+
+use std::time::Duration;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Transcript {
+    pub(crate) segments: Vec<TranscriptSegment>,
+    /// Speaker names, "Speaker 1" and so on until renamed; empty unless diarized.
+    pub(crate) speakers: Vec<String>,
+}
+
+impl Transcript {
+    /// The plain text used for the clipboard and the overlay.
+    pub(crate) fn text(&self) -> String {
+        self.segments
+            .iter()
+            .map(TranscriptSegment::text)
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The text with one `Name: ...` paragraph per change of speaker, or the plain text when
+    /// the transcript has no speakers.
+    pub(crate) fn labelled_text(&self) -> String {
+        if self.speakers.is_empty() {
+            return self.text();
+        }
+
+        let mut paragraphs: Vec<(Option<&str>, Vec<String>)> = Vec::new();
+        for segment in &self.segments {
+            let text = segment.text();
+            if text.is_empty() {
+                continue;
+            }
+
+            let speaker = self.speaker_name(segment);
+            match paragraphs.last_mut() {
+                Some((current, texts)) if *current == speaker => texts.push(text),
+                _ => paragraphs.push((speaker, vec![text])),
+            }
+        }
+
+        paragraphs
+            .into_iter()
+            .map(|(speaker, texts)| match speaker {
+                Some(speaker) => format!("{speaker}: {}", texts.join(" ")),
+                None => texts.join(" "),
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    pub(crate) fn speaker_name(&self, segment: &TranscriptSegment) -> Option<&str> {
+        segment
+            .speaker
+            .and_then(|speaker| self.speakers.get(speaker))
+            .map(String::as_str)
+    }
+
+    /// Returns false when `speaker` does not exist or `name` is blank.
+    pub(crate) fn rename_speaker(&mut self, speaker: usize, name: &str) -> bool {
+        let name = name.trim();
+        match self.speakers.get_mut(speaker) {
+            Some(current) if !name.is_empty() => {
+                *current = name.to_string();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub(crate) fn duration(&self) -> Duration {
+        self.segments
+            .last()
+            .map(|segment| segment.end)
+            .unwrap_or_default()
+    }
+}
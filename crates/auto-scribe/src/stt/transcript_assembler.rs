@@ -0,0 +1,80 @@
+use crate::stt::{Transcript, TranscriptSegment, TranscriptWord};
+
+use std::time::Duration;
+
+const MAX_SEGMENT_DURATION: Duration = Duration::from_secs(6);
+const SENTENCE_ENDINGS: [char; 3] = ['.', '?', '!'];
+
+/// Builds a `Transcript` from the text the streaming decoder emits for each audio chunk.
+/// Word times have chunk resolution: every word gets the bounds of the chunk that produced it.
+#[derive(Default)]
+pub(crate) struct TranscriptAssembler {
+    transcript: Transcript,
+    segment_open: bool,
+}
+
+impl TranscriptAssembler {
+    pub(crate) fn push_chunk(&mut self, text: &str, start: Duration, end: Duration) {
+        if text.trim().is_empty() {
+            // A chunk without speech ends the current segment.
+            self.segment_open = false;
+            return;
+        }
+
+        // Subword tokens continue the previous word unless the chunk starts with a space.
+        let continues_word = !text.starts_with(char::is_whitespace);
+        for (index, piece) in text.split_whitespace().enumerate() {
+            if index == 0
+                && continues_word
+                && self.segment_open
+                && let Some(segment) = self.transcript.segments.last_mut()
+                && let Some(word) = segment.words.last_mut()
+            {
+                word.text.push_str(piece);
+                word.end = end;
+                segment.end = end;
+                self.close_segment_after(piece);
+                continue;
+            }
+
+            let segment = match self.transcript.segments.last_mut() {
+                Some(segment)
+                    if self.segment_open
+                        && end.saturating_sub(segment.start) <= MAX_SEGMENT_DURATION =>
+                {
+                    segment
+                }
+                _ => {
+                    self.transcript.segments.push(TranscriptSegment {
+                        start,
+                        end,
+                        words: Vec::new(),
+                        speaker: None,
+                    });
+                    self.segment_open = true;
+                    let Some(segment) = self.transcript.segments.last_mut() else {
+                        return;
+                    };
+                    segment
+                }
+            };
+            segment.words.push(TranscriptWord {
+                text: piece.to_string(),
+                start,
+                end,
+            });
+            segment.end = end;
+            self.close_segment_after(piece);
+        }
+    }
+
+    pub(crate) fn finish(self) -> Transcript {
+        self.transcript
+    }
+
+    fn close_segment_after(&mut self, piece: &str) {
+        if piece.ends_with(SENTENCE_ENDINGS) {
+            self.segment_open = false;
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::stt::TranscriptWord;
+
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct TranscriptSegment {
+    pub(crate) start: Duration,
+    pub(crate) end: Duration,
+    pub(crate) words: Vec<TranscriptWord>,
+    /// Index into `Transcript::speakers` once the recording has been diarized.
+    pub(crate) speaker: Option<usize>,
+}
+
+impl TranscriptSegment {
+    pub(crate) fn text(&self) -> String {
+        self.words
+            .iter()
+            .map(|word| word.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
@@ -0,0 +1,8 @@
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct TranscriptWord {
+    pub(crate) text: String,
+    pub(crate) start: Duration,
+    pub(crate) end: Duration,
+}
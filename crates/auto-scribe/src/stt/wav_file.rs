@@ -0,0 +1,60 @@
+use crate::stt::{AudioRecording, SttError, SttResult, TARGET_SAMPLE_RATE, resample};
+
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use std::path::Path;
+
+/// Reads a PCM or IEEE float WAV file, mixed down to mono and resampled to 16 kHz.
+pub(crate) fn read_wav(path: &Path) -> SttResult<AudioRecording> {
+    let mut reader = WavReader::open(path)
+        .map_err(|error| SttError::audio_device(format!("open {}: {error}", path.display())))?;
+    let spec = reader.spec();
+    let read_error =
+        |error: hound::Error| SttError::audio_device(format!("read {}: {error}", path.display()));
+
+    let interleaved = match spec.sample_format {
+        SampleFormat::Float => reader
+            .samples::<f32>()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(read_error)?,
+        SampleFormat::Int => {
+            let scale = 2_f32.powi(i32::from(spec.bits_per_sample.saturating_sub(1)));
+            reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|sample| sample as f32 / scale))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(read_error)?
+        }
+    };
+
+    let channels = usize::from(spec.channels.max(1));
+    let samples = interleaved
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect();
+
+    Ok(AudioRecording {
+        samples: resample(samples, spec.sample_rate, TARGET_SAMPLE_RATE)?,
+        sample_rate: TARGET_SAMPLE_RATE,
+        clipped: false,
+    })
+}
+
+/// Writes 16 kHz mono samples in `-1.0..=1.0` as 16-bit PCM.
+pub(crate) fn write_wav(path: &Path, samples: &[f32]) -> SttResult<()> {
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: TARGET_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let write_error =
+        |error: hound::Error| SttError::audio_device(format!("write {}: {error}", path.display()));
+
+    let mut writer = WavWriter::create(path, spec).map_err(write_error)?;
+    for sample in samples {
+        writer
+            .write_sample((sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16)
+            .map_err(write_error)?;
+    }
+    writer.finalize().map_err(write_error)
+}
@@ -1,8 +1,14 @@
+use crate::stt::{ExecutionProvider, ModelChecksumStatus, Transcript};
+
 #[derive(Clone, Debug)]
 pub(crate) enum WorkerEvent {
-    Ready,
-    Transcript(String),
+    Ready(ExecutionProvider),
+    ProviderFallback(String),
+    OptimizedModelCache(String),
+    Transcript(Transcript),
+    TranscriptDiscarded,
     Error(String),
+    WorkerCrashed(String),
     ModelDownloadProgress {
         file_name: String,
         completed_files: usize,
@@ -10,6 +16,17 @@ pub(crate) enum WorkerEvent {
         file_downloaded_bytes: u64,
         file_total_bytes: Option<u64>,
     },
+    ModelDownloadAttempt {
+        file_name: String,
+        source: String,
+        route: String,
+        attempt: usize,
+        total_attempts: usize,
+        previous_error: Option<String>,
+    },
     ModelDownloadFinished,
     ModelDownloadError(String),
+    ModelImportFinished(ModelChecksumStatus),
+    ModelImportError(String),
+    MediaPauseError(String),
 }
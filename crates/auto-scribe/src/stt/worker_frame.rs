@@ -0,0 +1,588 @@
+use crate::stt::{
+    AudioProcessing, AudioRecording, ExecutionProvider, SttError, SttResult, Transcript,
+    TranscriptSegment, TranscriptWord, WorkerEvent, WorkerRequest,
+};
+
+use std::{
+    io::{ErrorKind, Read, Write},
+    path::PathBuf,
+    time::Duration,
+};
+
+/// Every frame starts with this marker so bytes that a native library prints to the worker
+/// process's stdout are skipped instead of desynchronizing the stream.
+const FRAME_MARKER: [u8; 4] = *b"ASW1";
+const MAX_FRAME_BYTES: usize = 256 * 1024 * 1024;
+
+const REQUEST_TRANSCRIBE: u8 = 1;
+const REQUEST_CANCEL: u8 = 2;
+
+const EVENT_READY: u8 = 1;
+const EVENT_PROVIDER_FALLBACK: u8 = 2;
+const EVENT_OPTIMIZED_MODEL_CACHE: u8 = 3;
+const EVENT_TRANSCRIPT: u8 = 4;
+const EVENT_TRANSCRIPT_DISCARDED: u8 = 5;
+const EVENT_ERROR: u8 = 6;
+const EVENT_WORKER_CRASHED: u8 = 7;
+
+pub(crate) fn write_request(writer: &mut impl Write, request: &WorkerRequest) -> SttResult<()> {
+    let frame = encode_request(request)?;
+    write_encoded(writer, &frame)
+}
+
+/// The complete frame for `request`. Fails without side effects when the frame would exceed
+/// `MAX_FRAME_BYTES`, which the reading side would reject, so the caller can report it.
+pub(crate) fn encode_request(request: &WorkerRequest) -> SttResult<Vec<u8>> {
+    let mut frame = Vec::new();
+    match request {
+        WorkerRequest::Transcribe {
+            recording,
+            processing,
+            diarization_model,
+            generation,
+        } => {
+            frame.push(REQUEST_TRANSCRIBE);
+            put_u64(&mut frame, *generation);
+            put_processing(&mut frame, processing);
+            frame.push(u8::from(diarization_model.is_some()));
+            put_str(
+                &mut frame,
+                &diarization_model
+                    .as_deref()
+                    .map(|path| path.to_string_lossy())
+                    .unwrap_or_default(),
+            )?;
+            // Checked before encoding so an overlong recording is not copied into a frame first.
+            let sample_bytes = recording.samples.len().saturating_mul(size_of::<f32>());
+            if sample_bytes > MAX_FRAME_BYTES {
+                return Err(frame_too_large(sample_bytes));
+            }
+            put_u32(&mut frame, recording.sample_rate);
+            frame.push(u8::from(recording.clipped));
+            put_len(&mut frame, recording.samples.len())?;
+            for sample in &recording.samples {
+                frame.extend_from_slice(&sample.to_le_bytes());
+            }
+        }
+        WorkerRequest::Cancel { generation } => {
+            frame.push(REQUEST_CANCEL);
+            put_u64(&mut frame, *generation);
+        }
+    }
+
+    with_frame_header(frame)
+}
+
+pub(crate) fn read_request(reader: &mut impl Read) -> SttResult<Option<WorkerRequest>> {
+    let Some(frame) = read_frame(reader)? else {
+        return Ok(None);
+    };
+    let mut bytes = frame.as_slice();
+
+    let request = match take_u8(&mut bytes)? {
+        REQUEST_TRANSCRIBE => {
+            let generation = take_u64(&mut bytes)?;
+            let processing = take_processing(&mut bytes)?;
+            let diarize = take_u8(&mut bytes)? != 0;
+            let diarization_model = take_str(&mut bytes)?;
+            let sample_rate = take_u32(&mut bytes)?;
+            let clipped = take_u8(&mut bytes)? != 0;
+            let sample_count = take_u32(&mut bytes)? as usize;
+            let samples = take(&mut bytes, sample_count.saturating_mul(4))?
+                .chunks_exact(4)
+                .map(|chunk| <[u8; 4]>::try_from(chunk).map(f32::from_le_bytes))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|error| SttError::worker_channel(error.to_string()))?;
+            WorkerRequest::Transcribe {
+                recording: AudioRecording {
+                    samples,
+                    sample_rate,
+                    clipped,
+                },
+                processing,
+                diarization_model: diarize.then(|| PathBuf::from(diarization_model)),
+                generation,
+            }
+        }
+        REQUEST_CANCEL => WorkerRequest::Cancel {
+            generation: take_u64(&mut bytes)?,
+        },
+        tag => {
+            return Err(SttError::worker_channel(format!(
+                "unknown worker request tag {tag}"
+            )));
+        }
+    };
+
+    Ok(Some(request))
+}
+
+pub(crate) fn write_event(writer: &mut impl Write, event: &WorkerEvent) -> SttResult<()> {
+    let mut frame = Vec::new();
+    match event {
+        WorkerEvent::Ready(provider) => {
+            frame.push(EVENT_READY);
+            put_str(&mut frame, provider.config_name())?;
+        }
+        WorkerEvent::ProviderFallback(reason) => {
+            frame.push(EVENT_PROVIDER_FALLBACK);
+            put_str(&mut frame, reason)?;
+        }
+        WorkerEvent::OptimizedModelCache(status) => {
+            frame.push(EVENT_OPTIMIZED_MODEL_CACHE);
+            put_str(&mut frame, status)?;
+        }
+        WorkerEvent::Transcript(transcript) => {
+            frame.push(EVENT_TRANSCRIPT);
+            put_transcript(&mut frame, transcript)?;
+        }
+        WorkerEvent::TranscriptDiscarded => frame.push(EVENT_TRANSCRIPT_DISCARDED),
+        WorkerEvent::Error(message) => {
+            frame.push(EVENT_ERROR);
+            put_str(&mut frame, message)?;
+        }
+        WorkerEvent::WorkerCrashed(reason) => {
+            frame.push(EVENT_WORKER_CRASHED);
+            put_str(&mut frame, reason)?;
+        }
+        WorkerEvent::ModelDownloadProgress { .. }
+        | WorkerEvent::ModelDownloadAttempt { .. }
+        | WorkerEvent::ModelDownloadFinished
+        | WorkerEvent::ModelDownloadError(_)
+        | WorkerEvent::ModelImportFinished(_)
+        | WorkerEvent::ModelImportError(_)
+        | WorkerEvent::MediaPauseError(_) => {
+            return Err(SttError::worker_channel(
+                "model download, import and media pause events are not sent by the STT worker",
+            ));
+        }
+    }
+
+    let frame = with_frame_header(frame)?;
+    write_encoded(writer, &frame)
+}
+
+pub(crate) fn read_event(reader: &mut impl Read) -> SttResult<Option<WorkerEvent>> {
+    let Some(frame) = read_frame(reader)? else {
+        return Ok(None);
+    };
+    let mut bytes = frame.as_slice();
+
+    let event = match take_u8(&mut bytes)? {
+        EVENT_READY => {
+            WorkerEvent::Ready(ExecutionProvider::from_config_name(&take_str(&mut bytes)?)?)
+        }
+        EVENT_PROVIDER_FALLBACK => WorkerEvent::ProviderFallback(take_str(&mut bytes)?),
+        EVENT_OPTIMIZED_MODEL_CACHE => WorkerEvent::OptimizedModelCache(take_str(&mut bytes)?),
+        EVENT_TRANSCRIPT => WorkerEvent::Transcript(take_transcript(&mut bytes)?),
+        EVENT_TRANSCRIPT_DISCARDED => WorkerEvent::TranscriptDiscarded,
+        EVENT_ERROR => WorkerEvent::Error(take_str(&mut bytes)?),
+        EVENT_WORKER_CRASHED => WorkerEvent::WorkerCrashed(take_str(&mut bytes)?),
+        tag => {
+            return Err(SttError::worker_channel(format!(
+                "unknown worker event tag {tag}"
+            )));
+        }
+    };
+
+    Ok(Some(event))
+}
+
+fn with_frame_header(frame: Vec<u8>) -> SttResult<Vec<u8>> {
+    if frame.len() > MAX_FRAME_BYTES {
+        return Err(frame_too_large(frame.len()));
+    }
+
+    let mut encoded = FRAME_MARKER.to_vec();
+    put_len(&mut encoded, frame.len())?;
+    encoded.extend_from_slice(&frame);
+    Ok(encoded)
+}
+
+fn frame_too_large(len: usize) -> SttError {
+    SttError::worker_channel(format!(
+        "worker frame of {len} bytes exceeds the {MAX_FRAME_BYTES} byte limit"
+    ))
+}
+
+fn write_encoded(writer: &mut impl Write, encoded: &[u8]) -> SttResult<()> {
+    writer
+        .write_all(encoded)
+        .and_then(|()| writer.flush())
+        .map_err(|error| SttError::worker_channel(format!("write worker frame: {error}")))
+}
+
+/// Returns `None` when the stream ends, including in the middle of a frame.
+fn read_frame(reader: &mut impl Read) -> SttResult<Option<Vec<u8>>> {
+    let mut window = [0; FRAME_MARKER.len()];
+    let mut filled = 0;
+    while filled < window.len() || window != FRAME_MARKER {
+        let mut byte = [0];
+        match reader.read(&mut byte) {
+            Ok(0) => return Ok(None),
+            Ok(_) => {}
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => {
+                return Err(SttError::worker_channel(format!(
+                    "read worker frame: {error}"
+                )));
+            }
+        }
+
+        if filled < window.len() {
+            window[filled] = byte[0];
+            filled += 1;
+        } else {
+            window.rotate_left(1);
+            window[FRAME_MARKER.len() - 1] = byte[0];
+        }
+    }
+
+    let mut len_bytes = [0; 4];
+    let mut frame = Vec::new();
+    let result = reader.read_exact(&mut len_bytes).and_then(|()| {
+        let frame_len = u32::from_le_bytes(len_bytes) as usize;
+        if frame_len > MAX_FRAME_BYTES {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidData,
+                format!("frame of {frame_len} bytes exceeds the {MAX_FRAME_BYTES} byte limit"),
+            ));
+        }
+        frame.resize(frame_len, 0);
+        reader.read_exact(&mut frame)
+    });
+
+    match result {
+        Ok(()) => Ok(Some(frame)),
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => Ok(None),
+        Err(error) => Err(SttError::worker_channel(format!(
+            "read worker frame: {error}"
+        ))),
+    }
+}
+
+fn put_u32(frame: &mut Vec<u8>, value: u32) {
+    frame.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u64(frame: &mut Vec<u8>, value: u64) {
+    frame.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_len(frame: &mut Vec<u8>, len: usize) -> SttResult<()> {
+    let len = u32::try_from(len).map_err(|_| {
+        SttError::worker_channel(format!("worker frame field of {len} is too long"))
+    })?;
+    put_u32(frame, len);
+    Ok(())
+}
+
+fn put_str(frame: &mut Vec<u8>, value: &str) -> SttResult<()> {
+    put_len(frame, value.len())?;
+    frame.extend_from_slice(value.as_bytes());
+    Ok(())
+}
+
+fn put_processing(frame: &mut Vec<u8>, processing: &AudioProcessing) {
+    frame.push(u8::from(processing.high_pass));
+    frame.push(u8::from(processing.noise_suppression));
+    frame.push(u8::from(processing.gain_control));
+    frame.extend_from_slice(&processing.target_level_dbfs.to_le_bytes());
+}
+
+fn put_duration(frame: &mut Vec<u8>, duration: Duration) {
+    put_u64(
+        frame,
+        u64::try_from(duration.as_micros()).unwrap_or(u64::MAX),
+    );
+}
+
+fn put_transcript(frame: &mut Vec<u8>, transcript: &Transcript) -> SttResult<()> {
+    put_len(frame, transcript.speakers.len())?;
+    for speaker in &transcript.speakers {
+        put_str(frame, speaker)?;
+    }
+    put_len(frame, transcript.segments.len())?;
+    for segment in &transcript.segments {
+        put_duration(frame, segment.start);
+        put_duration(frame, segment.end);
+        frame.push(u8::from(segment.speaker.is_some()));
+        put_len(frame, segment.speaker.unwrap_or_default())?;
+        put_len(frame, segment.words.len())?;
+        for word in &segment.words {
+            put_str(frame, &word.text)?;
+            put_duration(frame, word.start);
+            put_duration(frame, word.end);
+        }
+    }
+    Ok(())
+}
+
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> SttResult<&'a [u8]> {
+    if bytes.len() < len {
+        return Err(SttError::worker_channel("worker frame is truncated"));
+    }
+
+    let (head, tail) = bytes.split_at(len);
+    *bytes = tail;
+    Ok(head)
+}
+
+fn take_u8(bytes: &mut &[u8]) -> SttResult<u8> {
+    take(bytes, 1).map(|head| head[0])
+}
+
+fn take_u32(bytes: &mut &[u8]) -> SttResult<u32> {
+    let head = take(bytes, 4)?;
+    <[u8; 4]>::try_from(head)
+        .map(u32::from_le_bytes)
+        .map_err(|error| SttError::worker_channel(error.to_string()))
+}
+
+fn take_f32(bytes: &mut &[u8]) -> SttResult<f32> {
+    take_u32(bytes).map(f32::from_bits)
+}
+
+fn take_u64(bytes: &mut &[u8]) -> SttResult<u64> {
+    let head = take(bytes, 8)?;
+    <[u8; 8]>::try_from(head)
+        .map(u64::from_le_bytes)
+        .map_err(|error| SttError::worker_channel(error.to_string()))
+}
+
+fn take_processing(bytes: &mut &[u8]) -> SttResult<AudioProcessing> {
+    let high_pass = take_u8(bytes)? != 0;
+    let noise_suppression = take_u8(bytes)? != 0;
+    let gain_control = take_u8(bytes)? != 0;
+    let target_level_dbfs = take_f32(bytes)?;
+    Ok(AudioProcessing {
+        high_pass,
+        noise_suppression,
+        gain_control,
+        target_level_dbfs,
+    })
+}
+
+fn take_duration(bytes: &mut &[u8]) -> SttResult<Duration> {
+    take_u64(bytes).map(Duration::from_micros)
+}
+
+fn take_transcript(bytes: &mut &[u8]) -> SttResult<Transcript> {
+    let speaker_count = take_u32(bytes)?;
+    let mut speakers = Vec::new();
+    for _ in 0..speaker_count {
+        speakers.push(take_str(bytes)?);
+    }
+    let segment_count = take_u32(bytes)?;
+    let mut segments = Vec::new();
+    for _ in 0..segment_count {
+        let start = take_duration(bytes)?;
+        let end = take_duration(bytes)?;
+        let has_speaker = take_u8(bytes)? != 0;
+        let speaker = take_u32(bytes)? as usize;
+        let word_count = take_u32(bytes)?;
+        let mut words = Vec::new();
+        for _ in 0..word_count {
+            let text = take_str(bytes)?;
+            let word_start = take_duration(bytes)?;
+            let word_end = take_duration(bytes)?;
+            words.push(TranscriptWord {
+                text,
+                start: word_start,
+                end: word_end,
+            });
+        }
+        segments.push(TranscriptSegment {
+            start,
+            end,
+            words,
+            speaker: has_speaker.then_some(speaker),
+        });
+    }
+    Ok(Transcript { segments, speakers })
+}
+
+fn take_str(bytes: &mut &[u8]) -> SttResult<String> {
+    let len = take_u32(bytes)? as usize;
+    String::from_utf8(take(bytes, len)?.to_vec())
+        .map_err(|error| SttError::worker_channel(format!("worker frame string: {error}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diarized_transcript() -> Transcript {
+        let word = |text: &str, start_ms, end_ms| TranscriptWord {
+            text: text.to_string(),
+            start: Duration::from_millis(start_ms),
+            end: Duration::from_millis(end_ms),
+        };
+        Transcript {
+            segments: vec![
+                TranscriptSegment {
+                    start: Duration::ZERO,
+                    end: Duration::from_millis(900),
+                    words: vec![word("Hello", 0, 400), word("there.", 400, 900)],
+                    speaker: Some(1),
+                },
+                TranscriptSegment {
+                    start: Duration::from_millis(900),
+                    end: Duration::from_millis(1_500),
+                    words: vec![word("Grüße", 900, 1_500)],
+                    speaker: None,
+                },
+            ],
+            speakers: vec!["Speaker 1".to_string(), "Ana".to_string()],
+        }
+    }
+
+    #[test]
+    fn requests_round_trip() -> SttResult<()> {
+        let processing = AudioProcessing {
+            high_pass: true,
+            noise_suppression: false,
+            gain_control: true,
+            target_level_dbfs: -18.5,
+        };
+        let mut stream = Vec::new();
+        write_request(
+            &mut stream,
+            &WorkerRequest::Transcribe {
+                recording: AudioRecording {
+                    samples: vec![0.0, -0.5, 1.0, f32::MIN_POSITIVE],
+                    sample_rate: 48_000,
+                    clipped: true,
+                },
+                processing,
+                diarization_model: Some(PathBuf::from("/models/sortformer.onnx")),
+                generation: 7,
+            },
+        )?;
+        write_request(&mut stream, &WorkerRequest::Cancel { generation: 8 })?;
+
+        let mut reader = stream.as_slice();
+        let Some(WorkerRequest::Transcribe {
+            recording,
+            processing: read_processing,
+            diarization_model,
+            generation,
+        }) = read_request(&mut reader)?
+        else {
+            return Err(SttError::worker_channel("expected a transcribe request"));
+        };
+        assert_eq!(recording.samples, vec![0.0, -0.5, 1.0, f32::MIN_POSITIVE]);
+        assert_eq!((recording.sample_rate, recording.clipped), (48_000, true));
+        assert_eq!(read_processing, processing);
+        assert_eq!(
+            diarization_model,
+            Some(PathBuf::from("/models/sortformer.onnx"))
+        );
+        assert_eq!(generation, 7);
+        assert!(matches!(
+            read_request(&mut reader)?,
+            Some(WorkerRequest::Cancel { generation: 8 })
+        ));
+        assert!(read_request(&mut reader)?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn events_round_trip() -> SttResult<()> {
+        let mut stream = Vec::new();
+        write_event(&mut stream, &WorkerEvent::Ready(ExecutionProvider::Cpu))?;
+        write_event(&mut stream, &WorkerEvent::Transcript(diarized_transcript()))?;
+        write_event(&mut stream, &WorkerEvent::TranscriptDiscarded)?;
+        write_event(
+            &mut stream,
+            &WorkerEvent::Error("decoder failed".to_string()),
+        )?;
+
+        let mut reader = stream.as_slice();
+        assert!(matches!(
+            read_event(&mut reader)?,
+            Some(WorkerEvent::Ready(ExecutionProvider::Cpu))
+        ));
+        assert!(matches!(
+            read_event(&mut reader)?,
+            Some(WorkerEvent::Transcript(transcript)) if transcript == diarized_transcript()
+        ));
+        assert!(matches!(
+            read_event(&mut reader)?,
+            Some(WorkerEvent::TranscriptDiscarded)
+        ));
+        assert!(matches!(
+            read_event(&mut reader)?,
+            Some(WorkerEvent::Error(message)) if message == "decoder failed"
+        ));
+        assert!(read_event(&mut reader)?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn skips_stray_output_between_frames() -> SttResult<()> {
+        let mut stream = b"CUDA warning: ASW\nASASW".to_vec();
+        write_event(
+            &mut stream,
+            &WorkerEvent::ProviderFallback("cuda".to_string()),
+        )?;
+        stream.extend_from_slice(b"\x00\xffASW0 junk");
+        write_event(&mut stream, &WorkerEvent::TranscriptDiscarded)?;
+
+        let mut reader = stream.as_slice();
+        assert!(matches!(
+            read_event(&mut reader)?,
+            Some(WorkerEvent::ProviderFallback(reason)) if reason == "cuda"
+        ));
+        assert!(matches!(
+            read_event(&mut reader)?,
+            Some(WorkerEvent::TranscriptDiscarded)
+        ));
+        assert!(read_event(&mut reader)?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_oversized_frames_and_ends_on_truncated_ones() -> SttResult<()> {
+        let mut oversized = FRAME_MARKER.to_vec();
+        put_len(&mut oversized, MAX_FRAME_BYTES + 1)?;
+        let error = read_event(&mut oversized.as_slice())
+            .err()
+            .map(|error| error.to_string());
+        assert!(error.is_some_and(|error| error.contains("byte limit")));
+
+        let mut truncated = Vec::new();
+        write_event(&mut truncated, &WorkerEvent::Error("cut short".to_string()))?;
+        truncated.truncate(truncated.len() - 3);
+        assert!(read_event(&mut truncated.as_slice())?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn refuses_to_encode_requests_over_the_frame_limit() {
+        let request = WorkerRequest::Transcribe {
+            recording: AudioRecording {
+                samples: vec![0.0; MAX_FRAME_BYTES / 4 + 1],
+                sample_rate: 16_000,
+                clipped: false,
+            },
+            processing: AudioProcessing::default(),
+            diarization_model: None,
+            generation: 1,
+        };
+
+        let mut stream = Vec::new();
+        let error = write_request(&mut stream, &request)
+            .err()
+            .map(|error| error.to_string());
+        assert!(error.is_some_and(|error| error.contains("byte limit")));
+        assert!(stream.is_empty());
+    }
+
+    #[test]
+    fn refuses_events_the_worker_never_sends() {
+        let mut stream = Vec::new();
+        assert!(write_event(&mut stream, &WorkerEvent::ModelDownloadFinished).is_err());
+        assert!(stream.is_empty());
+    }
+}
@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+const FIRST_RESTART_DELAY: Duration = Duration::from_secs(1);
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(30);
+const MAX_CONSECUTIVE_CRASHES: u32 = 5;
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct WorkerHealth {
+    crashes: usize,
+    restarts: usize,
+    consecutive_crashes: u32,
+    replayed_recordings: usize,
+    dropped_recordings: usize,
+    last_crash: Option<String>,
+}
+
+impl WorkerHealth {
+    /// Records a worker death and returns how long to wait before restarting it, or `None`
+    /// once the worker has crashed too many times without reaching `Ready`.
+    pub(crate) fn record_crash(&mut self, reason: String) -> Option<Duration> {
+        self.crashes += 1;
+        self.consecutive_crashes += 1;
+        self.last_crash = Some(reason);
+
+        if self.consecutive_crashes > MAX_CONSECUTIVE_CRASHES {
+            return None;
+        }
+
+        let delay = FIRST_RESTART_DELAY.saturating_mul(1 << (self.consecutive_crashes - 1));
+        Some(delay.min(MAX_RESTART_DELAY))
+    }
+
+    pub(crate) fn record_restart(&mut self) {
+        self.restarts += 1;
+    }
+
+    pub(crate) fn record_ready(&mut self) {
+        self.consecutive_crashes = 0;
+    }
+
+    pub(crate) fn record_replayed_recording(&mut self) {
+        self.replayed_recordings += 1;
+    }
+
+    pub(crate) fn record_dropped_recording(&mut self) {
+        self.dropped_recordings += 1;
+    }
+
+    pub(crate) fn rows(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("Crashes", self.crashes.to_string()),
+            ("Restarts", self.restarts.to_string()),
+            (
+                "Consecutive crashes",
+                format!("{}/{MAX_CONSECUTIVE_CRASHES}", self.consecutive_crashes),
+            ),
+            ("Replayed recordings", self.replayed_recordings.to_string()),
+            ("Dropped recordings", self.dropped_recordings.to_string()),
+            (
+                "Last crash",
+                self.last_crash
+                    .clone()
+                    .unwrap_or_else(|| "none".to_string()),
+            ),
+        ]
+    }
+
+    pub(crate) fn to_text(&self) -> String {
+        let mut lines = vec!["STT worker:".to_string()];
+        lines.extend(
+            self.rows()
+                .into_iter()
+                .map(|(label, value)| format!("  {label}: {value}")),
+        );
+        lines.join("\n")
+    }
+}
@@ -0,0 +1,250 @@
+use crate::stt::{
+    ModelConfig, STT_WORKER_ARG, SttError, SttResult, WorkerEvent, WorkerRequest, encode_request,
+    read_event, read_request, run_supervised_stt_worker, write_event, write_request,
+};
+
+use async_channel::{Receiver, Sender};
+use std::{
+    env,
+    io::{self, BufReader, BufWriter, Read, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    thread,
+};
+
+/// Starts `auto-scribe --stt-worker` and bridges its stdin and stdout to the usual worker
+/// channels, so a native crash in the inference stack only ends the child.
+pub(crate) fn spawn_stt_worker_process(
+    cancel_generation: Arc<AtomicU64>,
+    event_tx: Sender<WorkerEvent>,
+) -> SttResult<Sender<WorkerRequest>> {
+    let executable = env::current_exe()
+        .map_err(|error| SttError::speech_to_text(format!("locate executable: {error}")))?;
+    let mut child = Command::new(&executable)
+        .arg(STT_WORKER_ARG)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|error| {
+            SttError::speech_to_text(format!(
+                "start {} {STT_WORKER_ARG}: {error}",
+                executable.display()
+            ))
+        })?;
+    let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+        let _ = child.kill();
+        let _ = child.wait();
+        return Err(SttError::worker_channel(
+            "STT worker process has no stdin or stdout pipe",
+        ));
+    };
+
+    let (request_tx, request_rx) = async_channel::unbounded();
+    let generation = cancel_generation.load(Ordering::Relaxed);
+    let request_event_tx = event_tx.clone();
+    thread::Builder::new()
+        .name("auto-scribe-stt-worker-requests".to_string())
+        .spawn(move || forward_requests(stdin, generation, &request_rx, &request_event_tx))
+        .map_err(|error| SttError::speech_to_text(error.to_string()))?;
+    thread::Builder::new()
+        .name("auto-scribe-stt-worker-events".to_string())
+        .spawn(move || forward_events(child, stdout, &event_tx))
+        .map_err(|error| SttError::speech_to_text(error.to_string()))?;
+
+    Ok(request_tx)
+}
+
+/// Entry point for `auto-scribe --stt-worker`: reads requests from stdin and writes events
+/// to stdout until stdin closes.
+pub(crate) fn run_stt_worker_process() -> SttResult<()> {
+    let model_config = ModelConfig::load()?;
+    let cancel_generation = Arc::new(AtomicU64::new(0));
+    let (request_tx, request_rx) = async_channel::unbounded();
+    let (event_tx, event_rx) = async_channel::unbounded::<WorkerEvent>();
+
+    let request_generation = cancel_generation.clone();
+    let request_event_tx = event_tx.clone();
+    let request_error = Arc::new(OnceLock::new());
+    let reader_error = request_error.clone();
+    thread::Builder::new()
+        .name("auto-scribe-stt-worker-stdin".to_string())
+        .spawn(move || {
+            let mut stdin = BufReader::new(io::stdin().lock());
+            if let Err(error) = read_worker_requests(
+                &mut stdin,
+                &request_generation,
+                &request_tx,
+                &request_event_tx,
+            ) {
+                let _ = reader_error.set(error.to_string());
+            }
+        })
+        .map_err(|error| SttError::speech_to_text(error.to_string()))?;
+    let event_writer = thread::Builder::new()
+        .name("auto-scribe-stt-worker-stdout".to_string())
+        .spawn(move || {
+            let mut stdout = BufWriter::new(io::stdout().lock());
+            while let Ok(event) = event_rx.recv_blocking() {
+                if write_event(&mut stdout, &event).is_err() {
+                    break;
+                }
+            }
+        })
+        .map_err(|error| SttError::speech_to_text(error.to_string()))?;
+
+    run_supervised_stt_worker(
+        model_config.model_dir().to_path_buf(),
+        model_config.execution_providers(),
+        model_config.runtime_options(),
+        &cancel_generation,
+        request_rx,
+        event_tx,
+    );
+
+    event_writer
+        .join()
+        .map_err(|_| SttError::worker_channel("STT worker event writer panicked"))?;
+
+    // A broken request stream must not look like a clean shutdown to the app.
+    match request_error.get() {
+        Some(error) => Err(SttError::worker_channel(error.clone())),
+        None => Ok(()),
+    }
+}
+
+/// Passes requests from the app to the worker until the stream closes. A frame that cannot be
+/// read leaves the app waiting on whatever it sent, so it is reported as a crash, which makes the
+/// app fail the in-flight recording and restart the worker.
+fn read_worker_requests(
+    reader: &mut impl Read,
+    cancel_generation: &AtomicU64,
+    request_tx: &Sender<WorkerRequest>,
+    event_tx: &Sender<WorkerEvent>,
+) -> SttResult<()> {
+    loop {
+        match read_request(reader) {
+            Ok(Some(WorkerRequest::Cancel { generation })) => {
+                cancel_generation.store(generation, Ordering::Relaxed);
+            }
+            Ok(Some(request @ WorkerRequest::Transcribe { .. })) => {
+                if request_tx.send_blocking(request).is_err() {
+                    return Ok(());
+                }
+            }
+            Ok(None) => return Ok(()),
+            Err(error) => {
+                let _ = event_tx.send_blocking(WorkerEvent::WorkerCrashed(format!(
+                    "STT worker process could not read a request: {error}"
+                )));
+                return Err(error);
+            }
+        }
+    }
+}
+
+fn forward_requests(
+    stdin: ChildStdin,
+    generation: u64,
+    request_rx: &Receiver<WorkerRequest>,
+    event_tx: &Sender<WorkerEvent>,
+) {
+    let mut stdin = BufWriter::new(stdin);
+    // The child starts at generation zero; bring it up to date before the first recording.
+    if write_request(&mut stdin, &WorkerRequest::Cancel { generation }).is_err() {
+        return;
+    }
+
+    while let Ok(request) = request_rx.recv_blocking() {
+        // A request the child could not read, such as an overlong recording, fails on its own
+        // instead of taking the worker down.
+        let frame = match encode_request(&request) {
+            Ok(frame) => frame,
+            Err(error) => {
+                let _ = event_tx.send_blocking(WorkerEvent::Error(error.to_string()));
+                continue;
+            }
+        };
+        if stdin
+            .write_all(&frame)
+            .and_then(|()| stdin.flush())
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+fn forward_events(mut child: Child, stdout: ChildStdout, event_tx: &Sender<WorkerEvent>) {
+    let mut stdout = BufReader::new(stdout);
+    let mut crash_reported = false;
+    let mut read_error = None;
+    loop {
+        match read_event(&mut stdout) {
+            Ok(Some(event)) => {
+                crash_reported |= matches!(event, WorkerEvent::WorkerCrashed(_));
+                if event_tx.send_blocking(event).is_err() {
+                    let _ = child.kill();
+                    break;
+                }
+            }
+            Ok(None) => break,
+            Err(error) => {
+                read_error = Some(error.to_string());
+                let _ = child.kill();
+                break;
+            }
+        }
+    }
+
+    let reason = match (child.wait(), read_error) {
+        (_, Some(error)) => format!("STT worker process stream failed: {error}"),
+        (Ok(status), None) if status.success() => return,
+        (Ok(status), None) => format!("STT worker process exited: {status}"),
+        (Err(error), None) => format!("STT worker process wait failed: {error}"),
+    };
+    if !crash_reported {
+        let _ = event_tx.send_blocking(WorkerEvent::WorkerCrashed(reason));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_bad_request_frame_reaches_the_app_as_a_crash() -> SttResult<()> {
+        let mut stream = Vec::new();
+        write_request(&mut stream, &WorkerRequest::Cancel { generation: 3 })?;
+        // A frame with an unknown request tag, as a desynchronised writer would produce.
+        stream.extend_from_slice(b"ASW1\x01\x00\x00\x00\x7f");
+        write_request(&mut stream, &WorkerRequest::Cancel { generation: 9 })?;
+
+        let cancel_generation = AtomicU64::new(0);
+        let (request_tx, _request_rx) = async_channel::unbounded();
+        let (event_tx, event_rx) = async_channel::unbounded();
+        let result = read_worker_requests(
+            &mut stream.as_slice(),
+            &cancel_generation,
+            &request_tx,
+            &event_tx,
+        );
+
+        assert!(result.is_err());
+        assert_eq!(cancel_generation.load(Ordering::Relaxed), 3);
+
+        // The crash travels over the child's stdout like any other event.
+        let mut stdout = Vec::new();
+        while let Ok(event) = event_rx.try_recv() {
+            write_event(&mut stdout, &event)?;
+        }
+        assert!(matches!(
+            read_event(&mut stdout.as_slice())?,
+            Some(WorkerEvent::WorkerCrashed(reason)) if reason.contains("unknown worker request tag 127")
+        ));
+        Ok(())
+    }
+}
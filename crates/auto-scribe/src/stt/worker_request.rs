@@ -1,5 +1,16 @@
-use crate::stt::AudioRecording;
+use crate::stt::{AudioProcessing, AudioRecording};
+
+use std::path::PathBuf;
 
 pub(crate) enum WorkerRequest {
-    Transcribe(AudioRecording),
+    Transcribe {
+        recording: AudioRecording,
+        processing: AudioProcessing,
+        /// Sortformer model to label speakers with, when diarization applies.
+        diarization_model: Option<PathBuf>,
+        generation: u64,
+    },
+    /// Requests from older generations are skipped; only the worker process needs this, the
+    /// in-process worker reads the shared generation counter directly.
+    Cancel { generation: u64 },
 }
@@ -1,74 +1,328 @@
 use crate::stt::{
-    AudioRecording, SttError, SttResult, TARGET_SAMPLE_RATE, WorkerEvent, WorkerRequest,
-    validate_model_dir,
+    AudioRecording, DECODER_CHUNK_SAMPLES, Diarizer, ExecutionProvider, GraphOptimization,
+    OptimizedModelCache, RuntimeOptions, SttError, SttResult, TARGET_SAMPLE_RATE, Transcript,
+    TranscriptAssembler, WorkerEvent, WorkerRequest, assign_speakers, describe_provider_failure,
+    record_model_last_used, validate_model_dir,
 };
 
 use async_channel::{Receiver, Sender};
+use ort::ep::ExecutionProviderDispatch;
 use parakeet_rs::{ExecutionConfig, Nemotron};
 use rubato::{
     Async, FixedAsync, Resampler, SincInterpolationParameters, SincInterpolationType,
     WindowFunction, audioadapter_buffers::owned::InterleavedOwned,
 };
-use std::{path::PathBuf, thread};
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    thread,
+    time::Duration,
+};
 
 pub(crate) fn spawn_stt_worker(
     model_dir: PathBuf,
-    use_gpu: bool,
+    execution_providers: Vec<ExecutionProvider>,
+    runtime_options: RuntimeOptions,
+    cancel_generation: Arc<AtomicU64>,
     event_tx: Sender<WorkerEvent>,
 ) -> SttResult<Sender<WorkerRequest>> {
     let (request_tx, request_rx) = async_channel::unbounded();
-    let error_tx = event_tx.clone();
 
     let _worker_thread = thread::Builder::new()
         .name("auto-scribe-stt-worker".to_string())
         .spawn(move || {
-            if let Err(error) = run_stt_worker(model_dir, use_gpu, request_rx, event_tx) {
-                let _ = error_tx.send_blocking(WorkerEvent::Error(error.to_string()));
-            }
+            run_supervised_stt_worker(
+                model_dir,
+                &execution_providers,
+                &runtime_options,
+                &cancel_generation,
+                request_rx,
+                event_tx,
+            );
         })
         .map_err(|error| SttError::speech_to_text(error.to_string()))?;
 
     Ok(request_tx)
 }
 
+/// Runs the worker until its request channel closes, reporting errors and panics as
+/// `WorkerEvent::WorkerCrashed`.
+pub(crate) fn run_supervised_stt_worker(
+    model_dir: PathBuf,
+    execution_providers: &[ExecutionProvider],
+    runtime_options: &RuntimeOptions,
+    cancel_generation: &AtomicU64,
+    request_rx: Receiver<WorkerRequest>,
+    event_tx: Sender<WorkerEvent>,
+) {
+    let error_tx = event_tx.clone();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_stt_worker(
+            model_dir,
+            execution_providers,
+            runtime_options,
+            cancel_generation,
+            request_rx,
+            event_tx,
+        )
+    }));
+    let reason = match result {
+        Ok(Ok(())) => return,
+        Ok(Err(error)) => error.to_string(),
+        Err(payload) => format!("STT worker panicked: {}", panic_message(payload.as_ref())),
+    };
+    let _ = error_tx.send_blocking(WorkerEvent::WorkerCrashed(reason));
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic payload")
+}
+
 fn run_stt_worker(
     model_dir: PathBuf,
-    use_gpu: bool,
+    execution_providers: &[ExecutionProvider],
+    runtime_options: &RuntimeOptions,
+    cancel_generation: &AtomicU64,
     request_rx: Receiver<WorkerRequest>,
     event_tx: Sender<WorkerEvent>,
 ) -> SttResult<()> {
     validate_model_dir(&model_dir)?;
-    let mut model = Nemotron::from_pretrained(&model_dir, execution_config(use_gpu))
-        .map_err(|error| SttError::speech_to_text(error.to_string()))?;
-    send_event(&event_tx, WorkerEvent::Ready)?;
+    let (mut model, active_provider) =
+        load_model(&model_dir, execution_providers, runtime_options, &event_tx)?;
+    let _ = record_model_last_used(&model_dir);
+    send_event(&event_tx, WorkerEvent::Ready(active_provider))?;
+    let mut diarizer = None;
 
     while let Ok(request) = request_rx.recv_blocking() {
         match request {
-            WorkerRequest::Transcribe(recording) => {
-                let result =
-                    prepare_audio(recording).and_then(|audio| transcribe_audio(&mut model, &audio));
+            WorkerRequest::Transcribe {
+                recording,
+                processing,
+                diarization_model,
+                generation,
+            } => {
+                if generation != cancel_generation.load(Ordering::Relaxed) {
+                    send_event(&event_tx, WorkerEvent::TranscriptDiscarded)?;
+                    continue;
+                }
+
+                let result = prepare_audio(recording)
+                    .and_then(|audio| processing.apply(audio))
+                    .and_then(|audio| {
+                        let transcript = transcribe_audio(&mut model, &audio)?;
+                        match &diarization_model {
+                            Some(model_path) => diarize_transcript(
+                                &mut diarizer,
+                                model_path,
+                                active_provider,
+                                runtime_options,
+                                &audio,
+                                transcript,
+                            ),
+                            None => Ok(transcript),
+                        }
+                    });
+                if generation != cancel_generation.load(Ordering::Relaxed) {
+                    send_event(&event_tx, WorkerEvent::TranscriptDiscarded)?;
+                    continue;
+                }
+
                 match result {
                     Ok(transcript) => send_event(&event_tx, WorkerEvent::Transcript(transcript))?,
                     Err(error) => send_event(&event_tx, WorkerEvent::Error(error.to_string()))?,
                 }
             }
+            WorkerRequest::Cancel { .. } => {}
         }
     }
 
     Ok(())
 }
 
-fn execution_config(use_gpu: bool) -> Option<ExecutionConfig> {
-    if use_gpu {
-        return Some(ExecutionConfig::new().with_custom_configure(|builder| {
-            Ok(builder.with_execution_providers([
-                ort::ep::CUDA::default().build().error_on_failure(),
-                ort::ep::CPU::default().build().error_on_failure(),
-            ])?)
-        }));
+fn load_model(
+    model_dir: &Path,
+    execution_providers: &[ExecutionProvider],
+    runtime_options: &RuntimeOptions,
+    event_tx: &Sender<WorkerEvent>,
+) -> SttResult<(Nemotron, ExecutionProvider)> {
+    let mut failures = Vec::new();
+
+    for &provider in execution_providers {
+        if provider == ExecutionProvider::Cpu {
+            break;
+        }
+
+        let Some(dispatch) = provider.dispatch() else {
+            failures.push(format!(
+                "{} is not included in this build; enable the `{}` cargo feature",
+                provider.label(),
+                provider.config_name()
+            ));
+            continue;
+        };
+
+        let dispatches = vec![
+            dispatch.error_on_failure(),
+            ort::ep::CPU::default().build().error_on_failure(),
+        ];
+        match load_with_provider(model_dir, provider, dispatches, runtime_options, event_tx) {
+            Ok(model) => {
+                report_provider_fallback(event_tx, &failures)?;
+                return Ok((model, provider));
+            }
+            Err(error) => failures.push(format!(
+                "{} {}",
+                provider.label(),
+                describe_provider_failure(&error)
+            )),
+        }
+    }
+
+    let dispatches = vec![ort::ep::CPU::default().build().error_on_failure()];
+    let model = load_with_provider(
+        model_dir,
+        ExecutionProvider::Cpu,
+        dispatches,
+        runtime_options,
+        event_tx,
+    )
+    .map_err(|cpu_error| {
+        if failures.is_empty() {
+            SttError::speech_to_text(cpu_error)
+        } else {
+            SttError::speech_to_text(format!(
+                "{cpu_error}; preferred providers had already failed: {}",
+                failures.join("; ")
+            ))
+        }
+    })?;
+    report_provider_fallback(event_tx, &failures)?;
+
+    Ok((model, ExecutionProvider::Cpu))
+}
+
+fn load_with_provider(
+    model_dir: &Path,
+    provider: ExecutionProvider,
+    dispatches: Vec<ExecutionProviderDispatch>,
+    runtime_options: &RuntimeOptions,
+    event_tx: &Sender<WorkerEvent>,
+) -> Result<Nemotron, String> {
+    let Some(cache_root) = runtime_options.optimized_model_cache() else {
+        let config = execution_config(dispatches, runtime_options, None, None);
+        return Nemotron::from_pretrained(model_dir, Some(config))
+            .map_err(|error| error.to_string());
+    };
+
+    let cache = OptimizedModelCache::new(
+        cache_root,
+        model_dir,
+        provider,
+        runtime_options.graph_optimization(),
+    );
+    if cache.is_current() {
+        // The cached graph is already optimized, so the session skips the optimizer passes.
+        let config = execution_config(
+            dispatches.clone(),
+            runtime_options,
+            Some(GraphOptimization::Disable),
+            None,
+        );
+        match Nemotron::from_pretrained(cache.dir(), Some(config)) {
+            Ok(model) => {
+                let _ = send_event(
+                    event_tx,
+                    WorkerEvent::OptimizedModelCache(format!(
+                        "loaded from {}",
+                        cache.dir().display()
+                    )),
+                );
+                return Ok(model);
+            }
+            Err(_) => cache.remove(),
+        }
+    }
+
+    let staged_cache = cache.prepare_staging().is_ok().then_some(&cache);
+    let config = execution_config(dispatches, runtime_options, None, staged_cache);
+    let model = Nemotron::from_pretrained(model_dir, Some(config)).map_err(|error| {
+        cache.discard_staging();
+        error.to_string()
+    })?;
+
+    let cache_status = match staged_cache.map(|cache| cache.commit(model_dir)) {
+        Some(Ok(())) => format!("written to {}", cache.dir().display()),
+        Some(Err(error)) => {
+            cache.discard_staging();
+            format!("write failed: {error}")
+        }
+        None => "staging directory could not be created".to_string(),
+    };
+    let _ = send_event(event_tx, WorkerEvent::OptimizedModelCache(cache_status));
+
+    Ok(model)
+}
+
+fn report_provider_fallback(event_tx: &Sender<WorkerEvent>, failures: &[String]) -> SttResult<()> {
+    if failures.is_empty() {
+        return Ok(());
     }
 
-    None
+    send_event(event_tx, WorkerEvent::ProviderFallback(failures.join("; ")))
+}
+
+fn execution_config(
+    dispatches: Vec<ExecutionProviderDispatch>,
+    runtime_options: &RuntimeOptions,
+    graph_optimization: Option<GraphOptimization>,
+    optimized_model_cache: Option<&OptimizedModelCache>,
+) -> ExecutionConfig {
+    let intra_op_threads = runtime_options.intra_op_threads();
+    let inter_op_threads = runtime_options.inter_op_threads();
+    let graph_optimization =
+        graph_optimization.unwrap_or_else(|| runtime_options.graph_optimization());
+    let optimized_model_cache = optimized_model_cache.cloned();
+
+    ExecutionConfig::new().with_custom_configure(move |builder| {
+        let mut builder = builder
+            .with_execution_providers(dispatches.clone())?
+            .with_optimization_level(graph_optimization.level())?;
+        if let Some(threads) = intra_op_threads {
+            builder = builder.with_intra_threads(threads)?;
+        }
+        if let Some(threads) = inter_op_threads {
+            builder = builder
+                .with_parallel_execution(threads > 1)?
+                .with_inter_threads(threads)?;
+        }
+
+        if let Some(optimized_path) = optimized_model_cache
+            .as_ref()
+            .and_then(OptimizedModelCache::next_staged_session_path)
+        {
+            let external_data_name = optimized_path
+                .file_name()
+                .map(|name| format!("{}.data", name.to_string_lossy()))
+                .unwrap_or_default();
+            builder = builder
+                .with_config_entry(
+                    "session.optimized_model_external_initializers_file_name",
+                    external_data_name,
+                )?
+                .with_optimized_model_path(optimized_path)?;
+        }
+
+        Ok(builder)
+    })
 }
 
 fn send_event(event_tx: &Sender<WorkerEvent>, event: WorkerEvent) -> SttResult<()> {
@@ -77,19 +331,87 @@ fn send_event(event_tx: &Sender<WorkerEvent>, event: WorkerEvent) -> SttResult<(
         .map_err(|_| SttError::worker_channel("UI event receiver has disconnected"))
 }
 
-fn transcribe_audio(model: &mut Nemotron, audio_16k_mono: &[f32]) -> SttResult<String> {
-    model
-        .transcribe_audio(audio_16k_mono)
-        .map_err(|error| SttError::speech_to_text(error.to_string()))
+/// Feeds the recording to the streaming decoder chunk by chunk so each piece of text can be
+/// placed in time.
+fn transcribe_audio(model: &mut Nemotron, audio_16k_mono: &[f32]) -> SttResult<Transcript> {
+    model.reset();
+    assemble_transcript(audio_16k_mono, |chunk| {
+        model
+            .transcribe_chunk(chunk)
+            .map_err(|error| SttError::speech_to_text(error.to_string()))
+    })
 }
 
-fn prepare_audio(recording: AudioRecording) -> SttResult<Vec<f32>> {
-    let (samples, sample_rate) = recording.into_parts();
-    let mut audio = if sample_rate == TARGET_SAMPLE_RATE {
-        samples
-    } else {
-        resample_to_16k(samples, sample_rate)?
+/// Passes `decode_chunk` full `DECODER_CHUNK_SAMPLES` chunks: the last one is padded with
+/// silence, and one more silent chunk flushes the decoder's lookahead.
+fn assemble_transcript(
+    audio_16k_mono: &[f32],
+    mut decode_chunk: impl FnMut(&[f32]) -> SttResult<String>,
+) -> SttResult<Transcript> {
+    let flush_chunk = [0.0; DECODER_CHUNK_SAMPLES];
+    let mut padded_chunk = Vec::new();
+    let mut assembler = TranscriptAssembler::default();
+
+    for (index, chunk) in audio_16k_mono
+        .chunks(DECODER_CHUNK_SAMPLES)
+        .chain([flush_chunk.as_slice()])
+        .enumerate()
+    {
+        let text = if chunk.len() < DECODER_CHUNK_SAMPLES {
+            padded_chunk.clear();
+            padded_chunk.extend_from_slice(chunk);
+            padded_chunk.resize(DECODER_CHUNK_SAMPLES, 0.0);
+            decode_chunk(&padded_chunk)?
+        } else {
+            decode_chunk(chunk)?
+        };
+        let start_sample = (index * DECODER_CHUNK_SAMPLES).min(audio_16k_mono.len());
+        let end_sample = (start_sample + chunk.len()).min(audio_16k_mono.len());
+        assembler.push_chunk(&text, sample_time(start_sample), sample_time(end_sample));
+    }
+
+    Ok(assembler.finish())
+}
+
+/// Labels the transcript's speakers, loading the diarization model on first use or when the
+/// configured model file changes.
+fn diarize_transcript(
+    diarizer: &mut Option<Diarizer>,
+    model_path: &Path,
+    provider: ExecutionProvider,
+    runtime_options: &RuntimeOptions,
+    audio_16k_mono: &[f32],
+    transcript: Transcript,
+) -> SttResult<Transcript> {
+    if diarizer
+        .as_ref()
+        .is_none_or(|loaded| loaded.model_path() != model_path)
+    {
+        // The diarization model follows the ASR model's provider and falls back to CPU quietly.
+        let mut dispatches = vec![ort::ep::CPU::default().build()];
+        if provider != ExecutionProvider::Cpu
+            && let Some(dispatch) = provider.dispatch()
+        {
+            dispatches.insert(0, dispatch);
+        }
+        let config = execution_config(dispatches, runtime_options, None, None);
+        *diarizer = Some(Diarizer::load(model_path, config)?);
+    }
+    let Some(diarizer) = diarizer else {
+        return Ok(transcript);
     };
+
+    let turns = diarizer.speaker_turns(audio_16k_mono)?;
+    Ok(assign_speakers(transcript, &turns))
+}
+
+fn sample_time(sample: usize) -> Duration {
+    Duration::from_secs_f64(sample as f64 / f64::from(TARGET_SAMPLE_RATE))
+}
+
+pub(crate) fn prepare_audio(recording: AudioRecording) -> SttResult<Vec<f32>> {
+    let (samples, sample_rate) = recording.into_parts();
+    let mut audio = resample(samples, sample_rate, TARGET_SAMPLE_RATE)?;
     sanitize_samples(&mut audio);
     Ok(audio)
 }
@@ -104,14 +426,18 @@ fn sanitize_samples(samples: &mut [f32]) {
     }
 }
 
-fn resample_to_16k(samples: Vec<f32>, source_sample_rate: u32) -> SttResult<Vec<f32>> {
-    if source_sample_rate == 0 {
+pub(crate) fn resample(
+    samples: Vec<f32>,
+    source_sample_rate: u32,
+    target_sample_rate: u32,
+) -> SttResult<Vec<f32>> {
+    if source_sample_rate == 0 || target_sample_rate == 0 {
         return Err(SttError::resampling(
-            "source sample rate must be greater than zero",
+            "source and target sample rates must be greater than zero",
         ));
     }
 
-    if samples.is_empty() {
+    if source_sample_rate == target_sample_rate || samples.is_empty() {
         return Ok(samples);
     }
 
@@ -122,7 +448,7 @@ fn resample_to_16k(samples: Vec<f32>, source_sample_rate: u32) -> SttResult<Vec<
         oversampling_factor: 256,
         window: WindowFunction::BlackmanHarris2,
     };
-    let ratio = f64::from(TARGET_SAMPLE_RATE) / f64::from(source_sample_rate);
+    let ratio = f64::from(target_sample_rate) / f64::from(source_sample_rate);
     let input_len = samples.len();
     let chunk_size = input_len.min(1024);
     let mut resampler =
@@ -139,3 +465,68 @@ fn resample_to_16k(samples: Vec<f32>, source_sample_rate: u32) -> SttResult<Vec<
     output_samples.truncate(frames_out);
     Ok(output_samples)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Stands in for the Nemotron decoder: returns one scripted piece of text per chunk.
+    fn scripted_decoder(
+        pieces: Vec<&'static str>,
+        chunk_lens: &mut Vec<usize>,
+    ) -> impl FnMut(&[f32]) -> SttResult<String> {
+        let mut pieces = pieces.into_iter();
+        move |chunk| {
+            chunk_lens.push(chunk.len());
+            Ok(pieces.next().unwrap_or_default().to_string())
+        }
+    }
+
+    #[test]
+    fn text_matches_the_concatenated_chunk_output() -> SttResult<()> {
+        let pieces = ["", " Hello", " wor", "ld.", " How are", " you?", ""];
+        let audio = vec![0.0; DECODER_CHUNK_SAMPLES * 5 + 100];
+        let mut chunk_lens = Vec::new();
+
+        let transcript =
+            assemble_transcript(&audio, scripted_decoder(pieces.to_vec(), &mut chunk_lens))?;
+
+        // A one-shot transcription is the concatenation of the chunk outputs.
+        let one_shot = pieces.concat();
+        assert_eq!(
+            transcript.text(),
+            one_shot.split_whitespace().collect::<Vec<_>>().join(" ")
+        );
+        assert_eq!(transcript.text(), "Hello world. How are you?");
+        Ok(())
+    }
+
+    #[test]
+    fn every_chunk_is_full_length_and_one_flush_chunk_follows() -> SttResult<()> {
+        let audio = vec![0.5; DECODER_CHUNK_SAMPLES * 2 + 1];
+        let mut chunk_lens = Vec::new();
+
+        assemble_transcript(&audio, scripted_decoder(Vec::new(), &mut chunk_lens))?;
+
+        assert_eq!(chunk_lens, vec![DECODER_CHUNK_SAMPLES; 4]);
+        Ok(())
+    }
+
+    #[test]
+    fn words_take_the_bounds_of_their_chunks() -> SttResult<()> {
+        let audio = vec![0.0; DECODER_CHUNK_SAMPLES * 2];
+        let mut chunk_lens = Vec::new();
+
+        let transcript = assemble_transcript(
+            &audio,
+            scripted_decoder(vec![" Hel", "lo there."], &mut chunk_lens),
+        )?;
+
+        let words = &transcript.segments[0].words;
+        assert_eq!(words[0].text, "Hello");
+        assert_eq!(words[0].start, Duration::ZERO);
+        assert_eq!(words[0].end, sample_time(DECODER_CHUNK_SAMPLES * 2));
+        assert_eq!(words[1].start, sample_time(DECODER_CHUNK_SAMPLES));
+        Ok(())
+    }
+}
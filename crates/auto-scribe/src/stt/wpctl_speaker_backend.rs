@@ -0,0 +1,62 @@
+use crate::stt::{SinkVolume, SpeakerBackend, SttError, SttResult, command_output};
+
+/// WirePlumber's `wpctl`, addressing sinks by PipeWire node id.
+#[derive(Debug, Default)]
+pub(crate) struct WpctlSpeakerBackend;
+
+impl SpeakerBackend for WpctlSpeakerBackend {
+    fn name(&self) -> &'static str {
+        "wpctl"
+    }
+
+    fn default_sink(&mut self) -> SttResult<String> {
+        // The first line reads "id 52, type PipeWire:Interface:Node".
+        let output = command_output("wpctl", &["inspect", "@DEFAULT_AUDIO_SINK@"])?;
+        output
+            .lines()
+            .next()
+            .and_then(|line| line.trim().strip_prefix("id "))
+            .and_then(|rest| rest.split(',').next())
+            .map(str::trim)
+            .filter(|id| !id.is_empty() && id.bytes().all(|byte| byte.is_ascii_digit()))
+            .map(str::to_string)
+            .ok_or_else(|| {
+                SttError::speaker_mute(format!("wpctl inspect printed no node id: {output:?}"))
+            })
+    }
+
+    fn is_muted(&mut self, sink: &str) -> SttResult<bool> {
+        let output = command_output("wpctl", &["get-volume", sink])?;
+        Ok(output.contains("[MUTED]"))
+    }
+
+    fn set_muted(&mut self, sink: &str, muted: bool) -> SttResult<()> {
+        command_output("wpctl", &["set-mute", sink, if muted { "1" } else { "0" }]).map(|_| ())
+    }
+
+    /// wpctl reports one volume for the node, such as "Volume: 0.40 [MUTED]".
+    fn volume(&mut self, sink: &str) -> SttResult<SinkVolume> {
+        let output = command_output("wpctl", &["get-volume", sink])?;
+        output
+            .strip_prefix("Volume:")
+            .and_then(|rest| rest.split_whitespace().next())
+            .and_then(|volume| volume.parse::<f64>().ok())
+            .filter(|volume| volume.is_finite() && *volume >= 0.0)
+            .map(|volume| {
+                SinkVolume::new(vec![(volume * f64::from(SinkVolume::NORM)).round() as u32])
+            })
+            .ok_or_else(|| {
+                SttError::speaker_mute(format!("unexpected wpctl get-volume output {output:?}"))
+            })
+    }
+
+    /// Sets every channel of the node to the loudest channel, so restoring a sink whose channels
+    /// differed, such as a balance set to one side, does not bring the balance back.
+    fn set_volume(&mut self, sink: &str, volume: &SinkVolume) -> SttResult<()> {
+        let Some(&level) = volume.channels().iter().max() else {
+            return Ok(());
+        };
+        let level = format!("{:.4}", f64::from(level) / f64::from(SinkVolume::NORM));
+        command_output("wpctl", &["set-volume", sink, &level]).map(|_| ())
+    }
+}
@@ -0,0 +1,333 @@
+use crate::{
+    MINIMUM_HEIGHT, MINIMUM_WIDTH,
+    diagnostics::{GpuReport, LibraryCheck, ProviderProbe, run_gpu_diagnostics_process},
+    hotkey::Controller,
+    icon::{APP_ID, window_icon},
+    stt::WorkerHealth,
+    windows::window_resize_handles,
+};
+
+use crate::error::{AppResult, ResultContext};
+use gpui::{
+    AnyElement, App, AppContext, ClipboardItem, Context, Entity, InteractiveElement, IntoElement,
+    ParentElement, Render, StatefulInteractiveElement, Styled, Window, WindowBounds, WindowHandle,
+    WindowOptions, div, px, rgb, size,
+};
+use gpui_component::{
+    ActiveTheme, IconName, Root, StyledExt, TitleBar,
+    button::{Button, ButtonVariants},
+    h_flex,
+    progress::Progress,
+    v_flex,
+};
+
+#[cfg(target_os = "linux")]
+use gpui::WindowDecorations;
+
+const DIAGNOSTICS_WINDOW_TITLE: &str = "GPU Diagnostics";
+const DIAGNOSTICS_WINDOW_WIDTH: f32 = 620.0;
+const DIAGNOSTICS_WINDOW_HEIGHT: f32 = 720.0;
+
+pub(crate) struct DiagnosticsWindow {
+    controller: Entity<Controller>,
+    report: Option<GpuReport>,
+    running: bool,
+    copied: bool,
+}
+
+impl DiagnosticsWindow {
+    pub(crate) fn new(controller: Entity<Controller>, cx: &mut Context<Self>) -> Self {
+        cx.observe(&controller, |_, _, cx| cx.notify()).detach();
+        let mut diagnostics_window = Self {
+            controller,
+            report: None,
+            running: false,
+            copied: false,
+        };
+        diagnostics_window.run(cx);
+        diagnostics_window
+    }
+
+    fn run(&mut self, cx: &mut Context<Self>) {
+        if self.running {
+            return;
+        }
+
+        self.running = true;
+        self.copied = false;
+        let diagnostics = cx
+            .background_executor()
+            .spawn(async move { run_gpu_diagnostics_process() });
+
+        cx.spawn(async move |this, cx| {
+            let report = diagnostics.await;
+            let _ = this.update(cx, |this, cx| {
+                this.report = Some(report);
+                this.running = false;
+                cx.notify();
+            });
+        })
+        .detach();
+        cx.notify();
+    }
+
+    fn copy_report(&mut self, cx: &mut Context<Self>) {
+        let Some(report) = &self.report else {
+            return;
+        };
+
+        let worker_health = self
+            .controller
+            .read_with(cx, |controller, _| controller.worker_health());
+        cx.write_to_clipboard(ClipboardItem::new_string(format!(
+            "{}\n\n{}",
+            report.to_text(),
+            worker_health.to_text()
+        )));
+        self.copied = true;
+        cx.notify();
+    }
+}
+
+impl Render for DiagnosticsWindow {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let title_bar = TitleBar::new();
+        #[cfg(not(target_os = "macos"))]
+        let title_bar = title_bar.child(DIAGNOSTICS_WINDOW_TITLE);
+
+        let worker_health = self
+            .controller
+            .read_with(cx, |controller, _| controller.worker_health());
+        let body = match &self.report {
+            Some(report) if !self.running => self.report_view(report, cx),
+            _ => v_flex()
+                .gap_2()
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child("Checking libraries and running test inference…"),
+                )
+                .child(Progress::new("gpu-diagnostics-progress").loading(true))
+                .into_any_element(),
+        };
+
+        v_flex()
+            .relative()
+            .size_full()
+            .bg(cx.theme().background)
+            .text_color(cx.theme().foreground)
+            .child(title_bar)
+            .child(
+                v_flex()
+                    .id("diagnostics-window-body")
+                    .size_full()
+                    .gap_4()
+                    .p_6()
+                    .border_t_1()
+                    .border_color(cx.theme().border)
+                    .overflow_y_scroll()
+                    .child(
+                        h_flex()
+                            .items_center()
+                            .justify_between()
+                            .gap_3()
+                            .child(div().text_xl().font_semibold().child("GPU Diagnostics"))
+                            .child(
+                                h_flex()
+                                    .gap_2()
+                                    .child(
+                                        Button::new("rerun-gpu-diagnostics")
+                                            .icon(IconName::Redo)
+                                            .label("Run again")
+                                            .disabled(self.running)
+                                            .on_click(cx.listener(|this, _, _, cx| this.run(cx))),
+                                    )
+                                    .child(
+                                        Button::new("copy-gpu-report")
+                                            .primary()
+                                            .icon(IconName::Copy)
+                                            .label(if self.copied {
+                                                "Copied"
+                                            } else {
+                                                "Copy report"
+                                            })
+                                            .disabled(self.running || self.report.is_none())
+                                            .on_click(
+                                                cx.listener(|this, _, _, cx| this.copy_report(cx)),
+                                            ),
+                                    ),
+                            ),
+                    )
+                    .child(body)
+                    .child(worker_health_section(&worker_health)),
+            )
+            .child(window_resize_handles(window))
+    }
+}
+
+impl DiagnosticsWindow {
+    fn report_view(&self, report: &GpuReport, cx: &mut Context<Self>) -> AnyElement {
+        let versions = &report.versions;
+        let mut version_rows = vec![
+            version_row("ONNX Runtime", Some(versions.onnx_runtime.clone())),
+            version_row("NVIDIA driver", versions.nvidia_driver.clone()),
+            version_row("CUDA driver API", versions.cuda_driver_api.clone()),
+            version_row("CUDA runtime", versions.cuda_runtime.clone()),
+        ];
+        if versions.gpus.is_empty() {
+            version_rows.push(version_row("GPU", None));
+        }
+        version_rows.extend(
+            versions
+                .gpus
+                .iter()
+                .map(|gpu| version_row("GPU", Some(gpu.clone()))),
+        );
+
+        v_flex()
+            .w_full()
+            .gap_4()
+            .child(
+                div()
+                    .text_sm()
+                    .font_semibold()
+                    .text_color(rgb(0x38bdf8))
+                    .child(report.summary()),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .overflow_hidden()
+                    .truncate()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(format!(
+                        "Executable directory: {}",
+                        report
+                            .executable_dir
+                            .as_ref()
+                            .map(|dir| dir.display().to_string())
+                            .unwrap_or_else(|| "unknown".to_string())
+                    )),
+            )
+            .child(section("Versions", version_rows))
+            .children(cfg!(feature = "cuda").then(|| {
+                section(
+                    "ONNX Runtime provider libraries",
+                    report.provider_libraries.iter().map(library_row).collect(),
+                )
+            }))
+            .children(cfg!(feature = "cuda").then(|| {
+                section(
+                    "CUDA libraries",
+                    report.cuda_libraries.iter().map(library_row).collect(),
+                )
+            }))
+            .child(section(
+                "Test inference",
+                report.provider_probes.iter().map(probe_row).collect(),
+            ))
+            .into_any_element()
+    }
+}
+
+pub(crate) fn open_diagnostics_window(
+    app: &mut App,
+    controller: Entity<Controller>,
+) -> AppResult<WindowHandle<Root>> {
+    let window_bounds = WindowBounds::centered(
+        size(px(DIAGNOSTICS_WINDOW_WIDTH), px(DIAGNOSTICS_WINDOW_HEIGHT)),
+        app,
+    );
+    let options = WindowOptions {
+        window_bounds: Some(window_bounds),
+        window_min_size: Some(size(px(MINIMUM_WIDTH), px(MINIMUM_HEIGHT))),
+        titlebar: Some(TitleBar::title_bar_options()),
+        app_id: Some(APP_ID.to_string()),
+        icon: Some(window_icon()),
+        #[cfg(target_os = "linux")]
+        window_decorations: Some(WindowDecorations::Client),
+        ..Default::default()
+    };
+
+    app.open_window(options, move |window, app| {
+        window.set_window_title(DIAGNOSTICS_WINDOW_TITLE);
+        let diagnostics_window = app.new(|cx| DiagnosticsWindow::new(controller, cx));
+        app.new(|cx| Root::new(diagnostics_window, window, cx))
+    })
+    .context("open diagnostics window")
+}
+
+fn section(title: &'static str, rows: Vec<AnyElement>) -> impl IntoElement {
+    v_flex()
+        .w_full()
+        .gap_1()
+        .border_1()
+        .border_color(rgb(0x334155))
+        .rounded_md()
+        .p_3()
+        .child(div().text_sm().font_semibold().child(title))
+        .children(rows)
+}
+
+fn worker_health_section(worker_health: &WorkerHealth) -> impl IntoElement {
+    section(
+        "STT worker",
+        worker_health
+            .rows()
+            .into_iter()
+            .map(|(label, value)| version_row(label, Some(value)))
+            .collect(),
+    )
+}
+
+fn version_row(label: &'static str, value: Option<String>) -> AnyElement {
+    let (value, color) = match value {
+        Some(value) => (value, rgb(0xe2e8f0)),
+        None => ("not detected".to_string(), rgb(0x94a3b8)),
+    };
+
+    h_flex()
+        .w_full()
+        .justify_between()
+        .gap_3()
+        .child(div().text_xs().text_color(rgb(0x94a3b8)).child(label))
+        .child(
+            div()
+                .min_w_0()
+                .text_xs()
+                .overflow_hidden()
+                .truncate()
+                .text_color(color)
+                .child(value),
+        )
+        .into_any_element()
+}
+
+fn library_row(check: &LibraryCheck) -> AnyElement {
+    check_row(check.name, check.error.clone(), "found".to_string())
+}
+
+fn probe_row(probe: &ProviderProbe) -> AnyElement {
+    match &probe.result {
+        Ok(elapsed) => check_row(
+            probe.provider,
+            None,
+            format!("ok in {} ms", elapsed.as_millis()),
+        ),
+        Err(error) => check_row(probe.provider, Some(error.clone()), String::new()),
+    }
+}
+
+fn check_row(name: &'static str, error: Option<String>, ok_label: String) -> AnyElement {
+    let (detail, color) = match error {
+        None => (ok_label, rgb(0x4ade80)),
+        Some(error) => (error, rgb(0xf87171)),
+    };
+
+    v_flex()
+        .w_full()
+        .child(div().text_xs().font_semibold().child(name))
+        .child(div().text_xs().text_color(color).child(detail))
+        .into_any_element()
+}
@@ -0,0 +1,368 @@
+use crate::{
+    MINIMUM_HEIGHT, MINIMUM_WIDTH,
+    export::ExportFormat,
+    hotkey::Controller,
+    icon::{APP_ID, window_icon},
+    stt::{HistoryEntry, TranscriptSegment},
+    windows::window_resize_handles,
+};
+
+use crate::error::{AppResult, ResultContext};
+use gpui::{
+    AnyElement, App, AppContext, ClipboardItem, Context, ElementId, Entity, InteractiveElement,
+    IntoElement, ParentElement, Render, StatefulInteractiveElement, Styled, Window, WindowBounds,
+    WindowHandle, WindowOptions, div, px, rgb, size,
+};
+use gpui_component::{
+    ActiveTheme, IconName, Root, StyledExt, TitleBar,
+    button::Button,
+    h_flex,
+    input::{Input, InputState},
+    v_flex,
+};
+use std::time::{Duration, SystemTime};
+
+#[cfg(target_os = "linux")]
+use gpui::WindowDecorations;
+
+const HISTORY_WINDOW_TITLE: &str = "Transcript History";
+const HISTORY_WINDOW_WIDTH: f32 = 620.0;
+const HISTORY_WINDOW_HEIGHT: f32 = 640.0;
+
+pub(crate) struct HistoryWindow {
+    controller: Entity<Controller>,
+    /// History entry id and speaker whose name is being edited in `speaker_name_input`.
+    renaming_speaker: Option<(usize, usize)>,
+    speaker_name_input: Entity<InputState>,
+}
+
+impl HistoryWindow {
+    pub(crate) fn new(
+        controller: Entity<Controller>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        cx.observe(&controller, |_, _, cx| cx.notify()).detach();
+        let speaker_name_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Speaker name"));
+        Self {
+            controller,
+            renaming_speaker: None,
+            speaker_name_input,
+        }
+    }
+
+    fn start_renaming_speaker(
+        &mut self,
+        entry_id: usize,
+        speaker: usize,
+        name: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.renaming_speaker = Some((entry_id, speaker));
+        self.speaker_name_input.update(cx, |input, cx| {
+            input.set_value(name, window, cx);
+            input.focus(window, cx);
+        });
+        cx.notify();
+    }
+
+    fn finish_renaming_speaker(&mut self, cx: &mut Context<Self>) {
+        let Some((entry_id, speaker)) = self.renaming_speaker.take() else {
+            return;
+        };
+
+        let name = self.speaker_name_input.read(cx).value().to_string();
+        self.controller.update(cx, |controller, cx| {
+            controller.rename_speaker(entry_id, speaker, &name, cx);
+        });
+        cx.notify();
+    }
+
+    fn entry_row(&self, entry: &HistoryEntry, cx: &mut Context<Self>) -> AnyElement {
+        let controller = &self.controller;
+        let entry_id = entry.id;
+        let text = entry.transcript.labelled_text();
+        let retranscribe_button = entry.recording_path.as_ref().map(|_| {
+            let controller = controller.clone();
+            Button::new(("retranscribe-history-recording", entry_id))
+                .icon(IconName::Redo)
+                .label("Re-transcribe")
+                .on_click(move |_, _, cx| {
+                    controller.update(cx, |controller, cx| {
+                        controller.retranscribe_recording(entry_id, cx);
+                    });
+                })
+        });
+
+        v_flex()
+            .w_full()
+            .gap_2()
+            .border_1()
+            .border_color(rgb(0x334155))
+            .rounded_md()
+            .p_3()
+            .child(
+                h_flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_3()
+                    .child(div().text_xs().text_color(rgb(0x94a3b8)).child(format!(
+                        "{} · {}",
+                        format_age(entry.completed_at),
+                        format_timestamp(entry.transcript.duration())
+                    )))
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(
+                                Button::new(("copy-history-transcript", entry_id))
+                                    .icon(IconName::Copy)
+                                    .label("Copy")
+                                    .on_click(move |_, _, cx| {
+                                        cx.write_to_clipboard(ClipboardItem::new_string(
+                                            text.clone(),
+                                        ));
+                                    }),
+                            )
+                            .children(retranscribe_button)
+                            .children(EXPORT_FORMATS.into_iter().map(|format| {
+                                let controller = controller.clone();
+                                Button::new((format.name(), entry_id))
+                                    .label(format.label())
+                                    .on_click(move |_, _, cx| {
+                                        controller.update(cx, |controller, cx| {
+                                            controller.export_transcript(entry_id, format, cx);
+                                        });
+                                    })
+                            })),
+                    ),
+            )
+            .children(self.speaker_row(entry, cx))
+            .children(
+                entry
+                    .transcript
+                    .segments
+                    .iter()
+                    .map(|segment| segment_row(segment, entry.transcript.speaker_name(segment))),
+            )
+            .into_any_element()
+    }
+
+    /// Speaker names of a diarized transcript; clicking one edits it.
+    fn speaker_row(&self, entry: &HistoryEntry, cx: &mut Context<Self>) -> Option<AnyElement> {
+        if entry.transcript.speakers.is_empty() {
+            return None;
+        }
+
+        let entry_id = entry.id;
+        if let Some((renaming_entry_id, _)) = self.renaming_speaker
+            && renaming_entry_id == entry_id
+        {
+            return Some(
+                h_flex()
+                    .w_full()
+                    .items_center()
+                    .gap_2()
+                    .child(div().flex_1().child(Input::new(&self.speaker_name_input)))
+                    .child(
+                        Button::new(("save-speaker-name", entry_id))
+                            .icon(IconName::Check)
+                            .label("Rename")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.finish_renaming_speaker(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new(("cancel-speaker-name", entry_id))
+                            .label("Cancel")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.renaming_speaker = None;
+                                cx.notify();
+                            })),
+                    )
+                    .into_any_element(),
+            );
+        }
+
+        Some(
+            h_flex()
+                .id(("history-speakers", entry_id))
+                .flex_wrap()
+                .items_center()
+                .gap_2()
+                .child(div().text_xs().text_color(rgb(0x94a3b8)).child("Speakers"))
+                .children(
+                    entry
+                        .transcript
+                        .speakers
+                        .iter()
+                        .enumerate()
+                        .map(|(speaker, name)| {
+                            let name = name.clone();
+                            Button::new(ElementId::Name(
+                                format!("rename-speaker-{entry_id}-{speaker}").into(),
+                            ))
+                            .label(name.clone())
+                            .on_click(cx.listener(
+                                move |this, _, window, cx| {
+                                    this.start_renaming_speaker(
+                                        entry_id,
+                                        speaker,
+                                        name.clone(),
+                                        window,
+                                        cx,
+                                    );
+                                },
+                            ))
+                        }),
+                )
+                .into_any_element(),
+        )
+    }
+}
+
+impl Render for HistoryWindow {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let (history, status) = self.controller.read_with(cx, |controller, _| {
+            (
+                controller.transcript_history(),
+                controller.snapshot().status,
+            )
+        });
+
+        let title_bar = TitleBar::new();
+        #[cfg(not(target_os = "macos"))]
+        let title_bar = title_bar.child(HISTORY_WINDOW_TITLE);
+
+        let entry_rows = if history.is_empty() {
+            vec![
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child("No transcripts yet in this session")
+                    .into_any_element(),
+            ]
+        } else {
+            history
+                .iter()
+                .map(|entry| self.entry_row(entry, cx))
+                .collect()
+        };
+
+        v_flex()
+            .relative()
+            .size_full()
+            .bg(cx.theme().background)
+            .text_color(cx.theme().foreground)
+            .child(title_bar)
+            .child(
+                v_flex()
+                    .id("history-window-body")
+                    .size_full()
+                    .gap_3()
+                    .p_6()
+                    .border_t_1()
+                    .border_color(cx.theme().border)
+                    .overflow_y_scroll()
+                    .child(
+                        v_flex()
+                            .gap_1()
+                            .child(div().text_xl().font_semibold().child("History"))
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(status),
+                            ),
+                    )
+                    .children(entry_rows),
+            )
+            .child(window_resize_handles(window))
+    }
+}
+
+/// Plain text is already covered by the Copy button.
+const EXPORT_FORMATS: [ExportFormat; 4] = [
+    ExportFormat::Srt,
+    ExportFormat::WebVtt,
+    ExportFormat::Json,
+    ExportFormat::Markdown,
+];
+
+fn segment_row(segment: &TranscriptSegment, speaker: Option<&str>) -> AnyElement {
+    h_flex()
+        .w_full()
+        .items_start()
+        .gap_3()
+        .child(
+            div()
+                .flex_none()
+                .text_xs()
+                .text_color(rgb(0x94a3b8))
+                .child(format_timestamp(segment.start)),
+        )
+        .children(speaker.map(|speaker| {
+            div()
+                .flex_none()
+                .text_xs()
+                .font_semibold()
+                .text_color(rgb(0x38bdf8))
+                .child(speaker.to_string())
+        }))
+        .child(
+            h_flex().flex_wrap().gap_1().children(
+                segment
+                    .words
+                    .iter()
+                    .map(|word| div().text_sm().child(word.text.clone())),
+            ),
+        )
+        .into_any_element()
+}
+
+pub(crate) fn open_history_window(
+    app: &mut App,
+    controller: Entity<Controller>,
+) -> AppResult<WindowHandle<Root>> {
+    let window_bounds = WindowBounds::centered(
+        size(px(HISTORY_WINDOW_WIDTH), px(HISTORY_WINDOW_HEIGHT)),
+        app,
+    );
+    let options = WindowOptions {
+        window_bounds: Some(window_bounds),
+        window_min_size: Some(size(px(MINIMUM_WIDTH), px(MINIMUM_HEIGHT))),
+        titlebar: Some(TitleBar::title_bar_options()),
+        app_id: Some(APP_ID.to_string()),
+        icon: Some(window_icon()),
+        #[cfg(target_os = "linux")]
+        window_decorations: Some(WindowDecorations::Client),
+        ..Default::default()
+    };
+
+    app.open_window(options, move |window, app| {
+        window.set_window_title(HISTORY_WINDOW_TITLE);
+        let history_window = app.new(|cx| HistoryWindow::new(controller, window, cx));
+        app.new(|cx| Root::new(history_window, window, cx))
+    })
+    .context("open history window")
+}
+
+fn format_timestamp(offset: Duration) -> String {
+    let tenths = offset.as_millis() / 100;
+    format!("{}:{:02}.{}", tenths / 600, tenths / 10 % 60, tenths % 10)
+}
+
+fn format_age(completed_at: SystemTime) -> String {
+    let Ok(elapsed) = SystemTime::now().duration_since(completed_at) else {
+        return "just now".to_string();
+    };
+
+    match elapsed.as_secs() / 60 {
+        0 => "just now".to_string(),
+        1 => "1 minute ago".to_string(),
+        minutes if minutes < 60 => format!("{minutes} minutes ago"),
+        minutes => format!("{} h {} min ago", minutes / 60, minutes % 60),
+    }
+}
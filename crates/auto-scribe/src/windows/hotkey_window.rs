@@ -5,8 +5,9 @@ use crate::windows::{
 };
 
 use gpui::{
-    App, AppContext, Context, IntoElement, ParentElement, Pixels, Render, Styled, Window,
-    WindowBackgroundAppearance, WindowHandle, WindowKind, WindowOptions, div, px, rgb, rgba, size,
+    App, AppContext, Context, InteractiveElement, IntoElement, ParentElement, Pixels, Render,
+    StatefulInteractiveElement, Styled, WeakEntity, Window, WindowBackgroundAppearance,
+    WindowHandle, WindowKind, WindowOptions, div, px, rgb, rgba, size,
 };
 use gpui_component::StyledExt;
 
@@ -19,24 +20,32 @@ use gpui::WindowDecorations;
 const HOTKEY_WINDOW_TITLE: &str = "Hotkey Overlay";
 
 pub(crate) struct HotkeyWindow {
+    controller: WeakEntity<Controller>,
     backend_kind: BackendKind,
     is_visible: bool,
+    cancellable: bool,
     stt_label: String,
     transcript: String,
+    /// Where the last transcript went, shown under it.
+    delivery_label: &'static str,
 }
 
 impl HotkeyWindow {
     pub(crate) fn new(
+        controller: WeakEntity<Controller>,
         backend_kind: BackendKind,
         stt_label: String,
         transcript: String,
         _: &mut Context<Self>,
     ) -> Self {
         Self {
+            controller,
             backend_kind,
             is_visible: true,
+            cancellable: false,
             stt_label,
             transcript,
+            delivery_label: "Copied to clipboard",
         }
     }
 
@@ -64,21 +73,48 @@ impl HotkeyWindow {
         &mut self,
         stt_label: String,
         transcript: String,
+        cancellable: bool,
         cx: &mut Context<Self>,
     ) {
         self.stt_label = stt_label;
         self.transcript = transcript;
+        self.cancellable = cancellable;
         cx.notify();
     }
+
+    pub(crate) fn set_delivery_label(
+        &mut self,
+        delivery_label: &'static str,
+        cx: &mut Context<Self>,
+    ) {
+        self.delivery_label = delivery_label;
+        cx.notify();
+    }
+
+    fn cancel(&mut self, cx: &mut Context<Self>) {
+        if !self.cancellable {
+            return;
+        }
+
+        let _ = self.controller.update(cx, |controller, cx| {
+            controller.cancel(cx);
+        });
+    }
 }
 
 impl Render for HotkeyWindow {
-    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         if !self.is_visible {
-            return div().size_full().hidden();
+            return div().id("hotkey-overlay").size_full().hidden();
         }
 
+        let cancel_hint = match self.backend_kind {
+            BackendKind::GlobalHotkey => "Click or press Esc to cancel",
+            BackendKind::WaylandPortal => "Click or use the cancel shortcut to cancel",
+        };
+
         let mut container = div()
+            .id("hotkey-overlay")
             .size_full()
             .flex()
             .flex_col()
@@ -108,6 +144,13 @@ impl Render for HotkeyWindow {
                     .child(self.backend_kind.label()),
             );
 
+        if self.cancellable {
+            container = container
+                .cursor_pointer()
+                .on_click(cx.listener(|this, _, _, cx| this.cancel(cx)))
+                .child(div().text_xs().text_color(rgb(0xfca5a5)).child(cancel_hint));
+        }
+
         if !self.transcript.is_empty() {
             container = container
                 .child(
@@ -128,7 +171,7 @@ impl Render for HotkeyWindow {
                     div()
                         .text_xs()
                         .text_color(rgb(0x86efac))
-                        .child("Copied to clipboard"),
+                        .child(self.delivery_label),
                 );
         }
 
@@ -138,6 +181,7 @@ impl Render for HotkeyWindow {
 
 pub(crate) fn open_hotkey_window(
     app: &mut App,
+    controller: WeakEntity<Controller>,
     backend_kind: BackendKind,
     stt_label: String,
     transcript: String,
@@ -148,6 +192,7 @@ pub(crate) fn open_hotkey_window(
     if app.compositor_name() == "Wayland" {
         return open_hotkey_window_with_kind(
             app,
+            controller,
             backend_kind,
             stt_label,
             transcript,
@@ -159,6 +204,7 @@ pub(crate) fn open_hotkey_window(
 
     open_hotkey_window_with_kind(
         app,
+        controller,
         backend_kind,
         stt_label,
         transcript,
@@ -169,6 +215,7 @@ pub(crate) fn open_hotkey_window(
 
 fn open_hotkey_window_with_kind(
     app: &mut App,
+    controller: WeakEntity<Controller>,
     backend_kind: BackendKind,
     stt_label: String,
     transcript: String,
@@ -179,7 +226,7 @@ fn open_hotkey_window_with_kind(
 
     app.open_window(options, move |window, app| {
         window.set_window_title(HOTKEY_WINDOW_TITLE);
-        app.new(|cx| HotkeyWindow::new(backend_kind, stt_label, transcript, cx))
+        app.new(|cx| HotkeyWindow::new(controller, backend_kind, stt_label, transcript, cx))
     })
     .context("open hotkey overlay window")
 }
@@ -2,6 +2,7 @@ use crate::{
     MINIMUM_HEIGHT, MINIMUM_WIDTH, WINDOW_TITLE,
     hotkey::Controller,
     icon::{APP_ID, window_icon},
+    stt::{ExecutionProvider, SpeakerMode},
     windows::window_resize_handles,
 };
 
@@ -92,6 +93,10 @@ impl Render for MainWindow {
                         "Worker",
                         if snapshot.stt_worker_ready {
                             "ready"
+                        } else if snapshot.stt_model_unloaded {
+                            "unloaded"
+                        } else if snapshot.stt_worker_restarting {
+                            "restarting after crash"
                         } else {
                             "loading"
                         },
@@ -104,9 +109,35 @@ impl Render for MainWindow {
                             "unavailable"
                         },
                     ))
-                    .child(self.use_gpu_control(&snapshot, cx))
-                    .child(self.auto_mute_control(&snapshot, cx))
+                    .child(self.execution_provider_control(&snapshot, cx))
+                    .child(status_row(
+                        "Execution Providers",
+                        snapshot.stt_execution_providers.clone(),
+                    ))
+                    .child(status_row(
+                        "Active Provider",
+                        snapshot.stt_active_provider.unwrap_or("not loaded"),
+                    ))
+                    .child(provider_fallback_indicator(&snapshot))
+                    .child(status_row(
+                        "ONNX Threads",
+                        snapshot.stt_onnx_threads.clone(),
+                    ))
+                    .child(status_row(
+                        "Graph Optimization",
+                        snapshot.stt_graph_optimization,
+                    ))
+                    .child(status_row(
+                        "Optimized Model Cache",
+                        snapshot.stt_optimized_model_cache.clone(),
+                    ))
+                    .child(self.speaker_mode_control(&snapshot, cx))
+                    .child(self.pause_media_control(&snapshot))
                     .child(model_download_control)
+                    .child(self.models_control(cx))
+                    .child(self.history_control(cx))
+                    .child(self.output_profiles_control(cx))
+                    .child(self.meeting_control(&snapshot, cx))
                     .child(
                         div()
                             .text_sm()
@@ -137,12 +168,36 @@ impl Render for MainWindow {
 }
 
 impl MainWindow {
-    fn use_gpu_control(
+    /// One toggle per accelerator compiled into this build; turning one on puts it just ahead of CPU.
+    fn execution_provider_control(
         &self,
         snapshot: &crate::hotkey::Snapshot,
-        _cx: &mut Context<Self>,
+        cx: &mut Context<Self>,
     ) -> impl IntoElement {
-        let controller = self.controller.clone();
+        let provider_buttons = ExecutionProvider::ALL
+            .into_iter()
+            .filter(|provider| *provider != ExecutionProvider::Cpu && provider.is_compiled())
+            .enumerate()
+            .map(|(index, provider)| {
+                let button = Button::new(("execution-provider", index))
+                    .label(provider.label())
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.controller.update(cx, |controller, cx| {
+                            controller.toggle_execution_provider(provider, cx);
+                        });
+                    }));
+                if snapshot.stt_enabled_providers.contains(&provider) {
+                    button.primary()
+                } else {
+                    button.ghost()
+                }
+            })
+            .collect::<Vec<_>>();
+        let label = if provider_buttons.is_empty() {
+            "CPU-only build"
+        } else {
+            "Accelerators"
+        };
 
         h_flex()
             .w_full()
@@ -154,23 +209,69 @@ impl MainWindow {
             .rounded_md()
             .px_3()
             .py_2()
-            .child(div().text_sm().text_color(rgb(0x94a3b8)).child("Use GPU"))
+            .child(div().text_sm().text_color(rgb(0x94a3b8)).child(label))
             .child(
-                Switch::new("use-gpu")
-                    .checked(snapshot.stt_use_gpu)
-                    .on_click(move |enabled, _, cx| {
-                        controller.update(cx, |controller, cx| {
-                            controller.set_use_gpu(*enabled, cx);
-                        });
-                    }),
+                h_flex()
+                    .items_center()
+                    .gap_3()
+                    .child(
+                        Button::new("gpu-diagnostics")
+                            .ghost()
+                            .icon(IconName::Info)
+                            .label("Diagnostics")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.controller.update(cx, |controller, cx| {
+                                    controller.open_diagnostics_window(cx);
+                                });
+                            })),
+                    )
+                    .children(provider_buttons),
             )
     }
 
-    fn auto_mute_control(
+    fn speaker_mode_control(
         &self,
         snapshot: &crate::hotkey::Snapshot,
-        _cx: &mut Context<Self>,
+        cx: &mut Context<Self>,
     ) -> impl IntoElement {
+        let mode_buttons = SpeakerMode::ALL
+            .into_iter()
+            .enumerate()
+            .map(|(index, mode)| {
+                let button = Button::new(("speaker-mode", index))
+                    .label(mode.label())
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.controller.update(cx, |controller, cx| {
+                            controller.set_speaker_mode(mode, cx);
+                        });
+                    }));
+                if mode == snapshot.stt_speaker_mode {
+                    button.primary()
+                } else {
+                    button.ghost()
+                }
+            });
+
+        h_flex()
+            .w_full()
+            .items_center()
+            .justify_between()
+            .gap_3()
+            .border_1()
+            .border_color(rgb(0x334155))
+            .rounded_md()
+            .px_3()
+            .py_2()
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(rgb(0x94a3b8))
+                    .child("Speakers While Recording"),
+            )
+            .child(h_flex().items_center().gap_1().children(mode_buttons))
+    }
+
+    fn pause_media_control(&self, snapshot: &crate::hotkey::Snapshot) -> impl IntoElement {
         let controller = self.controller.clone();
 
         h_flex()
@@ -187,19 +288,148 @@ impl MainWindow {
                 div()
                     .text_sm()
                     .text_color(rgb(0x94a3b8))
-                    .child("Auto-Mute Speakers"),
+                    .child("Pause Media Players"),
             )
             .child(
-                Switch::new("auto-mute-speakers")
-                    .checked(snapshot.stt_auto_mute_speakers)
+                Switch::new("pause-media")
+                    .checked(snapshot.stt_pause_media)
                     .on_click(move |enabled, _, cx| {
                         controller.update(cx, |controller, cx| {
-                            controller.set_auto_mute_speakers(*enabled, cx);
+                            controller.set_pause_media(*enabled, cx);
                         });
                     }),
             )
     }
 
+    fn models_control(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .items_center()
+            .justify_between()
+            .gap_3()
+            .border_1()
+            .border_color(rgb(0x334155))
+            .rounded_md()
+            .px_3()
+            .py_2()
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(rgb(0x94a3b8))
+                    .child("Installed Models"),
+            )
+            .child(
+                Button::new("manage-models")
+                    .icon(IconName::Folder)
+                    .label("Manage models")
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.controller
+                            .update(cx, |controller, cx| controller.open_models_window(cx));
+                    })),
+            )
+    }
+
+    fn history_control(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .items_center()
+            .justify_between()
+            .gap_3()
+            .border_1()
+            .border_color(rgb(0x334155))
+            .rounded_md()
+            .px_3()
+            .py_2()
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(rgb(0x94a3b8))
+                    .child("Transcript History"),
+            )
+            .child(
+                Button::new("open-history")
+                    .icon(IconName::BookOpen)
+                    .label("Open history")
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.controller
+                            .update(cx, |controller, cx| controller.open_history_window(cx));
+                    })),
+            )
+    }
+
+    fn output_profiles_control(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .items_center()
+            .justify_between()
+            .gap_3()
+            .border_1()
+            .border_color(rgb(0x334155))
+            .rounded_md()
+            .px_3()
+            .py_2()
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(rgb(0x94a3b8))
+                    .child("Output Profiles"),
+            )
+            .child(
+                Button::new("open-output-profiles")
+                    .icon(IconName::Settings)
+                    .label("Edit profiles")
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.controller.update(cx, |controller, cx| {
+                            controller.open_output_profiles_window(cx);
+                        });
+                    })),
+            )
+    }
+
+    fn meeting_control(
+        &self,
+        snapshot: &crate::hotkey::Snapshot,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let button = if snapshot.stt_meeting_label.is_some() {
+            Button::new("stop-meeting")
+                .icon(IconName::CircleX)
+                .label("Stop meeting")
+                .on_click(cx.listener(|this, _, _, cx| {
+                    this.controller
+                        .update(cx, |controller, cx| controller.stop_meeting(cx));
+                }))
+        } else {
+            Button::new("start-meeting")
+                .icon(IconName::Calendar)
+                .label("Start meeting")
+                .on_click(cx.listener(|this, _, _, cx| {
+                    this.controller
+                        .update(cx, |controller, cx| controller.start_meeting(cx));
+                }))
+        };
+
+        h_flex()
+            .w_full()
+            .items_center()
+            .justify_between()
+            .gap_3()
+            .border_1()
+            .border_color(rgb(0x334155))
+            .rounded_md()
+            .px_3()
+            .py_2()
+            .child(
+                div().min_w_0().text_sm().text_color(rgb(0x94a3b8)).child(
+                    snapshot
+                        .stt_meeting_label
+                        .clone()
+                        .unwrap_or_else(|| "Meeting Transcript".to_string()),
+                ),
+            )
+            .child(button)
+    }
+
     fn model_download_control(
         &self,
         snapshot: &crate::hotkey::Snapshot,
@@ -277,18 +507,52 @@ impl MainWindow {
                         ),
                 )
                 .child(
-                    Button::new("download-model")
-                        .primary()
-                        .icon(IconName::ArrowDown)
-                        .label("Download model")
-                        .on_click(cx.listener(|this, _, _, cx| {
-                            this.controller
-                                .update(cx, |controller, cx| controller.download_model(cx));
-                        })),
+                    h_flex()
+                        .gap_2()
+                        .child(
+                            Button::new("import-model")
+                                .icon(IconName::Folder)
+                                .label("Import model…")
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.controller
+                                        .update(cx, |controller, cx| controller.import_model(cx));
+                                })),
+                        )
+                        .child(
+                            Button::new("download-model")
+                                .primary()
+                                .icon(IconName::ArrowDown)
+                                .label("Download model")
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.controller
+                                        .update(cx, |controller, cx| controller.download_model(cx));
+                                })),
+                        ),
                 )
                 .into_any_element();
         }
 
+        if snapshot.stt_model_importing {
+            return v_flex()
+                .w_full()
+                .gap_3()
+                .border_1()
+                .border_color(rgb(0x334155))
+                .rounded_md()
+                .p_3()
+                .child(div().text_sm().font_semibold().child("Importing model"))
+                .child(
+                    div()
+                        .text_xs()
+                        .overflow_hidden()
+                        .truncate()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(format!("Target: {}", snapshot.stt_model_dir)),
+                )
+                .child(Progress::new("model-import-progress").loading(true))
+                .into_any_element();
+        }
+
         div().hidden().into_any_element()
     }
 
@@ -350,6 +614,34 @@ pub(crate) fn open_main_window(
     Ok(())
 }
 
+fn provider_fallback_indicator(snapshot: &crate::hotkey::Snapshot) -> AnyElement {
+    let Some(reason) = snapshot.stt_provider_fallback.clone() else {
+        return div().hidden().into_any_element();
+    };
+    let title = match snapshot.stt_active_provider {
+        Some("CPU") | None => "GPU unavailable, using CPU".to_string(),
+        Some(active_provider) => format!("Preferred provider unavailable, using {active_provider}"),
+    };
+
+    v_flex()
+        .w_full()
+        .gap_1()
+        .border_1()
+        .border_color(rgb(0xb45309))
+        .rounded_md()
+        .px_3()
+        .py_2()
+        .child(
+            div()
+                .text_sm()
+                .font_semibold()
+                .text_color(rgb(0xfbbf24))
+                .child(title),
+        )
+        .child(div().text_xs().text_color(rgb(0x94a3b8)).child(reason))
+        .into_any_element()
+}
+
 fn status_row(label: &'static str, value: impl Into<String>) -> impl IntoElement {
     div()
         .flex()
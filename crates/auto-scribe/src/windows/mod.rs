@@ -1,6 +1,10 @@
+mod diagnostics_window;
+mod history_window;
 mod hotkey_window;
 mod hotkey_window_placement;
 mod main_window;
+mod models_window;
+mod output_profiles_window;
 mod resize;
 #[cfg(target_os = "linux")]
 mod xrandr_geometry;
@@ -9,9 +13,13 @@ mod xrandr_size;
 
 // ---------------------------------------------------------------------------------------------- //
 
+pub(crate) use diagnostics_window::open_diagnostics_window;
+pub(crate) use history_window::open_history_window;
 pub(crate) use hotkey_window::{HotkeyWindow, open_hotkey_window};
 pub(crate) use hotkey_window_placement::{HotkeyWindowPlacement, hotkey_window_placement};
 pub(crate) use main_window::open_main_window;
+pub(crate) use models_window::open_models_window;
+pub(crate) use output_profiles_window::open_output_profiles_window;
 pub(crate) use resize::window_resize_handles;
 #[cfg(target_os = "linux")]
 pub(crate) use xrandr_geometry::XrandrGeometry;
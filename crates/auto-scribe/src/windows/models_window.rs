@@ -0,0 +1,336 @@
+use crate::{
+    MINIMUM_HEIGHT, MINIMUM_WIDTH,
+    hotkey::Controller,
+    icon::{APP_ID, window_icon},
+    stt::InstalledModel,
+    windows::window_resize_handles,
+};
+
+use crate::error::{AppResult, ResultContext};
+use gpui::{
+    AnyElement, App, AppContext, Context, Entity, InteractiveElement, IntoElement, ParentElement,
+    Render, StatefulInteractiveElement, Styled, Window, WindowBounds, WindowHandle, WindowOptions,
+    div, px, rgb, size,
+};
+use gpui_component::{
+    ActiveTheme, IconName, Root, StyledExt, TitleBar,
+    button::{Button, ButtonVariants},
+    h_flex, v_flex,
+};
+use std::{path::PathBuf, time::SystemTime};
+
+#[cfg(target_os = "linux")]
+use gpui::WindowDecorations;
+
+const MODELS_WINDOW_TITLE: &str = "Installed Models";
+const MODELS_WINDOW_WIDTH: f32 = 560.0;
+const MODELS_WINDOW_HEIGHT: f32 = 480.0;
+
+pub(crate) struct ModelsWindow {
+    controller: Entity<Controller>,
+    /// The model whose Delete button was clicked and now waits for confirmation.
+    pending_delete: Option<PathBuf>,
+}
+
+impl ModelsWindow {
+    pub(crate) fn new(controller: Entity<Controller>, cx: &mut Context<Self>) -> Self {
+        cx.observe(&controller, |_, _, cx| cx.notify()).detach();
+        Self {
+            controller,
+            pending_delete: None,
+        }
+    }
+}
+
+impl Render for ModelsWindow {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let (installed_models, models_root, status) =
+            self.controller.read_with(cx, |controller, _| {
+                (
+                    controller.installed_models(),
+                    controller.models_root(),
+                    controller.snapshot().stt_status,
+                )
+            });
+        let total_bytes = installed_models
+            .iter()
+            .map(|installed_model| installed_model.size_bytes)
+            .sum::<u64>();
+
+        let title_bar = TitleBar::new();
+        #[cfg(not(target_os = "macos"))]
+        let title_bar = title_bar.child(MODELS_WINDOW_TITLE);
+
+        let model_rows = if installed_models.is_empty() {
+            vec![
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child("No models installed")
+                    .into_any_element(),
+            ]
+        } else {
+            installed_models
+                .iter()
+                .enumerate()
+                .map(|(index, installed_model)| self.model_row(index, installed_model, cx))
+                .collect()
+        };
+
+        v_flex()
+            .relative()
+            .size_full()
+            .bg(cx.theme().background)
+            .text_color(cx.theme().foreground)
+            .child(title_bar)
+            .child(
+                v_flex()
+                    .id("models-window-body")
+                    .size_full()
+                    .gap_3()
+                    .p_6()
+                    .border_t_1()
+                    .border_color(cx.theme().border)
+                    .overflow_y_scroll()
+                    .child(
+                        h_flex()
+                            .items_center()
+                            .justify_between()
+                            .gap_3()
+                            .child(
+                                v_flex()
+                                    .min_w_0()
+                                    .gap_1()
+                                    .child(div().text_xl().font_semibold().child("Models"))
+                                    .child(
+                                        div()
+                                            .text_xs()
+                                            .overflow_hidden()
+                                            .truncate()
+                                            .text_color(cx.theme().muted_foreground)
+                                            .child(format!(
+                                                "{} · {} on disk",
+                                                models_root.display(),
+                                                format_bytes(total_bytes)
+                                            )),
+                                    ),
+                            )
+                            .child(
+                                Button::new("refresh-models")
+                                    .icon(IconName::Redo)
+                                    .label("Refresh")
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.controller.update(cx, |controller, cx| {
+                                            controller.refresh_models(cx);
+                                        });
+                                    })),
+                            ),
+                    )
+                    .children(model_rows)
+                    .child(div().text_sm().text_color(rgb(0x38bdf8)).child(status)),
+            )
+            .child(window_resize_handles(window))
+    }
+}
+
+impl ModelsWindow {
+    fn model_row(
+        &self,
+        index: usize,
+        installed_model: &InstalledModel,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let validation_label = match &installed_model.validation_error {
+            Some(error) => format!("Invalid: {error}"),
+            None => "Valid".to_string(),
+        };
+        let last_used_label = installed_model
+            .last_used
+            .map(format_last_used)
+            .unwrap_or_else(|| "never".to_string());
+        let open_path = installed_model.path.clone();
+        let delete_path = installed_model.path.clone();
+
+        let mut actions = h_flex().gap_2().child(
+            Button::new(("open-model-folder", index))
+                .icon(IconName::FolderOpen)
+                .label("Open folder")
+                .on_click(move |_, _, cx| cx.open_with_system(&open_path)),
+        );
+
+        if installed_model.active {
+            actions = actions.child(
+                Button::new(("redownload-model", index))
+                    .icon(IconName::ArrowDown)
+                    .label("Re-download")
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.controller.update(cx, |controller, cx| {
+                            controller.redownload_model(cx);
+                        });
+                    })),
+            );
+        }
+
+        if self.pending_delete.as_ref() == Some(&installed_model.path) {
+            actions = actions
+                .child(
+                    Button::new(("confirm-delete-model", index))
+                        .danger()
+                        .icon(IconName::Delete)
+                        .label("Confirm delete")
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.pending_delete = None;
+                            this.controller.update(cx, |controller, cx| {
+                                controller.delete_model(&delete_path, cx);
+                            });
+                        })),
+                )
+                .child(
+                    Button::new(("cancel-delete-model", index))
+                        .ghost()
+                        .label("Cancel")
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.pending_delete = None;
+                            cx.notify();
+                        })),
+                );
+        } else {
+            actions = actions.child(
+                Button::new(("delete-model", index))
+                    .danger()
+                    .icon(IconName::Delete)
+                    .label("Delete")
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.pending_delete = Some(delete_path.clone());
+                        cx.notify();
+                    })),
+            );
+        }
+
+        v_flex()
+            .w_full()
+            .gap_2()
+            .border_1()
+            .border_color(rgb(0x334155))
+            .rounded_md()
+            .p_3()
+            .child(
+                h_flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_3()
+                    .child(
+                        h_flex()
+                            .min_w_0()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .font_semibold()
+                                    .overflow_hidden()
+                                    .truncate()
+                                    .child(installed_model.name.clone()),
+                            )
+                            .children(installed_model.active.then(active_badge)),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_semibold()
+                            .child(format_bytes(installed_model.size_bytes)),
+                    ),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .overflow_hidden()
+                    .truncate()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(installed_model.path.display().to_string()),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(if installed_model.validation_error.is_some() {
+                        rgb(0xf87171)
+                    } else {
+                        rgb(0x94a3b8)
+                    })
+                    .child(validation_label),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(rgb(0x94a3b8))
+                    .child(format!("Last used: {last_used_label}")),
+            )
+            .child(actions)
+            .into_any_element()
+    }
+}
+
+fn active_badge() -> impl IntoElement {
+    div()
+        .px_2()
+        .rounded_md()
+        .bg(rgb(0x1e3a8a))
+        .text_xs()
+        .text_color(rgb(0xbfdbfe))
+        .child("active")
+}
+
+pub(crate) fn open_models_window(
+    app: &mut App,
+    controller: Entity<Controller>,
+) -> AppResult<WindowHandle<Root>> {
+    let window_bounds =
+        WindowBounds::centered(size(px(MODELS_WINDOW_WIDTH), px(MODELS_WINDOW_HEIGHT)), app);
+    let options = WindowOptions {
+        window_bounds: Some(window_bounds),
+        window_min_size: Some(size(px(MINIMUM_WIDTH), px(MINIMUM_HEIGHT))),
+        titlebar: Some(TitleBar::title_bar_options()),
+        app_id: Some(APP_ID.to_string()),
+        icon: Some(window_icon()),
+        #[cfg(target_os = "linux")]
+        window_decorations: Some(WindowDecorations::Client),
+        ..Default::default()
+    };
+
+    app.open_window(options, move |window, app| {
+        window.set_window_title(MODELS_WINDOW_TITLE);
+        let models_window = app.new(|cx| ModelsWindow::new(controller, cx));
+        app.new(|cx| Root::new(models_window, window, cx))
+    })
+    .context("open models window")
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit_index = 0;
+    while value >= 1024.0 && unit_index + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit_index += 1;
+    }
+
+    if unit_index == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit_index])
+    }
+}
+
+fn format_last_used(last_used: SystemTime) -> String {
+    let Ok(elapsed) = SystemTime::now().duration_since(last_used) else {
+        return "just now".to_string();
+    };
+
+    let minutes = elapsed.as_secs() / 60;
+    match minutes {
+        0 => "just now".to_string(),
+        1..60 => format!("{minutes} min ago"),
+        60..1440 => format!("{} h ago", minutes / 60),
+        _ => format!("{} days ago", minutes / 1440),
+    }
+}
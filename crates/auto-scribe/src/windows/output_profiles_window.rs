@@ -0,0 +1,432 @@
+use crate::{
+    MINIMUM_HEIGHT, MINIMUM_WIDTH,
+    hotkey::Controller,
+    icon::{APP_ID, window_icon},
+    output::{OutputMethod, OutputProfile, TextStage},
+    windows::window_resize_handles,
+};
+
+use crate::error::{AppResult, ResultContext};
+use gpui::{
+    AnyElement, App, AppContext, Context, Entity, InteractiveElement, IntoElement, ParentElement,
+    Render, StatefulInteractiveElement, Styled, Window, WindowBounds, WindowHandle, WindowOptions,
+    div, px, rgb, size,
+};
+use gpui_component::{
+    ActiveTheme, IconName, Root, StyledExt, TitleBar,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputState},
+    switch::Switch,
+    v_flex,
+};
+
+#[cfg(target_os = "linux")]
+use gpui::WindowDecorations;
+
+const OUTPUT_PROFILES_WINDOW_TITLE: &str = "Output Profiles";
+const OUTPUT_PROFILES_WINDOW_WIDTH: f32 = 620.0;
+const OUTPUT_PROFILES_WINDOW_HEIGHT: f32 = 640.0;
+
+pub(crate) struct OutputProfilesWindow {
+    controller: Entity<Controller>,
+    /// Index of the profile being edited, `None` for a new one, with its unsaved choices.
+    /// The text fields live in the inputs below until the profile is saved.
+    editing: Option<(Option<usize>, OutputProfile)>,
+    name_input: Entity<InputState>,
+    apps_input: Entity<InputState>,
+    line_prefix_input: Entity<InputState>,
+}
+
+impl OutputProfilesWindow {
+    pub(crate) fn new(
+        controller: Entity<Controller>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        cx.observe(&controller, |_, _, cx| cx.notify()).detach();
+        let name_input = cx.new(|cx| InputState::new(window, cx).placeholder("Profile name"));
+        let apps_input = cx.new(|cx| {
+            InputState::new(window, cx).placeholder("App IDs or WM_CLASS names, comma-separated")
+        });
+        let line_prefix_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Line prefix, such as // "));
+        Self {
+            controller,
+            editing: None,
+            name_input,
+            apps_input,
+            line_prefix_input,
+        }
+    }
+
+    fn start_editing(
+        &mut self,
+        index: Option<usize>,
+        profile: OutputProfile,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.name_input.update(cx, |input, cx| {
+            input.set_value(profile.name.clone(), window, cx);
+            input.focus(window, cx);
+        });
+        self.apps_input.update(cx, |input, cx| {
+            input.set_value(profile.apps.join(", "), window, cx);
+        });
+        self.line_prefix_input.update(cx, |input, cx| {
+            input.set_value(profile.line_prefix.clone(), window, cx);
+        });
+        self.editing = Some((index, profile));
+        cx.notify();
+    }
+
+    fn save_editing(&mut self, cx: &mut Context<Self>) {
+        let Some((index, mut profile)) = self.editing.take() else {
+            return;
+        };
+
+        let name = self.name_input.read(cx).value().trim().to_string();
+        if !name.is_empty() {
+            profile.name = name;
+        }
+        profile.apps = self
+            .apps_input
+            .read(cx)
+            .value()
+            .split(',')
+            .map(str::trim)
+            .filter(|app| !app.is_empty())
+            .map(str::to_string)
+            .collect();
+        profile.line_prefix = self.line_prefix_input.read(cx).value().to_string();
+
+        self.controller.update(cx, |controller, cx| {
+            controller.save_output_profile(index, profile, cx);
+        });
+        cx.notify();
+    }
+
+    fn update_editing(&mut self, cx: &mut Context<Self>, change: impl FnOnce(&mut OutputProfile)) {
+        if let Some((_, profile)) = &mut self.editing {
+            change(profile);
+            cx.notify();
+        }
+    }
+
+    fn profile_row(
+        &self,
+        index: usize,
+        profile: &OutputProfile,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let stages = if profile.stages.is_empty() {
+            "no stages".to_string()
+        } else {
+            profile
+                .stages
+                .iter()
+                .map(|stage| stage.label())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let apps = if profile.apps.is_empty() {
+            "No apps; never matched".to_string()
+        } else {
+            profile.apps.join(", ")
+        };
+        let edited_profile = profile.clone();
+
+        v_flex()
+            .w_full()
+            .gap_1()
+            .border_1()
+            .border_color(rgb(0x334155))
+            .rounded_md()
+            .p_3()
+            .child(
+                h_flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_3()
+                    .child(div().text_sm().font_semibold().child(profile.name.clone()))
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(
+                                Button::new(("edit-output-profile", index))
+                                    .label("Edit")
+                                    .on_click(cx.listener(move |this, _, window, cx| {
+                                        this.start_editing(
+                                            Some(index),
+                                            edited_profile.clone(),
+                                            window,
+                                            cx,
+                                        );
+                                    })),
+                            )
+                            .child(
+                                Button::new(("delete-output-profile", index))
+                                    .ghost()
+                                    .icon(IconName::Delete)
+                                    .label("Delete")
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.controller.update(cx, |controller, cx| {
+                                            controller.delete_output_profile(index, cx);
+                                        });
+                                    })),
+                            ),
+                    ),
+            )
+            .child(div().text_xs().text_color(rgb(0x94a3b8)).child(apps))
+            .child(div().text_xs().text_color(rgb(0x94a3b8)).child(format!(
+                "{} · {stages}{}{}",
+                profile.output.label(),
+                if profile.line_prefix.is_empty() {
+                    String::new()
+                } else {
+                    format!(" · prefix {:?}", profile.line_prefix)
+                },
+                if profile.trailing_newline {
+                    " · trailing newline"
+                } else {
+                    ""
+                }
+            )))
+            .into_any_element()
+    }
+
+    fn editor(&self, profile: &OutputProfile, cx: &mut Context<Self>) -> AnyElement {
+        let output_buttons = OutputMethod::ALL
+            .into_iter()
+            .enumerate()
+            .map(|(index, method)| {
+                let button = Button::new(("output-profile-method", index))
+                    .label(method.label())
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.update_editing(cx, |profile| profile.output = method);
+                    }));
+                if method == profile.output {
+                    button.primary()
+                } else {
+                    button.ghost()
+                }
+            });
+        let stage_buttons = TextStage::ALL
+            .into_iter()
+            .enumerate()
+            .map(|(index, stage)| {
+                let button = Button::new(("output-profile-stage", index))
+                    .label(stage.label())
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.update_editing(cx, |profile| {
+                            let enabled = !profile.stages.contains(&stage);
+                            profile.stages = TextStage::ALL
+                                .into_iter()
+                                .filter(|candidate| {
+                                    if *candidate == stage {
+                                        enabled
+                                    } else {
+                                        profile.stages.contains(candidate)
+                                    }
+                                })
+                                .collect();
+                        });
+                    }));
+                if profile.stages.contains(&stage) {
+                    button.primary()
+                } else {
+                    button.ghost()
+                }
+            });
+
+        v_flex()
+            .w_full()
+            .gap_3()
+            .border_1()
+            .border_color(rgb(0x38bdf8))
+            .rounded_md()
+            .p_3()
+            .child(Input::new(&self.name_input))
+            .child(Input::new(&self.apps_input))
+            .child(editor_row(
+                "Output",
+                h_flex().gap_1().children(output_buttons),
+            ))
+            .child(editor_row(
+                "Stages",
+                h_flex().flex_wrap().gap_1().children(stage_buttons),
+            ))
+            .child(Input::new(&self.line_prefix_input))
+            .child(editor_row(
+                "Trailing newline",
+                Switch::new("output-profile-trailing-newline")
+                    .checked(profile.trailing_newline)
+                    .on_click(cx.listener(|this, enabled: &bool, _, cx| {
+                        let enabled = *enabled;
+                        this.update_editing(cx, |profile| profile.trailing_newline = enabled);
+                    })),
+            ))
+            .child(
+                h_flex()
+                    .justify_end()
+                    .gap_2()
+                    .child(
+                        Button::new("cancel-output-profile")
+                            .label("Cancel")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.editing = None;
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        Button::new("save-output-profile")
+                            .primary()
+                            .icon(IconName::Check)
+                            .label("Save")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.save_editing(cx);
+                            })),
+                    ),
+            )
+            .into_any_element()
+    }
+}
+
+impl Render for OutputProfilesWindow {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let (profiles, status) = self.controller.read_with(cx, |controller, _| {
+            (controller.output_profiles(), controller.snapshot().status)
+        });
+
+        let title_bar = TitleBar::new();
+        #[cfg(not(target_os = "macos"))]
+        let title_bar = title_bar.child(OUTPUT_PROFILES_WINDOW_TITLE);
+
+        let editing = self.editing.clone();
+        let new_profile_editor = editing
+            .as_ref()
+            .filter(|(index, _)| index.is_none())
+            .map(|(_, profile)| self.editor(profile, cx));
+        let mut profile_rows = profiles
+            .iter()
+            .enumerate()
+            .map(|(index, profile)| match &editing {
+                Some((Some(editing_index), edited)) if *editing_index == index => {
+                    self.editor(edited, cx)
+                }
+                _ => self.profile_row(index, profile, cx),
+            })
+            .collect::<Vec<_>>();
+        if profile_rows.is_empty() && new_profile_editor.is_none() {
+            profile_rows.push(
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child("No profiles; every transcript is copied to the clipboard as is")
+                    .into_any_element(),
+            );
+        }
+        let next_profile_name = format!("Profile {}", profiles.len() + 1);
+
+        v_flex()
+            .relative()
+            .size_full()
+            .bg(cx.theme().background)
+            .text_color(cx.theme().foreground)
+            .child(title_bar)
+            .child(
+                v_flex()
+                    .id("output-profiles-window-body")
+                    .size_full()
+                    .gap_3()
+                    .p_6()
+                    .border_t_1()
+                    .border_color(cx.theme().border)
+                    .overflow_y_scroll()
+                    .child(
+                        h_flex()
+                            .items_start()
+                            .justify_between()
+                            .gap_3()
+                            .child(
+                                v_flex()
+                                    .gap_1()
+                                    .child(div().text_xl().font_semibold().child("Output Profiles"))
+                                    .child(
+                                        div()
+                                            .text_xs()
+                                            .text_color(cx.theme().muted_foreground)
+                                            .child(
+                                                "The first profile listing the focused window's app ID or WM_CLASS decides how a transcript is delivered",
+                                            ),
+                                    )
+                                    .child(
+                                        div()
+                                            .text_xs()
+                                            .text_color(cx.theme().muted_foreground)
+                                            .child(status),
+                                    ),
+                            )
+                            .child(
+                                Button::new("add-output-profile")
+                                    .primary()
+                                    .label("Add profile")
+                                    .on_click(cx.listener(move |this, _, window, cx| {
+                                        this.start_editing(
+                                            None,
+                                            OutputProfile::new(next_profile_name.clone()),
+                                            window,
+                                            cx,
+                                        );
+                                    })),
+                            ),
+                    )
+                    .children(new_profile_editor)
+                    .children(profile_rows),
+            )
+            .child(window_resize_handles(window))
+    }
+}
+
+fn editor_row(label: &'static str, control: impl IntoElement) -> AnyElement {
+    h_flex()
+        .w_full()
+        .items_center()
+        .justify_between()
+        .gap_3()
+        .child(div().text_sm().text_color(rgb(0x94a3b8)).child(label))
+        .child(control)
+        .into_any_element()
+}
+
+pub(crate) fn open_output_profiles_window(
+    app: &mut App,
+    controller: Entity<Controller>,
+) -> AppResult<WindowHandle<Root>> {
+    let window_bounds = WindowBounds::centered(
+        size(
+            px(OUTPUT_PROFILES_WINDOW_WIDTH),
+            px(OUTPUT_PROFILES_WINDOW_HEIGHT),
+        ),
+        app,
+    );
+    let options = WindowOptions {
+        window_bounds: Some(window_bounds),
+        window_min_size: Some(size(px(MINIMUM_WIDTH), px(MINIMUM_HEIGHT))),
+        titlebar: Some(TitleBar::title_bar_options()),
+        app_id: Some(APP_ID.to_string()),
+        icon: Some(window_icon()),
+        #[cfg(target_os = "linux")]
+        window_decorations: Some(WindowDecorations::Client),
+        ..Default::default()
+    };
+
+    app.open_window(options, move |window, app| {
+        window.set_window_title(OUTPUT_PROFILES_WINDOW_TITLE);
+        let output_profiles_window =
+            app.new(|cx| OutputProfilesWindow::new(controller, window, cx));
+        app.new(|cx| Root::new(output_profiles_window, window, cx))
+    })
+    .context("open output profiles window")
+}
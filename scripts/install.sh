@@ -6,6 +6,7 @@ usage() {
 Usage:
   ./install.sh
   ./install.sh auto-scribe-linux-x86_64-cuda.tar.gz
+  ./install.sh auto-scribe-linux-x86_64-cpu.tar.gz
 
 Run without arguments from an extracted Auto Scribe release directory, or pass
 the release archive path and this script will extract it before installing.
@@ -71,7 +72,7 @@ fi
 
 shopt -s nullglob
 provider_libs=("${source_dir}"/libonnxruntime*.so*)
-if [[ "${#provider_libs[@]}" -eq 0 ]]; then
+if [[ "${#provider_libs[@]}" -eq 0 && "$(basename "${source_dir}")" == *-cuda ]]; then
     echo "error: no libonnxruntime*.so* files were found in the release payload" >&2
     exit 1
 fi